@@ -178,6 +178,14 @@ pub fn config_bool(key: &str) -> Option<bool> {
     RAW.get().and_then(|cfg| cfg.get_bool(key).ok())
 }
 
+/// Deserializes a whole section of the raw config (e.g. "workflow") into `T`
+/// None if the section is missing or empty, an error if it is there but does not fit `T`
+pub fn config_section<T: DeserializeOwned>(key: &str) -> Option<Result<T, String>> {
+    let cfg = RAW.get()?;
+    cfg.get_table(key).ok().filter(|table| !table.is_empty())?;
+    Some(cfg.get::<T>(key).map_err(|e| e.to_string()))
+}
+
 // = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
 // Unit tests for config module
 // = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
@@ -28,6 +28,24 @@ pub const API_VERSION: &str = "1.0.0";
 pub enum ApproveTradeResponse {
     /// Trade approved
     Status204_TradeApproved,
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Action not permitted
+    Status403_ActionNotPermitted(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
+    /// Version conflict
+    Status412_VersionConflict(models::ErrorResponse),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum BatchTradesResponse {
+    /// One result per command, in order
+    Status200_OneResultPerCommand(models::TradeBatchResponse),
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -36,6 +54,14 @@ pub enum ApproveTradeResponse {
 pub enum BookTradeResponse {
     /// Trade booked
     Status204_TradeBooked,
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Action not permitted
+    Status403_ActionNotPermitted(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
+    /// Version conflict
+    Status412_VersionConflict(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -44,6 +70,14 @@ pub enum BookTradeResponse {
 pub enum CancelTradeResponse {
     /// Trade cancelled
     Status204_TradeCancelled,
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Action not permitted
+    Status403_ActionNotPermitted(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
+    /// Version conflict
+    Status412_VersionConflict(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -52,6 +86,10 @@ pub enum CancelTradeResponse {
 pub enum CreateTradeResponse {
     /// Trade created
     Status200_TradeCreated(models::TradeCreateResponse),
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Action not permitted
+    Status403_ActionNotPermitted(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -59,7 +97,11 @@ pub enum CreateTradeResponse {
 #[allow(clippy::large_enum_variant)]
 pub enum GetTradeDetailsResponse {
     /// Full trade details
-    Status200_FullTradeDetails(models::TradeDetails),
+    Status200_FullTradeDetails { body: models::TradeDetails, e_tag: String },
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -76,6 +118,10 @@ pub enum GetTradeHistoryResponse {
 pub enum GetTradeStatusResponse {
     /// Current trade status
     Status200_CurrentTradeStatus(models::TradeStatus),
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -90,8 +136,26 @@ pub enum HelloResponse {
 #[must_use]
 #[allow(clippy::large_enum_variant)]
 pub enum ListTradesResponse {
-    /// List of trade IDs
-    Status200_ListOfTradeIDs(Vec<String>),
+    /// A page of trades
+    Status200_APageOfTrades(models::TradeListPage),
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[must_use]
+#[allow(clippy::large_enum_variant)]
+pub enum RevertTradeResponse {
+    /// Trade reverted
+    Status204_TradeReverted,
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Action not permitted
+    Status403_ActionNotPermitted(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
+    /// Version conflict
+    Status412_VersionConflict(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -100,6 +164,14 @@ pub enum ListTradesResponse {
 pub enum SendTradeResponse {
     /// Trade sent
     Status204_TradeSent,
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Action not permitted
+    Status403_ActionNotPermitted(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
+    /// Version conflict
+    Status412_VersionConflict(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -108,6 +180,14 @@ pub enum SendTradeResponse {
 pub enum SubmitTradeResponse {
     /// Trade submitted
     Status204_TradeSubmitted,
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Action not permitted
+    Status403_ActionNotPermitted(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
+    /// Version conflict
+    Status412_VersionConflict(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -116,6 +196,10 @@ pub enum SubmitTradeResponse {
 pub enum TradeDiffResponse {
     /// Field differences between two versions
     Status200_FieldDifferencesBetweenTwoVersions(models::TradeDiff),
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -124,6 +208,14 @@ pub enum TradeDiffResponse {
 pub enum UpdateTradeResponse {
     /// Trade updated
     Status204_TradeUpdated,
+    /// Request rejected
+    Status400_RequestRejected(models::ErrorResponse),
+    /// Action not permitted
+    Status403_ActionNotPermitted(models::ErrorResponse),
+    /// Trade not found
+    Status404_TradeNotFound(models::ErrorResponse),
+    /// Version conflict
+    Status412_VersionConflict(models::ErrorResponse),
 }
 
 /// API
@@ -138,9 +230,22 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: models::ApproveTradeHeaderParams,
         path_params: models::ApproveTradePathParams,
     ) -> Result<ApproveTradeResponse, String>;
 
+    /// Run several trade actions in one call.
+    ///
+    /// BatchTrades - POST /trade/batch
+    async fn batch_trades(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        header_params: models::BatchTradesHeaderParams,
+        body: models::TradeBatchRequest,
+    ) -> Result<BatchTradesResponse, String>;
+
     /// Mark a trade as executed.
     ///
     /// BookTrade - POST /trade/{id}/book
@@ -149,7 +254,9 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: models::BookTradeHeaderParams,
         path_params: models::BookTradePathParams,
+        body: models::ExecutionConfirmation,
     ) -> Result<BookTradeResponse, String>;
 
     /// Cancel a trade.
@@ -160,6 +267,7 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: models::CancelTradeHeaderParams,
         path_params: models::CancelTradePathParams,
     ) -> Result<CancelTradeResponse, String>;
 
@@ -171,6 +279,7 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: models::CreateTradeHeaderParams,
         body: models::TradeCreateRequest,
     ) -> Result<CreateTradeResponse, String>;
 
@@ -205,6 +314,7 @@ pub trait Api {
         host: Host,
         cookies: CookieJar,
         path_params: models::GetTradeStatusPathParams,
+        query_params: models::GetTradeStatusQueryParams,
     ) -> Result<GetTradeStatusResponse, String>;
 
     /// Hello World endpoint.
@@ -212,7 +322,7 @@ pub trait Api {
     /// Hello - GET /hello
     async fn hello(&self, method: Method, host: Host, cookies: CookieJar) -> Result<HelloResponse, String>;
 
-    /// List trade IDs.
+    /// List trades, filtered, sorted and paged.
     ///
     /// ListTrades - GET /trade
     async fn list_trades(
@@ -223,6 +333,19 @@ pub trait Api {
         query_params: models::ListTradesQueryParams,
     ) -> Result<ListTradesResponse, String>;
 
+    /// Revert a trade to the details of an earlier version.
+    ///
+    /// RevertTrade - POST /trade/{id}/revert
+    async fn revert_trade(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        header_params: models::RevertTradeHeaderParams,
+        path_params: models::RevertTradePathParams,
+        query_params: models::RevertTradeQueryParams,
+    ) -> Result<RevertTradeResponse, String>;
+
     /// Send trade to counterparty.
     ///
     /// SendTrade - POST /trade/{id}/send
@@ -231,6 +354,7 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: models::SendTradeHeaderParams,
         path_params: models::SendTradePathParams,
     ) -> Result<SendTradeResponse, String>;
 
@@ -242,6 +366,7 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: models::SubmitTradeHeaderParams,
         path_params: models::SubmitTradePathParams,
     ) -> Result<SubmitTradeResponse, String>;
 
@@ -265,6 +390,7 @@ pub trait Api {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: models::UpdateTradeHeaderParams,
         path_params: models::UpdateTradePathParams,
         body: models::TradeDetails,
     ) -> Result<UpdateTradeResponse, String>;
@@ -7,24 +7,71 @@ use validator::Validate;
 use crate::header;
 use crate::{models, types::*};
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct ApproveTradeHeaderParams {
+    pub x_user_id: String,
+    /// ETag from GET /trade/{id}/details, the action is refused if the trade has changed since
+    pub if_match: Option<String>,
+    /// Reason for the action, kept in the trade history
+    pub x_comment: Option<String>,
+    /// Retries sent with the same key get the first outcome back, instead of running again
+    pub idempotency_key: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct ApproveTradePathParams {
     pub id: String,
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct BatchTradesHeaderParams {
+    pub x_user_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct BookTradeHeaderParams {
+    pub x_user_id: String,
+    /// ETag from GET /trade/{id}/details, the action is refused if the trade has changed since
+    pub if_match: Option<String>,
+    /// Retries sent with the same key get the first outcome back, instead of running again
+    pub idempotency_key: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct BookTradePathParams {
     pub id: String,
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct CancelTradeHeaderParams {
+    pub x_user_id: String,
+    /// ETag from GET /trade/{id}/details, the action is refused if the trade has changed since
+    pub if_match: Option<String>,
+    /// Reason for the action, kept in the trade history
+    pub x_comment: Option<String>,
+    /// Retries sent with the same key get the first outcome back, instead of running again
+    pub idempotency_key: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct CancelTradePathParams {
     pub id: String,
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct CreateTradeHeaderParams {
+    /// Retries sent with the same key get the first outcome back, instead of running again
+    pub idempotency_key: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct GetTradeDetailsPathParams {
@@ -43,12 +90,109 @@ pub struct GetTradeStatusPathParams {
     pub id: String,
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct GetTradeStatusQueryParams {
+    /// Point in time to look at (RFC 3339), 404 if the trade did not exist yet
+    #[serde(rename = "as_of")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct ListTradesQueryParams {
+    /// Deprecated, results are always sorted (see sort_by)
     #[serde(rename = "sort")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sort: Option<bool>,
+    #[serde(rename = "state")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(rename = "counterparty")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counterparty: Option<String>,
+    #[serde(rename = "trading_entity")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trading_entity: Option<String>,
+    #[serde(rename = "notional_currency")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notional_currency: Option<String>,
+    /// Note: inline enums are not fully supported by openapi-generator
+    #[serde(rename = "direction")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<String>,
+    #[serde(rename = "requester")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requester: Option<String>,
+    /// Created at or after (inclusive)
+    #[serde(rename = "created_from")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Created before (exclusive)
+    #[serde(rename = "created_to")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_to: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(rename = "notional_min")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notional_min: Option<f64>,
+    #[serde(rename = "notional_max")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notional_max: Option<f64>,
+    /// Note: inline enums are not fully supported by openapi-generator
+    #[serde(rename = "sort_by")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_by: Option<String>,
+    /// Note: inline enums are not fully supported by openapi-generator
+    #[serde(rename = "order")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<String>,
+    #[serde(rename = "limit")]
+    #[validate(range(min = 1, max = 1000))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+    /// `nextCursor` from the previous page
+    #[serde(rename = "cursor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct RevertTradeHeaderParams {
+    pub x_user_id: String,
+    /// ETag from GET /trade/{id}/details, the revert is refused if the trade has changed since
+    pub if_match: Option<String>,
+    /// Reason for the action, kept in the trade history
+    pub x_comment: Option<String>,
+    /// Retries sent with the same key get the first outcome back, instead of running again
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct RevertTradePathParams {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct RevertTradeQueryParams {
+    /// Version whose details are brought back
+    #[serde(rename = "version")]
+    pub version: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct SendTradeHeaderParams {
+    pub x_user_id: String,
+    /// ETag from GET /trade/{id}/details, the action is refused if the trade has changed since
+    pub if_match: Option<String>,
+    /// Reason for the action, kept in the trade history
+    pub x_comment: Option<String>,
+    /// Retries sent with the same key get the first outcome back, instead of running again
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
@@ -57,6 +201,16 @@ pub struct SendTradePathParams {
     pub id: String,
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct SubmitTradeHeaderParams {
+    pub x_user_id: String,
+    /// ETag from GET /trade/{id}/details, the action is refused if the trade has changed since
+    pub if_match: Option<String>,
+    /// Retries sent with the same key get the first outcome back, instead of running again
+    pub idempotency_key: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct SubmitTradePathParams {
@@ -78,6 +232,18 @@ pub struct TradeDiffQueryParams {
     pub v2: i32,
 }
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct UpdateTradeHeaderParams {
+    pub x_user_id: String,
+    /// ETag from GET /trade/{id}/details, the update is refused if the trade has changed since
+    pub if_match: Option<String>,
+    /// Reason for the action, kept in the trade history
+    pub x_comment: Option<String>,
+    /// Retries sent with the same key get the first outcome back, instead of running again
+    pub idempotency_key: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
 pub struct UpdateTradePathParams {
@@ -86,35 +252,41 @@ pub struct UpdateTradePathParams {
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct HelloResponse {
+pub struct ErrorResponse {
+    #[serde(rename = "code")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
 }
 
-impl HelloResponse {
+impl ErrorResponse {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new() -> HelloResponse {
-        HelloResponse { message: None }
+    pub fn new() -> ErrorResponse {
+        ErrorResponse { code: None, message: None }
     }
 }
 
-/// Converts the HelloResponse value to the Query Parameters representation (style=form, explode=false)
+/// Converts the ErrorResponse value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::fmt::Display for HelloResponse {
+impl std::fmt::Display for ErrorResponse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let params: Vec<Option<String>> =
-            vec![self.message.as_ref().map(|message| ["message".to_string(), message.to_string()].join(","))];
+        let params: Vec<Option<String>> = vec![
+            self.code.as_ref().map(|code| ["code".to_string(), code.to_string()].join(",")),
+            self.message.as_ref().map(|message| ["message".to_string(), message.to_string()].join(",")),
+        ];
 
         write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a HelloResponse value
+/// Converts Query Parameters representation (style=form, explode=false) to a ErrorResponse value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for HelloResponse {
+impl std::str::FromStr for ErrorResponse {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -122,6 +294,7 @@ impl std::str::FromStr for HelloResponse {
         #[derive(Default)]
         #[allow(dead_code)]
         struct IntermediateRep {
+            pub code: Vec<String>,
             pub message: Vec<String>,
         }
 
@@ -134,17 +307,21 @@ impl std::str::FromStr for HelloResponse {
         while key_result.is_some() {
             let val = match string_iter.next() {
                 Some(x) => x,
-                None => return std::result::Result::Err("Missing value while parsing HelloResponse".to_string()),
+                None => return std::result::Result::Err("Missing value while parsing ErrorResponse".to_string()),
             };
 
             if let Some(key) = key_result {
                 #[allow(clippy::match_single_binding)]
                 match key {
                     #[allow(clippy::redundant_clone)]
+                    "code" => intermediate_rep
+                        .code
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
                     "message" => intermediate_rep
                         .message
                         .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    _ => return std::result::Result::Err("Unexpected key while parsing HelloResponse".to_string()),
+                    _ => return std::result::Result::Err("Unexpected key while parsing ErrorResponse".to_string()),
                 }
             }
 
@@ -153,22 +330,25 @@ impl std::str::FromStr for HelloResponse {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(HelloResponse { message: intermediate_rep.message.into_iter().next() })
+        std::result::Result::Ok(ErrorResponse {
+            code: intermediate_rep.code.into_iter().next(),
+            message: intermediate_rep.message.into_iter().next(),
+        })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<HelloResponse> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<ErrorResponse> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<HelloResponse>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<ErrorResponse>> for HeaderValue {
     type Error = String;
 
-    fn try_from(hdr_value: header::IntoHeaderValue<HelloResponse>) -> std::result::Result<Self, Self::Error> {
+    fn try_from(hdr_value: header::IntoHeaderValue<ErrorResponse>) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for HelloResponse - value: {} is invalid {}",
+                "Invalid header value for ErrorResponse - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -176,15 +356,15 @@ impl std::convert::TryFrom<header::IntoHeaderValue<HelloResponse>> for HeaderVal
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<HelloResponse> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<ErrorResponse> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
-            std::result::Result::Ok(value) => match <HelloResponse as std::str::FromStr>::from_str(value) {
+            std::result::Result::Ok(value) => match <ErrorResponse as std::str::FromStr>::from_str(value) {
                 std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
                 std::result::Result::Err(err) => std::result::Result::Err(format!(
-                    "Unable to convert header value '{}' into HelloResponse - {}",
+                    "Unable to convert header value '{}' into ErrorResponse - {}",
                     value, err
                 )),
             },
@@ -197,41 +377,66 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<HelloRespons
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct TradeCreateRequest {
-    #[serde(rename = "userId")]
+pub struct ExecutionConfirmation {
+    #[serde(rename = "rate")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub user_id: Option<String>,
+    pub rate: Option<f64>,
 
-    #[serde(rename = "details")]
+    #[serde(rename = "executed_at")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub details: Option<models::TradeDetails>,
+    pub executed_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    #[serde(rename = "counterparty_ref")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counterparty_ref: Option<String>,
+
+    #[serde(rename = "executed_notional")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executed_notional: Option<f64>,
+
+    #[serde(rename = "far_rate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub far_rate: Option<f64>,
 }
 
-impl TradeCreateRequest {
+impl ExecutionConfirmation {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new() -> TradeCreateRequest {
-        TradeCreateRequest { user_id: None, details: None }
+    pub fn new() -> ExecutionConfirmation {
+        ExecutionConfirmation {
+            rate: None,
+            executed_at: None,
+            counterparty_ref: None,
+            executed_notional: None,
+            far_rate: None,
+        }
     }
 }
 
-/// Converts the TradeCreateRequest value to the Query Parameters representation (style=form, explode=false)
+/// Converts the ExecutionConfirmation value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::fmt::Display for TradeCreateRequest {
+impl std::fmt::Display for ExecutionConfirmation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let params: Vec<Option<String>> = vec![
-            self.user_id.as_ref().map(|user_id| ["userId".to_string(), user_id.to_string()].join(",")),
-            // Skipping details in query parameter serialization
+            self.rate.as_ref().map(|rate| ["rate".to_string(), rate.to_string()].join(",")),
+            // Skipping executed_at in query parameter serialization
+            self.counterparty_ref
+                .as_ref()
+                .map(|counterparty_ref| ["counterparty_ref".to_string(), counterparty_ref.to_string()].join(",")),
+            self.executed_notional
+                .as_ref()
+                .map(|executed_notional| ["executed_notional".to_string(), executed_notional.to_string()].join(",")),
+            self.far_rate.as_ref().map(|far_rate| ["far_rate".to_string(), far_rate.to_string()].join(",")),
         ];
 
         write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a TradeCreateRequest value
+/// Converts Query Parameters representation (style=form, explode=false) to a ExecutionConfirmation value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for TradeCreateRequest {
+impl std::str::FromStr for ExecutionConfirmation {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -239,8 +444,11 @@ impl std::str::FromStr for TradeCreateRequest {
         #[derive(Default)]
         #[allow(dead_code)]
         struct IntermediateRep {
-            pub user_id: Vec<String>,
-            pub details: Vec<models::TradeDetails>,
+            pub rate: Vec<f64>,
+            pub executed_at: Vec<chrono::DateTime<chrono::Utc>>,
+            pub counterparty_ref: Vec<String>,
+            pub executed_notional: Vec<f64>,
+            pub far_rate: Vec<f64>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -252,22 +460,39 @@ impl std::str::FromStr for TradeCreateRequest {
         while key_result.is_some() {
             let val = match string_iter.next() {
                 Some(x) => x,
-                None => return std::result::Result::Err("Missing value while parsing TradeCreateRequest".to_string()),
+                None => {
+                    return std::result::Result::Err("Missing value while parsing ExecutionConfirmation".to_string())
+                }
             };
 
             if let Some(key) = key_result {
                 #[allow(clippy::match_single_binding)]
                 match key {
                     #[allow(clippy::redundant_clone)]
-                    "userId" => intermediate_rep
-                        .user_id
+                    "rate" => intermediate_rep
+                        .rate
+                        .push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "executed_at" => intermediate_rep.executed_at.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "counterparty_ref" => intermediate_rep
+                        .counterparty_ref
                         .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
                     #[allow(clippy::redundant_clone)]
-                    "details" => intermediate_rep
-                        .details
-                        .push(<models::TradeDetails as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    "executed_notional" => intermediate_rep
+                        .executed_notional
+                        .push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "far_rate" => intermediate_rep
+                        .far_rate
+                        .push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
                     _ => {
-                        return std::result::Result::Err("Unexpected key while parsing TradeCreateRequest".to_string())
+                        return std::result::Result::Err(
+                            "Unexpected key while parsing ExecutionConfirmation".to_string(),
+                        )
                     }
                 }
             }
@@ -277,25 +502,28 @@ impl std::str::FromStr for TradeCreateRequest {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(TradeCreateRequest {
-            user_id: intermediate_rep.user_id.into_iter().next(),
-            details: intermediate_rep.details.into_iter().next(),
+        std::result::Result::Ok(ExecutionConfirmation {
+            rate: intermediate_rep.rate.into_iter().next(),
+            executed_at: intermediate_rep.executed_at.into_iter().next(),
+            counterparty_ref: intermediate_rep.counterparty_ref.into_iter().next(),
+            executed_notional: intermediate_rep.executed_notional.into_iter().next(),
+            far_rate: intermediate_rep.far_rate.into_iter().next(),
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<TradeCreateRequest> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<ExecutionConfirmation> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<TradeCreateRequest>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<ExecutionConfirmation>> for HeaderValue {
     type Error = String;
 
-    fn try_from(hdr_value: header::IntoHeaderValue<TradeCreateRequest>) -> std::result::Result<Self, Self::Error> {
+    fn try_from(hdr_value: header::IntoHeaderValue<ExecutionConfirmation>) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for TradeCreateRequest - value: {} is invalid {}",
+                "Invalid header value for ExecutionConfirmation - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -303,15 +531,15 @@ impl std::convert::TryFrom<header::IntoHeaderValue<TradeCreateRequest>> for Head
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeCreateRequest> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<ExecutionConfirmation> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
-            std::result::Result::Ok(value) => match <TradeCreateRequest as std::str::FromStr>::from_str(value) {
+            std::result::Result::Ok(value) => match <ExecutionConfirmation as std::str::FromStr>::from_str(value) {
                 std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
                 std::result::Result::Err(err) => std::result::Result::Err(format!(
-                    "Unable to convert header value '{}' into TradeCreateRequest - {}",
+                    "Unable to convert header value '{}' into ExecutionConfirmation - {}",
                     value, err
                 )),
             },
@@ -324,35 +552,35 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeCreateR
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct TradeCreateResponse {
-    #[serde(rename = "tradeId")]
+pub struct HelloResponse {
+    #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub trade_id: Option<String>,
+    pub message: Option<String>,
 }
 
-impl TradeCreateResponse {
+impl HelloResponse {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new() -> TradeCreateResponse {
-        TradeCreateResponse { trade_id: None }
+    pub fn new() -> HelloResponse {
+        HelloResponse { message: None }
     }
 }
 
-/// Converts the TradeCreateResponse value to the Query Parameters representation (style=form, explode=false)
+/// Converts the HelloResponse value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::fmt::Display for TradeCreateResponse {
+impl std::fmt::Display for HelloResponse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let params: Vec<Option<String>> =
-            vec![self.trade_id.as_ref().map(|trade_id| ["tradeId".to_string(), trade_id.to_string()].join(","))];
+            vec![self.message.as_ref().map(|message| ["message".to_string(), message.to_string()].join(","))];
 
         write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a TradeCreateResponse value
+/// Converts Query Parameters representation (style=form, explode=false) to a HelloResponse value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for TradeCreateResponse {
+impl std::str::FromStr for HelloResponse {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -360,7 +588,7 @@ impl std::str::FromStr for TradeCreateResponse {
         #[derive(Default)]
         #[allow(dead_code)]
         struct IntermediateRep {
-            pub trade_id: Vec<String>,
+            pub message: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -372,19 +600,17 @@ impl std::str::FromStr for TradeCreateResponse {
         while key_result.is_some() {
             let val = match string_iter.next() {
                 Some(x) => x,
-                None => return std::result::Result::Err("Missing value while parsing TradeCreateResponse".to_string()),
+                None => return std::result::Result::Err("Missing value while parsing HelloResponse".to_string()),
             };
 
             if let Some(key) = key_result {
                 #[allow(clippy::match_single_binding)]
                 match key {
                     #[allow(clippy::redundant_clone)]
-                    "tradeId" => intermediate_rep
-                        .trade_id
+                    "message" => intermediate_rep
+                        .message
                         .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    _ => {
-                        return std::result::Result::Err("Unexpected key while parsing TradeCreateResponse".to_string())
-                    }
+                    _ => return std::result::Result::Err("Unexpected key while parsing HelloResponse".to_string()),
                 }
             }
 
@@ -393,22 +619,22 @@ impl std::str::FromStr for TradeCreateResponse {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(TradeCreateResponse { trade_id: intermediate_rep.trade_id.into_iter().next() })
+        std::result::Result::Ok(HelloResponse { message: intermediate_rep.message.into_iter().next() })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<TradeCreateResponse> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<HelloResponse> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<TradeCreateResponse>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<HelloResponse>> for HeaderValue {
     type Error = String;
 
-    fn try_from(hdr_value: header::IntoHeaderValue<TradeCreateResponse>) -> std::result::Result<Self, Self::Error> {
+    fn try_from(hdr_value: header::IntoHeaderValue<HelloResponse>) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for TradeCreateResponse - value: {} is invalid {}",
+                "Invalid header value for HelloResponse - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -416,15 +642,15 @@ impl std::convert::TryFrom<header::IntoHeaderValue<TradeCreateResponse>> for Hea
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeCreateResponse> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<HelloResponse> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
-            std::result::Result::Ok(value) => match <TradeCreateResponse as std::str::FromStr>::from_str(value) {
+            std::result::Result::Ok(value) => match <HelloResponse as std::str::FromStr>::from_str(value) {
                 std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
                 std::result::Result::Err(err) => std::result::Result::Err(format!(
-                    "Unable to convert header value '{}' into TradeCreateResponse - {}",
+                    "Unable to convert header value '{}' into HelloResponse - {}",
                     value, err
                 )),
             },
@@ -437,106 +663,1447 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeCreateR
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct TradeDetails {
-    #[serde(rename = "trading_entity")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub trading_entity: Option<String>,
-
-    #[serde(rename = "counterparty")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub counterparty: Option<String>,
-
-    /// Note: inline enums are not fully supported by openapi-generator
-    #[serde(rename = "direction")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub direction: Option<String>,
-
-    #[serde(rename = "notional_currency")]
+pub struct TradeBatchCommand {
+    #[serde(rename = "action")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub notional_currency: Option<String>,
+    pub action: Option<String>,
 
-    #[serde(rename = "notional_amount")]
+    #[serde(rename = "tradeId")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub notional_amount: Option<f64>,
+    pub trade_id: Option<String>,
 
-    #[serde(rename = "underlying")]
+    #[serde(rename = "version")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub underlying: Option<Vec<String>>,
+    pub version: Option<i32>,
 
-    #[serde(rename = "trade_date")]
+    #[serde(rename = "comment")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub trade_date: Option<chrono::DateTime<chrono::Utc>>,
+    pub comment: Option<String>,
 
-    #[serde(rename = "value_date")]
+    #[serde(rename = "details")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub value_date: Option<chrono::DateTime<chrono::Utc>>,
+    pub details: Option<models::TradeDetails>,
 
-    #[serde(rename = "delivery_date")]
+    /// Execution confirmation, for a `book`
+    #[serde(rename = "execution")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub delivery_date: Option<chrono::DateTime<chrono::Utc>>,
+    pub execution: Option<models::ExecutionConfirmation>,
 
-    #[serde(rename = "strike")]
+    /// Version whose details a `revert` brings back
+    #[serde(rename = "revertTo")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub strike: Option<f64>,
+    pub revert_to: Option<i32>,
 }
 
-impl TradeDetails {
+impl TradeBatchCommand {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new() -> TradeDetails {
-        TradeDetails {
-            trading_entity: None,
-            counterparty: None,
-            direction: None,
-            notional_currency: None,
-            notional_amount: None,
-            underlying: None,
-            trade_date: None,
-            value_date: None,
-            delivery_date: None,
-            strike: None,
+    pub fn new() -> TradeBatchCommand {
+        TradeBatchCommand {
+            action: None,
+            trade_id: None,
+            version: None,
+            comment: None,
+            details: None,
+            execution: None,
+            revert_to: None,
         }
     }
 }
 
-/// Converts the TradeDetails value to the Query Parameters representation (style=form, explode=false)
+/// Converts the TradeBatchCommand value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::fmt::Display for TradeDetails {
+impl std::fmt::Display for TradeBatchCommand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let params: Vec<Option<String>> = vec![
-            self.trading_entity
+            self.action.as_ref().map(|action| ["action".to_string(), action.to_string()].join(",")),
+            self.trade_id.as_ref().map(|trade_id| ["tradeId".to_string(), trade_id.to_string()].join(",")),
+            self.version.as_ref().map(|version| ["version".to_string(), version.to_string()].join(",")),
+            self.comment.as_ref().map(|comment| ["comment".to_string(), comment.to_string()].join(",")),
+            // Skipping details in query parameter serialization
+            // Skipping execution in query parameter serialization
+            self.revert_to.as_ref().map(|revert_to| ["revertTo".to_string(), revert_to.to_string()].join(",")),
+        ];
+
+        write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeBatchCommand value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for TradeBatchCommand {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub action: Vec<String>,
+            pub trade_id: Vec<String>,
+            pub version: Vec<i32>,
+            pub comment: Vec<String>,
+            pub details: Vec<models::TradeDetails>,
+            pub execution: Vec<models::ExecutionConfirmation>,
+            pub revert_to: Vec<i32>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing TradeBatchCommand".to_string()),
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "action" => intermediate_rep
+                        .action
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "tradeId" => intermediate_rep
+                        .trade_id
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "version" => intermediate_rep
+                        .version
+                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "comment" => intermediate_rep
+                        .comment
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "details" => intermediate_rep
+                        .details
+                        .push(<models::TradeDetails as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "execution" => intermediate_rep.execution.push(
+                        <models::ExecutionConfirmation as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "revertTo" => intermediate_rep
+                        .revert_to
+                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    _ => return std::result::Result::Err("Unexpected key while parsing TradeBatchCommand".to_string()),
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(TradeBatchCommand {
+            action: intermediate_rep.action.into_iter().next(),
+            trade_id: intermediate_rep.trade_id.into_iter().next(),
+            version: intermediate_rep.version.into_iter().next(),
+            comment: intermediate_rep.comment.into_iter().next(),
+            details: intermediate_rep.details.into_iter().next(),
+            execution: intermediate_rep.execution.into_iter().next(),
+            revert_to: intermediate_rep.revert_to.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<TradeBatchCommand> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeBatchCommand>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeBatchCommand>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for TradeBatchCommand - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeBatchCommand> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <TradeBatchCommand as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into TradeBatchCommand - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => {
+                std::result::Result::Err(format!("Unable to convert header: {:?} to string: {}", hdr_value, e))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct TradeBatchRequest {
+    #[serde(rename = "mode")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+
+    #[serde(rename = "commands")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commands: Option<Vec<models::TradeBatchCommand>>,
+}
+
+impl TradeBatchRequest {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> TradeBatchRequest {
+        TradeBatchRequest { mode: None, commands: None }
+    }
+}
+
+/// Converts the TradeBatchRequest value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for TradeBatchRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            self.mode.as_ref().map(|mode| ["mode".to_string(), mode.to_string()].join(",")),
+            // Skipping commands in query parameter serialization
+        ];
+
+        write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeBatchRequest value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for TradeBatchRequest {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub mode: Vec<String>,
+            pub commands: Vec<Vec<models::TradeBatchCommand>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing TradeBatchRequest".to_string()),
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "mode" => intermediate_rep
+                        .mode
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    "commands" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in TradeBatchRequest".to_string(),
+                        )
+                    }
+                    _ => return std::result::Result::Err("Unexpected key while parsing TradeBatchRequest".to_string()),
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(TradeBatchRequest {
+            mode: intermediate_rep.mode.into_iter().next(),
+            commands: intermediate_rep.commands.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<TradeBatchRequest> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeBatchRequest>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeBatchRequest>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for TradeBatchRequest - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeBatchRequest> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <TradeBatchRequest as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into TradeBatchRequest - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => {
+                std::result::Result::Err(format!("Unable to convert header: {:?} to string: {}", hdr_value, e))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct TradeBatchResponse {
+    #[serde(rename = "results")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub results: Option<Vec<models::TradeBatchResult>>,
+}
+
+impl TradeBatchResponse {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> TradeBatchResponse {
+        TradeBatchResponse { results: None }
+    }
+}
+
+/// Converts the TradeBatchResponse value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for TradeBatchResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            // Skipping results in query parameter serialization
+        ];
+
+        write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeBatchResponse value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for TradeBatchResponse {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub results: Vec<Vec<models::TradeBatchResult>>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing TradeBatchResponse".to_string()),
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    "results" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in TradeBatchResponse".to_string(),
+                        )
+                    }
+                    _ => {
+                        return std::result::Result::Err("Unexpected key while parsing TradeBatchResponse".to_string())
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(TradeBatchResponse { results: intermediate_rep.results.into_iter().next() })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<TradeBatchResponse> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeBatchResponse>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeBatchResponse>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for TradeBatchResponse - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeBatchResponse> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <TradeBatchResponse as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into TradeBatchResponse - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => {
+                std::result::Result::Err(format!("Unable to convert header: {:?} to string: {}", hdr_value, e))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct TradeBatchResult {
+    #[serde(rename = "tradeId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<String>,
+
+    #[serde(rename = "state")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+
+    #[serde(rename = "error")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<models::ErrorResponse>,
+}
+
+impl TradeBatchResult {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> TradeBatchResult {
+        TradeBatchResult { trade_id: None, state: None, error: None }
+    }
+}
+
+/// Converts the TradeBatchResult value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for TradeBatchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            self.trade_id.as_ref().map(|trade_id| ["tradeId".to_string(), trade_id.to_string()].join(",")),
+            self.state.as_ref().map(|state| ["state".to_string(), state.to_string()].join(",")),
+            // Skipping error in query parameter serialization
+        ];
+
+        write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeBatchResult value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for TradeBatchResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub trade_id: Vec<String>,
+            pub state: Vec<String>,
+            pub error: Vec<models::ErrorResponse>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing TradeBatchResult".to_string()),
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "tradeId" => intermediate_rep
+                        .trade_id
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "state" => intermediate_rep
+                        .state
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "error" => intermediate_rep
+                        .error
+                        .push(<models::ErrorResponse as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    _ => return std::result::Result::Err("Unexpected key while parsing TradeBatchResult".to_string()),
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(TradeBatchResult {
+            trade_id: intermediate_rep.trade_id.into_iter().next(),
+            state: intermediate_rep.state.into_iter().next(),
+            error: intermediate_rep.error.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<TradeBatchResult> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeBatchResult>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeBatchResult>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for TradeBatchResult - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeBatchResult> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <TradeBatchResult as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into TradeBatchResult - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => {
+                std::result::Result::Err(format!("Unable to convert header: {:?} to string: {}", hdr_value, e))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct TradeCreateRequest {
+    #[serde(rename = "userId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    #[serde(rename = "details")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<models::TradeDetails>,
+}
+
+impl TradeCreateRequest {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> TradeCreateRequest {
+        TradeCreateRequest { user_id: None, details: None }
+    }
+}
+
+/// Converts the TradeCreateRequest value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for TradeCreateRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            self.user_id.as_ref().map(|user_id| ["userId".to_string(), user_id.to_string()].join(",")),
+            // Skipping details in query parameter serialization
+            // Skipping execution in query parameter serialization
+        ];
+
+        write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeCreateRequest value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for TradeCreateRequest {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub user_id: Vec<String>,
+            pub details: Vec<models::TradeDetails>,
+            pub execution: Vec<models::ExecutionConfirmation>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing TradeCreateRequest".to_string()),
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "userId" => intermediate_rep
+                        .user_id
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "details" => intermediate_rep
+                        .details
+                        .push(<models::TradeDetails as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    _ => {
+                        return std::result::Result::Err("Unexpected key while parsing TradeCreateRequest".to_string())
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(TradeCreateRequest {
+            user_id: intermediate_rep.user_id.into_iter().next(),
+            details: intermediate_rep.details.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<TradeCreateRequest> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeCreateRequest>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeCreateRequest>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for TradeCreateRequest - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeCreateRequest> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <TradeCreateRequest as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into TradeCreateRequest - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => {
+                std::result::Result::Err(format!("Unable to convert header: {:?} to string: {}", hdr_value, e))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct TradeCreateResponse {
+    #[serde(rename = "tradeId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<String>,
+}
+
+impl TradeCreateResponse {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> TradeCreateResponse {
+        TradeCreateResponse { trade_id: None }
+    }
+}
+
+/// Converts the TradeCreateResponse value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for TradeCreateResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> =
+            vec![self.trade_id.as_ref().map(|trade_id| ["tradeId".to_string(), trade_id.to_string()].join(","))];
+
+        write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeCreateResponse value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for TradeCreateResponse {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub trade_id: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing TradeCreateResponse".to_string()),
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "tradeId" => intermediate_rep
+                        .trade_id
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    _ => {
+                        return std::result::Result::Err("Unexpected key while parsing TradeCreateResponse".to_string())
+                    }
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(TradeCreateResponse { trade_id: intermediate_rep.trade_id.into_iter().next() })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<TradeCreateResponse> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeCreateResponse>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeCreateResponse>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for TradeCreateResponse - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeCreateResponse> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <TradeCreateResponse as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into TradeCreateResponse - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => {
+                std::result::Result::Err(format!("Unable to convert header: {:?} to string: {}", hdr_value, e))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct TradeDetails {
+    #[serde(rename = "trading_entity")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trading_entity: Option<String>,
+
+    #[serde(rename = "counterparty")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counterparty: Option<String>,
+
+    /// Note: inline enums are not fully supported by openapi-generator
+    #[serde(rename = "direction")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<String>,
+
+    #[serde(rename = "notional_currency")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notional_currency: Option<String>,
+
+    #[serde(rename = "notional_amount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notional_amount: Option<f64>,
+
+    #[serde(rename = "underlying")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlying: Option<Vec<String>>,
+
+    #[serde(rename = "trade_date")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_date: Option<chrono::DateTime<chrono::Utc>>,
+
+    #[serde(rename = "value_date")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_date: Option<chrono::DateTime<chrono::Utc>>,
+
+    #[serde(rename = "delivery_date")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_date: Option<chrono::DateTime<chrono::Utc>>,
+
+    #[serde(rename = "strike")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strike: Option<f64>,
+
+    /// Settlement tenor (TOD, TOM, SPOT, 1W, 3M, IMM1 ...), instead of value and delivery dates
+    #[serde(rename = "tenor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenor: Option<String>,
+    /// Product: Spot, Forward (the default), Swap, NDF or VanillaOption
+    #[serde(rename = "product")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+
+    /// Swap: value date of the far leg
+    #[serde(rename = "far_value_date")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub far_value_date: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Swap: notional of the far leg
+    #[serde(rename = "far_notional_amount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub far_notional_amount: Option<f64>,
+
+    /// Swap: rate of the far leg, once executed
+    #[serde(rename = "far_rate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub far_rate: Option<f64>,
+
+    /// NDF: date the rate is fixed
+    #[serde(rename = "fixing_date")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fixing_date: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// NDF: currency the difference is settled in
+    #[serde(rename = "settlement_currency")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement_currency: Option<String>,
+
+    /// VanillaOption: expiry date
+    #[serde(rename = "expiry_date")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_date: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// VanillaOption: expiry cut (NY, TOK or ECB)
+    #[serde(rename = "cut")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cut: Option<String>,
+
+    /// VanillaOption: Call or Put
+    #[serde(rename = "option_type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub option_type: Option<String>,
+}
+
+impl TradeDetails {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> TradeDetails {
+        TradeDetails {
+            trading_entity: None,
+            counterparty: None,
+            direction: None,
+            notional_currency: None,
+            notional_amount: None,
+            underlying: None,
+            trade_date: None,
+            value_date: None,
+            delivery_date: None,
+            strike: None,
+            tenor: None,
+            product: None,
+            far_value_date: None,
+            far_notional_amount: None,
+            far_rate: None,
+            fixing_date: None,
+            settlement_currency: None,
+            expiry_date: None,
+            cut: None,
+            option_type: None,
+        }
+    }
+}
+
+/// Converts the TradeDetails value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for TradeDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            self.trading_entity
                 .as_ref()
                 .map(|trading_entity| ["trading_entity".to_string(), trading_entity.to_string()].join(",")),
             self.counterparty
                 .as_ref()
-                .map(|counterparty| ["counterparty".to_string(), counterparty.to_string()].join(",")),
-            self.direction.as_ref().map(|direction| ["direction".to_string(), direction.to_string()].join(",")),
-            self.notional_currency
+                .map(|counterparty| ["counterparty".to_string(), counterparty.to_string()].join(",")),
+            self.direction.as_ref().map(|direction| ["direction".to_string(), direction.to_string()].join(",")),
+            self.notional_currency
+                .as_ref()
+                .map(|notional_currency| ["notional_currency".to_string(), notional_currency.to_string()].join(",")),
+            self.notional_amount
+                .as_ref()
+                .map(|notional_amount| ["notional_amount".to_string(), notional_amount.to_string()].join(",")),
+            self.underlying.as_ref().map(|underlying| {
+                ["underlying".to_string(), underlying.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",")]
+                    .join(",")
+            }),
+            // Skipping trade_date in query parameter serialization
+
+            // Skipping value_date in query parameter serialization
+
+            // Skipping delivery_date in query parameter serialization
+            self.strike.as_ref().map(|strike| ["strike".to_string(), strike.to_string()].join(",")),
+            self.tenor.as_ref().map(|tenor| ["tenor".to_string(), tenor.to_string()].join(",")),
+            self.product.as_ref().map(|product| ["product".to_string(), product.to_string()].join(",")),
+            // Skipping far_value_date in query parameter serialization
+            self.far_notional_amount.as_ref().map(|far_notional_amount| {
+                ["far_notional_amount".to_string(), far_notional_amount.to_string()].join(",")
+            }),
+            self.far_rate.as_ref().map(|far_rate| ["far_rate".to_string(), far_rate.to_string()].join(",")),
+            // Skipping fixing_date in query parameter serialization
+            self.settlement_currency.as_ref().map(|settlement_currency| {
+                ["settlement_currency".to_string(), settlement_currency.to_string()].join(",")
+            }),
+            // Skipping expiry_date in query parameter serialization
+            self.cut.as_ref().map(|cut| ["cut".to_string(), cut.to_string()].join(",")),
+            self.option_type.as_ref().map(|option_type| ["option_type".to_string(), option_type.to_string()].join(",")),
+        ];
+
+        write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
+    }
+}
+
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeDetails value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for TradeDetails {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub trading_entity: Vec<String>,
+            pub counterparty: Vec<String>,
+            pub direction: Vec<String>,
+            pub notional_currency: Vec<String>,
+            pub notional_amount: Vec<f64>,
+            pub underlying: Vec<Vec<String>>,
+            pub trade_date: Vec<chrono::DateTime<chrono::Utc>>,
+            pub value_date: Vec<chrono::DateTime<chrono::Utc>>,
+            pub delivery_date: Vec<chrono::DateTime<chrono::Utc>>,
+            pub strike: Vec<f64>,
+            pub tenor: Vec<String>,
+            pub product: Vec<String>,
+            pub far_value_date: Vec<chrono::DateTime<chrono::Utc>>,
+            pub far_notional_amount: Vec<f64>,
+            pub far_rate: Vec<f64>,
+            pub fixing_date: Vec<chrono::DateTime<chrono::Utc>>,
+            pub settlement_currency: Vec<String>,
+            pub expiry_date: Vec<chrono::DateTime<chrono::Utc>>,
+            pub cut: Vec<String>,
+            pub option_type: Vec<String>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing TradeDetails".to_string()),
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "trading_entity" => intermediate_rep
+                        .trading_entity
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "counterparty" => intermediate_rep
+                        .counterparty
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "direction" => intermediate_rep
+                        .direction
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "notional_currency" => intermediate_rep
+                        .notional_currency
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "notional_amount" => intermediate_rep
+                        .notional_amount
+                        .push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    "underlying" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in TradeDetails".to_string(),
+                        )
+                    }
+                    #[allow(clippy::redundant_clone)]
+                    "trade_date" => intermediate_rep.trade_date.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "value_date" => intermediate_rep.value_date.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "delivery_date" => intermediate_rep.delivery_date.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "strike" => intermediate_rep
+                        .strike
+                        .push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "tenor" => intermediate_rep
+                        .tenor
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "product" => intermediate_rep
+                        .product
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "far_value_date" => intermediate_rep.far_value_date.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "far_notional_amount" => intermediate_rep
+                        .far_notional_amount
+                        .push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "far_rate" => intermediate_rep
+                        .far_rate
+                        .push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "fixing_date" => intermediate_rep.fixing_date.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "settlement_currency" => intermediate_rep
+                        .settlement_currency
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "expiry_date" => intermediate_rep.expiry_date.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "cut" => intermediate_rep
+                        .cut
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "option_type" => intermediate_rep
+                        .option_type
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    _ => return std::result::Result::Err("Unexpected key while parsing TradeDetails".to_string()),
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(TradeDetails {
+            trading_entity: intermediate_rep.trading_entity.into_iter().next(),
+            counterparty: intermediate_rep.counterparty.into_iter().next(),
+            direction: intermediate_rep.direction.into_iter().next(),
+            notional_currency: intermediate_rep.notional_currency.into_iter().next(),
+            notional_amount: intermediate_rep.notional_amount.into_iter().next(),
+            underlying: intermediate_rep.underlying.into_iter().next(),
+            trade_date: intermediate_rep.trade_date.into_iter().next(),
+            value_date: intermediate_rep.value_date.into_iter().next(),
+            delivery_date: intermediate_rep.delivery_date.into_iter().next(),
+            strike: intermediate_rep.strike.into_iter().next(),
+            tenor: intermediate_rep.tenor.into_iter().next(),
+            product: intermediate_rep.product.into_iter().next(),
+            far_value_date: intermediate_rep.far_value_date.into_iter().next(),
+            far_notional_amount: intermediate_rep.far_notional_amount.into_iter().next(),
+            far_rate: intermediate_rep.far_rate.into_iter().next(),
+            fixing_date: intermediate_rep.fixing_date.into_iter().next(),
+            settlement_currency: intermediate_rep.settlement_currency.into_iter().next(),
+            expiry_date: intermediate_rep.expiry_date.into_iter().next(),
+            cut: intermediate_rep.cut.into_iter().next(),
+            option_type: intermediate_rep.option_type.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<TradeDetails> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeDetails>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeDetails>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for TradeDetails - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeDetails> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <TradeDetails as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into TradeDetails - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => {
+                std::result::Result::Err(format!("Unable to convert header: {:?} to string: {}", hdr_value, e))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct TradeDiff {
+    #[serde(rename = "trade_id")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<String>,
+
+    #[serde(rename = "from_version")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_version: Option<i32>,
+
+    #[serde(rename = "to_version")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_version: Option<i32>,
+
+    #[serde(rename = "from_reverted_from")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_reverted_from: Option<i32>,
+
+    #[serde(rename = "to_reverted_from")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_reverted_from: Option<i32>,
+
+    #[serde(rename = "differences")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub differences: Option<crate::types::Object>,
+}
+
+impl TradeDiff {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> TradeDiff {
+        TradeDiff {
+            trade_id: None,
+            from_version: None,
+            to_version: None,
+            from_reverted_from: None,
+            to_reverted_from: None,
+            differences: None,
+        }
+    }
+}
+
+/// Converts the TradeDiff value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for TradeDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            self.trade_id.as_ref().map(|trade_id| ["trade_id".to_string(), trade_id.to_string()].join(",")),
+            self.from_version
+                .as_ref()
+                .map(|from_version| ["from_version".to_string(), from_version.to_string()].join(",")),
+            self.to_version.as_ref().map(|to_version| ["to_version".to_string(), to_version.to_string()].join(",")),
+            self.from_reverted_from
                 .as_ref()
-                .map(|notional_currency| ["notional_currency".to_string(), notional_currency.to_string()].join(",")),
-            self.notional_amount
+                .map(|from_reverted_from| ["from_reverted_from".to_string(), from_reverted_from.to_string()].join(",")),
+            self.to_reverted_from
                 .as_ref()
-                .map(|notional_amount| ["notional_amount".to_string(), notional_amount.to_string()].join(",")),
-            self.underlying.as_ref().map(|underlying| {
-                ["underlying".to_string(), underlying.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",")]
-                    .join(",")
-            }),
-            // Skipping trade_date in query parameter serialization
+                .map(|to_reverted_from| ["to_reverted_from".to_string(), to_reverted_from.to_string()].join(",")),
+            // Skipping differences in query parameter serialization
+        ];
 
-            // Skipping value_date in query parameter serialization
+        write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
+    }
+}
 
-            // Skipping delivery_date in query parameter serialization
-            self.strike.as_ref().map(|strike| ["strike".to_string(), strike.to_string()].join(",")),
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeDiff value
+/// as specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde deserializer
+impl std::str::FromStr for TradeDiff {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        /// An intermediate representation of the struct to use for parsing.
+        #[derive(Default)]
+        #[allow(dead_code)]
+        struct IntermediateRep {
+            pub trade_id: Vec<String>,
+            pub from_version: Vec<i32>,
+            pub to_version: Vec<i32>,
+            pub from_reverted_from: Vec<i32>,
+            pub to_reverted_from: Vec<i32>,
+            pub differences: Vec<crate::types::Object>,
+        }
+
+        let mut intermediate_rep = IntermediateRep::default();
+
+        // Parse into intermediate representation
+        let mut string_iter = s.split(',');
+        let mut key_result = string_iter.next();
+
+        while key_result.is_some() {
+            let val = match string_iter.next() {
+                Some(x) => x,
+                None => return std::result::Result::Err("Missing value while parsing TradeDiff".to_string()),
+            };
+
+            if let Some(key) = key_result {
+                #[allow(clippy::match_single_binding)]
+                match key {
+                    #[allow(clippy::redundant_clone)]
+                    "trade_id" => intermediate_rep
+                        .trade_id
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "from_version" => intermediate_rep
+                        .from_version
+                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "to_version" => intermediate_rep
+                        .to_version
+                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "from_reverted_from" => intermediate_rep
+                        .from_reverted_from
+                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "to_reverted_from" => intermediate_rep
+                        .to_reverted_from
+                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "differences" => intermediate_rep
+                        .differences
+                        .push(<crate::types::Object as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    _ => return std::result::Result::Err("Unexpected key while parsing TradeDiff".to_string()),
+                }
+            }
+
+            // Get the next key
+            key_result = string_iter.next();
+        }
+
+        // Use the intermediate representation to return the struct
+        std::result::Result::Ok(TradeDiff {
+            trade_id: intermediate_rep.trade_id.into_iter().next(),
+            from_version: intermediate_rep.from_version.into_iter().next(),
+            to_version: intermediate_rep.to_version.into_iter().next(),
+            from_reverted_from: intermediate_rep.from_reverted_from.into_iter().next(),
+            to_reverted_from: intermediate_rep.to_reverted_from.into_iter().next(),
+            differences: intermediate_rep.differences.into_iter().next(),
+        })
+    }
+}
+
+// Methods for converting between header::IntoHeaderValue<TradeDiff> and HeaderValue
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeDiff>> for HeaderValue {
+    type Error = String;
+
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeDiff>) -> std::result::Result<Self, Self::Error> {
+        let hdr_value = hdr_value.to_string();
+        match HeaderValue::from_str(&hdr_value) {
+            std::result::Result::Ok(value) => std::result::Result::Ok(value),
+            std::result::Result::Err(e) => std::result::Result::Err(format!(
+                "Invalid header value for TradeDiff - value: {} is invalid {}",
+                hdr_value, e
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeDiff> {
+    type Error = String;
+
+    fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
+        match hdr_value.to_str() {
+            std::result::Result::Ok(value) => match <TradeDiff as std::str::FromStr>::from_str(value) {
+                std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
+                std::result::Result::Err(err) => std::result::Result::Err(format!(
+                    "Unable to convert header value '{}' into TradeDiff - {}",
+                    value, err
+                )),
+            },
+            std::result::Result::Err(e) => {
+                std::result::Result::Err(format!("Unable to convert header: {:?} to string: {}", hdr_value, e))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
+#[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
+pub struct TradeEvent {
+    #[serde(rename = "user_id")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+
+    #[serde(rename = "timestamp")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+
+    #[serde(rename = "state")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+
+    #[serde(rename = "details")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<models::TradeDetails>,
+
+    #[serde(rename = "comment")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    #[serde(rename = "reverted_from")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverted_from: Option<i32>,
+
+    /// On the snapshot that booked the trade
+    #[serde(rename = "execution")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution: Option<models::ExecutionConfirmation>,
+}
+
+impl TradeEvent {
+    #[allow(clippy::new_without_default, clippy::too_many_arguments)]
+    pub fn new() -> TradeEvent {
+        TradeEvent {
+            user_id: None,
+            timestamp: None,
+            state: None,
+            details: None,
+            comment: None,
+            reverted_from: None,
+            execution: None,
+        }
+    }
+}
+
+/// Converts the TradeEvent value to the Query Parameters representation (style=form, explode=false)
+/// specified in https://swagger.io/docs/specification/serialization/
+/// Should be implemented in a serde serializer
+impl std::fmt::Display for TradeEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<Option<String>> = vec![
+            self.user_id.as_ref().map(|user_id| ["user_id".to_string(), user_id.to_string()].join(",")),
+            // Skipping timestamp in query parameter serialization
+            self.state.as_ref().map(|state| ["state".to_string(), state.to_string()].join(",")),
+            // Skipping details in query parameter serialization
+            self.comment.as_ref().map(|comment| ["comment".to_string(), comment.to_string()].join(",")),
+            self.reverted_from
+                .as_ref()
+                .map(|reverted_from| ["reverted_from".to_string(), reverted_from.to_string()].join(",")),
+            // Skipping execution in query parameter serialization
         ];
 
         write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a TradeDetails value
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeEvent value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for TradeDetails {
+impl std::str::FromStr for TradeEvent {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -544,16 +2111,13 @@ impl std::str::FromStr for TradeDetails {
         #[derive(Default)]
         #[allow(dead_code)]
         struct IntermediateRep {
-            pub trading_entity: Vec<String>,
-            pub counterparty: Vec<String>,
-            pub direction: Vec<String>,
-            pub notional_currency: Vec<String>,
-            pub notional_amount: Vec<f64>,
-            pub underlying: Vec<Vec<String>>,
-            pub trade_date: Vec<chrono::DateTime<chrono::Utc>>,
-            pub value_date: Vec<chrono::DateTime<chrono::Utc>>,
-            pub delivery_date: Vec<chrono::DateTime<chrono::Utc>>,
-            pub strike: Vec<f64>,
+            pub user_id: Vec<String>,
+            pub timestamp: Vec<chrono::DateTime<chrono::Utc>>,
+            pub state: Vec<String>,
+            pub details: Vec<models::TradeDetails>,
+            pub comment: Vec<String>,
+            pub reverted_from: Vec<i32>,
+            pub execution: Vec<models::ExecutionConfirmation>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -565,57 +2129,43 @@ impl std::str::FromStr for TradeDetails {
         while key_result.is_some() {
             let val = match string_iter.next() {
                 Some(x) => x,
-                None => return std::result::Result::Err("Missing value while parsing TradeDetails".to_string()),
+                None => return std::result::Result::Err("Missing value while parsing TradeEvent".to_string()),
             };
 
             if let Some(key) = key_result {
                 #[allow(clippy::match_single_binding)]
                 match key {
                     #[allow(clippy::redundant_clone)]
-                    "trading_entity" => intermediate_rep
-                        .trading_entity
-                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    #[allow(clippy::redundant_clone)]
-                    "counterparty" => intermediate_rep
-                        .counterparty
+                    "user_id" => intermediate_rep
+                        .user_id
                         .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
                     #[allow(clippy::redundant_clone)]
-                    "direction" => intermediate_rep
-                        .direction
-                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    "timestamp" => intermediate_rep.timestamp.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
                     #[allow(clippy::redundant_clone)]
-                    "notional_currency" => intermediate_rep
-                        .notional_currency
+                    "state" => intermediate_rep
+                        .state
                         .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
                     #[allow(clippy::redundant_clone)]
-                    "notional_amount" => intermediate_rep
-                        .notional_amount
-                        .push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    "underlying" => {
-                        return std::result::Result::Err(
-                            "Parsing a container in this style is not supported in TradeDetails".to_string(),
-                        )
-                    }
+                    "details" => intermediate_rep
+                        .details
+                        .push(<models::TradeDetails as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
                     #[allow(clippy::redundant_clone)]
-                    "trade_date" => intermediate_rep.trade_date.push(
-                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
-                            .map_err(|x| x.to_string())?,
-                    ),
+                    "comment" => intermediate_rep
+                        .comment
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
                     #[allow(clippy::redundant_clone)]
-                    "value_date" => intermediate_rep.value_date.push(
-                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
-                            .map_err(|x| x.to_string())?,
-                    ),
+                    "reverted_from" => intermediate_rep
+                        .reverted_from
+                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
                     #[allow(clippy::redundant_clone)]
-                    "delivery_date" => intermediate_rep.delivery_date.push(
-                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                    "execution" => intermediate_rep.execution.push(
+                        <models::ExecutionConfirmation as std::str::FromStr>::from_str(val)
                             .map_err(|x| x.to_string())?,
                     ),
-                    #[allow(clippy::redundant_clone)]
-                    "strike" => intermediate_rep
-                        .strike
-                        .push(<f64 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    _ => return std::result::Result::Err("Unexpected key while parsing TradeDetails".to_string()),
+                    _ => return std::result::Result::Err("Unexpected key while parsing TradeEvent".to_string()),
                 }
             }
 
@@ -624,33 +2174,30 @@ impl std::str::FromStr for TradeDetails {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(TradeDetails {
-            trading_entity: intermediate_rep.trading_entity.into_iter().next(),
-            counterparty: intermediate_rep.counterparty.into_iter().next(),
-            direction: intermediate_rep.direction.into_iter().next(),
-            notional_currency: intermediate_rep.notional_currency.into_iter().next(),
-            notional_amount: intermediate_rep.notional_amount.into_iter().next(),
-            underlying: intermediate_rep.underlying.into_iter().next(),
-            trade_date: intermediate_rep.trade_date.into_iter().next(),
-            value_date: intermediate_rep.value_date.into_iter().next(),
-            delivery_date: intermediate_rep.delivery_date.into_iter().next(),
-            strike: intermediate_rep.strike.into_iter().next(),
+        std::result::Result::Ok(TradeEvent {
+            user_id: intermediate_rep.user_id.into_iter().next(),
+            timestamp: intermediate_rep.timestamp.into_iter().next(),
+            state: intermediate_rep.state.into_iter().next(),
+            details: intermediate_rep.details.into_iter().next(),
+            comment: intermediate_rep.comment.into_iter().next(),
+            reverted_from: intermediate_rep.reverted_from.into_iter().next(),
+            execution: intermediate_rep.execution.into_iter().next(),
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<TradeDetails> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<TradeEvent> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<TradeDetails>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeEvent>> for HeaderValue {
     type Error = String;
 
-    fn try_from(hdr_value: header::IntoHeaderValue<TradeDetails>) -> std::result::Result<Self, Self::Error> {
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeEvent>) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for TradeDetails - value: {} is invalid {}",
+                "Invalid header value for TradeEvent - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -658,15 +2205,15 @@ impl std::convert::TryFrom<header::IntoHeaderValue<TradeDetails>> for HeaderValu
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeDetails> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeEvent> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
-            std::result::Result::Ok(value) => match <TradeDetails as std::str::FromStr>::from_str(value) {
+            std::result::Result::Ok(value) => match <TradeEvent as std::str::FromStr>::from_str(value) {
                 std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
                 std::result::Result::Err(err) => std::result::Result::Err(format!(
-                    "Unable to convert header value '{}' into TradeDetails - {}",
+                    "Unable to convert header value '{}' into TradeEvent - {}",
                     value, err
                 )),
             },
@@ -679,53 +2226,41 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeDetails
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct TradeDiff {
-    #[serde(rename = "trade_id")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub trade_id: Option<String>,
-
-    #[serde(rename = "from_version")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub from_version: Option<i32>,
-
-    #[serde(rename = "to_version")]
+pub struct TradeListPage {
+    #[serde(rename = "trades")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub to_version: Option<i32>,
+    pub trades: Option<Vec<models::TradeSummary>>,
 
-    #[serde(rename = "differences")]
+    #[serde(rename = "nextCursor")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub differences: Option<crate::types::Object>,
+    pub next_cursor: Option<String>,
 }
 
-impl TradeDiff {
+impl TradeListPage {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new() -> TradeDiff {
-        TradeDiff { trade_id: None, from_version: None, to_version: None, differences: None }
+    pub fn new() -> TradeListPage {
+        TradeListPage { trades: None, next_cursor: None }
     }
 }
 
-/// Converts the TradeDiff value to the Query Parameters representation (style=form, explode=false)
+/// Converts the TradeListPage value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::fmt::Display for TradeDiff {
+impl std::fmt::Display for TradeListPage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let params: Vec<Option<String>> = vec![
-            self.trade_id.as_ref().map(|trade_id| ["trade_id".to_string(), trade_id.to_string()].join(",")),
-            self.from_version
-                .as_ref()
-                .map(|from_version| ["from_version".to_string(), from_version.to_string()].join(",")),
-            self.to_version.as_ref().map(|to_version| ["to_version".to_string(), to_version.to_string()].join(",")),
-            // Skipping differences in query parameter serialization
+            // Skipping trades in query parameter serialization
+            self.next_cursor.as_ref().map(|next_cursor| ["nextCursor".to_string(), next_cursor.to_string()].join(",")),
         ];
 
         write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a TradeDiff value
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeListPage value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for TradeDiff {
+impl std::str::FromStr for TradeListPage {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -733,10 +2268,8 @@ impl std::str::FromStr for TradeDiff {
         #[derive(Default)]
         #[allow(dead_code)]
         struct IntermediateRep {
-            pub trade_id: Vec<String>,
-            pub from_version: Vec<i32>,
-            pub to_version: Vec<i32>,
-            pub differences: Vec<crate::types::Object>,
+            pub trades: Vec<Vec<models::TradeSummary>>,
+            pub next_cursor: Vec<String>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -748,29 +2281,22 @@ impl std::str::FromStr for TradeDiff {
         while key_result.is_some() {
             let val = match string_iter.next() {
                 Some(x) => x,
-                None => return std::result::Result::Err("Missing value while parsing TradeDiff".to_string()),
+                None => return std::result::Result::Err("Missing value while parsing TradeListPage".to_string()),
             };
 
             if let Some(key) = key_result {
                 #[allow(clippy::match_single_binding)]
                 match key {
+                    "trades" => {
+                        return std::result::Result::Err(
+                            "Parsing a container in this style is not supported in TradeListPage".to_string(),
+                        )
+                    }
                     #[allow(clippy::redundant_clone)]
-                    "trade_id" => intermediate_rep
-                        .trade_id
+                    "nextCursor" => intermediate_rep
+                        .next_cursor
                         .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    #[allow(clippy::redundant_clone)]
-                    "from_version" => intermediate_rep
-                        .from_version
-                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    #[allow(clippy::redundant_clone)]
-                    "to_version" => intermediate_rep
-                        .to_version
-                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    #[allow(clippy::redundant_clone)]
-                    "differences" => intermediate_rep
-                        .differences
-                        .push(<crate::types::Object as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    _ => return std::result::Result::Err("Unexpected key while parsing TradeDiff".to_string()),
+                    _ => return std::result::Result::Err("Unexpected key while parsing TradeListPage".to_string()),
                 }
             }
 
@@ -779,27 +2305,25 @@ impl std::str::FromStr for TradeDiff {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(TradeDiff {
-            trade_id: intermediate_rep.trade_id.into_iter().next(),
-            from_version: intermediate_rep.from_version.into_iter().next(),
-            to_version: intermediate_rep.to_version.into_iter().next(),
-            differences: intermediate_rep.differences.into_iter().next(),
+        std::result::Result::Ok(TradeListPage {
+            trades: intermediate_rep.trades.into_iter().next(),
+            next_cursor: intermediate_rep.next_cursor.into_iter().next(),
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<TradeDiff> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<TradeListPage> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<TradeDiff>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeListPage>> for HeaderValue {
     type Error = String;
 
-    fn try_from(hdr_value: header::IntoHeaderValue<TradeDiff>) -> std::result::Result<Self, Self::Error> {
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeListPage>) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for TradeDiff - value: {} is invalid {}",
+                "Invalid header value for TradeListPage - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -807,15 +2331,15 @@ impl std::convert::TryFrom<header::IntoHeaderValue<TradeDiff>> for HeaderValue {
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeDiff> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeListPage> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
-            std::result::Result::Ok(value) => match <TradeDiff as std::str::FromStr>::from_str(value) {
+            std::result::Result::Ok(value) => match <TradeListPage as std::str::FromStr>::from_str(value) {
                 std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
                 std::result::Result::Err(err) => std::result::Result::Err(format!(
-                    "Unable to convert header value '{}' into TradeDiff - {}",
+                    "Unable to convert header value '{}' into TradeListPage - {}",
                     value, err
                 )),
             },
@@ -828,7 +2352,15 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeDiff> {
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct TradeEvent {
+pub struct TradeStatus {
+    #[serde(rename = "state")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+
+    #[serde(rename = "version")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+
     #[serde(rename = "user_id")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
@@ -837,42 +2369,40 @@ pub struct TradeEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
 
-    #[serde(rename = "state")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub state: Option<String>,
-
     #[serde(rename = "details")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<models::TradeDetails>,
 }
 
-impl TradeEvent {
+impl TradeStatus {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new() -> TradeEvent {
-        TradeEvent { user_id: None, timestamp: None, state: None, details: None }
+    pub fn new() -> TradeStatus {
+        TradeStatus { state: None, version: None, user_id: None, timestamp: None, details: None }
     }
 }
 
-/// Converts the TradeEvent value to the Query Parameters representation (style=form, explode=false)
+/// Converts the TradeStatus value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::fmt::Display for TradeEvent {
+impl std::fmt::Display for TradeStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let params: Vec<Option<String>> = vec![
+            self.state.as_ref().map(|state| ["state".to_string(), state.to_string()].join(",")),
+            self.version.as_ref().map(|version| ["version".to_string(), version.to_string()].join(",")),
             self.user_id.as_ref().map(|user_id| ["user_id".to_string(), user_id.to_string()].join(",")),
             // Skipping timestamp in query parameter serialization
-            self.state.as_ref().map(|state| ["state".to_string(), state.to_string()].join(",")),
             // Skipping details in query parameter serialization
+            // Skipping execution in query parameter serialization
         ];
 
         write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a TradeEvent value
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeStatus value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for TradeEvent {
+impl std::str::FromStr for TradeStatus {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -880,10 +2410,12 @@ impl std::str::FromStr for TradeEvent {
         #[derive(Default)]
         #[allow(dead_code)]
         struct IntermediateRep {
+            pub state: Vec<String>,
+            pub version: Vec<i32>,
             pub user_id: Vec<String>,
             pub timestamp: Vec<chrono::DateTime<chrono::Utc>>,
-            pub state: Vec<String>,
             pub details: Vec<models::TradeDetails>,
+            pub execution: Vec<models::ExecutionConfirmation>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -895,13 +2427,21 @@ impl std::str::FromStr for TradeEvent {
         while key_result.is_some() {
             let val = match string_iter.next() {
                 Some(x) => x,
-                None => return std::result::Result::Err("Missing value while parsing TradeEvent".to_string()),
+                None => return std::result::Result::Err("Missing value while parsing TradeStatus".to_string()),
             };
 
             if let Some(key) = key_result {
                 #[allow(clippy::match_single_binding)]
                 match key {
                     #[allow(clippy::redundant_clone)]
+                    "state" => intermediate_rep
+                        .state
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "version" => intermediate_rep
+                        .version
+                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
                     "user_id" => intermediate_rep
                         .user_id
                         .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
@@ -911,14 +2451,10 @@ impl std::str::FromStr for TradeEvent {
                             .map_err(|x| x.to_string())?,
                     ),
                     #[allow(clippy::redundant_clone)]
-                    "state" => intermediate_rep
-                        .state
-                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    #[allow(clippy::redundant_clone)]
                     "details" => intermediate_rep
                         .details
                         .push(<models::TradeDetails as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    _ => return std::result::Result::Err("Unexpected key while parsing TradeEvent".to_string()),
+                    _ => return std::result::Result::Err("Unexpected key while parsing TradeStatus".to_string()),
                 }
             }
 
@@ -927,27 +2463,28 @@ impl std::str::FromStr for TradeEvent {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(TradeEvent {
+        std::result::Result::Ok(TradeStatus {
+            state: intermediate_rep.state.into_iter().next(),
+            version: intermediate_rep.version.into_iter().next(),
             user_id: intermediate_rep.user_id.into_iter().next(),
             timestamp: intermediate_rep.timestamp.into_iter().next(),
-            state: intermediate_rep.state.into_iter().next(),
             details: intermediate_rep.details.into_iter().next(),
         })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<TradeEvent> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<TradeStatus> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<TradeEvent>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeStatus>> for HeaderValue {
     type Error = String;
 
-    fn try_from(hdr_value: header::IntoHeaderValue<TradeEvent>) -> std::result::Result<Self, Self::Error> {
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeStatus>) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for TradeEvent - value: {} is invalid {}",
+                "Invalid header value for TradeStatus - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -955,15 +2492,15 @@ impl std::convert::TryFrom<header::IntoHeaderValue<TradeEvent>> for HeaderValue
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeEvent> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeStatus> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
-            std::result::Result::Ok(value) => match <TradeEvent as std::str::FromStr>::from_str(value) {
+            std::result::Result::Ok(value) => match <TradeStatus as std::str::FromStr>::from_str(value) {
                 std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
                 std::result::Result::Err(err) => std::result::Result::Err(format!(
-                    "Unable to convert header value '{}' into TradeEvent - {}",
+                    "Unable to convert header value '{}' into TradeStatus - {}",
                     value, err
                 )),
             },
@@ -976,35 +2513,62 @@ impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeEvent>
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, validator::Validate)]
 #[cfg_attr(feature = "conversion", derive(frunk::LabelledGeneric))]
-pub struct TradeStatus {
+pub struct TradeSummary {
+    #[serde(rename = "tradeId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trade_id: Option<String>,
+
     #[serde(rename = "state")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
+
+    #[serde(rename = "requester")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requester: Option<String>,
+
+    #[serde(rename = "createdAt")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    #[serde(rename = "version")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+
+    #[serde(rename = "details")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<models::TradeDetails>,
 }
 
-impl TradeStatus {
+impl TradeSummary {
     #[allow(clippy::new_without_default, clippy::too_many_arguments)]
-    pub fn new() -> TradeStatus {
-        TradeStatus { state: None }
+    pub fn new() -> TradeSummary {
+        TradeSummary { trade_id: None, state: None, requester: None, created_at: None, version: None, details: None }
     }
 }
 
-/// Converts the TradeStatus value to the Query Parameters representation (style=form, explode=false)
+/// Converts the TradeSummary value to the Query Parameters representation (style=form, explode=false)
 /// specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde serializer
-impl std::fmt::Display for TradeStatus {
+impl std::fmt::Display for TradeSummary {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let params: Vec<Option<String>> =
-            vec![self.state.as_ref().map(|state| ["state".to_string(), state.to_string()].join(","))];
+        let params: Vec<Option<String>> = vec![
+            self.trade_id.as_ref().map(|trade_id| ["tradeId".to_string(), trade_id.to_string()].join(",")),
+            self.state.as_ref().map(|state| ["state".to_string(), state.to_string()].join(",")),
+            self.requester.as_ref().map(|requester| ["requester".to_string(), requester.to_string()].join(",")),
+            // Skipping createdAt in query parameter serialization
+            self.version.as_ref().map(|version| ["version".to_string(), version.to_string()].join(",")),
+            // Skipping details in query parameter serialization
+            // Skipping execution in query parameter serialization
+        ];
 
         write!(f, "{}", params.into_iter().flatten().collect::<Vec<_>>().join(","))
     }
 }
 
-/// Converts Query Parameters representation (style=form, explode=false) to a TradeStatus value
+/// Converts Query Parameters representation (style=form, explode=false) to a TradeSummary value
 /// as specified in https://swagger.io/docs/specification/serialization/
 /// Should be implemented in a serde deserializer
-impl std::str::FromStr for TradeStatus {
+impl std::str::FromStr for TradeSummary {
     type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
@@ -1012,7 +2576,13 @@ impl std::str::FromStr for TradeStatus {
         #[derive(Default)]
         #[allow(dead_code)]
         struct IntermediateRep {
+            pub trade_id: Vec<String>,
             pub state: Vec<String>,
+            pub requester: Vec<String>,
+            pub created_at: Vec<chrono::DateTime<chrono::Utc>>,
+            pub version: Vec<i32>,
+            pub details: Vec<models::TradeDetails>,
+            pub execution: Vec<models::ExecutionConfirmation>,
         }
 
         let mut intermediate_rep = IntermediateRep::default();
@@ -1024,17 +2594,38 @@ impl std::str::FromStr for TradeStatus {
         while key_result.is_some() {
             let val = match string_iter.next() {
                 Some(x) => x,
-                None => return std::result::Result::Err("Missing value while parsing TradeStatus".to_string()),
+                None => return std::result::Result::Err("Missing value while parsing TradeSummary".to_string()),
             };
 
             if let Some(key) = key_result {
                 #[allow(clippy::match_single_binding)]
                 match key {
                     #[allow(clippy::redundant_clone)]
+                    "tradeId" => intermediate_rep
+                        .trade_id
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
                     "state" => intermediate_rep
                         .state
                         .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
-                    _ => return std::result::Result::Err("Unexpected key while parsing TradeStatus".to_string()),
+                    #[allow(clippy::redundant_clone)]
+                    "requester" => intermediate_rep
+                        .requester
+                        .push(<String as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "createdAt" => intermediate_rep.created_at.push(
+                        <chrono::DateTime<chrono::Utc> as std::str::FromStr>::from_str(val)
+                            .map_err(|x| x.to_string())?,
+                    ),
+                    #[allow(clippy::redundant_clone)]
+                    "version" => intermediate_rep
+                        .version
+                        .push(<i32 as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    #[allow(clippy::redundant_clone)]
+                    "details" => intermediate_rep
+                        .details
+                        .push(<models::TradeDetails as std::str::FromStr>::from_str(val).map_err(|x| x.to_string())?),
+                    _ => return std::result::Result::Err("Unexpected key while parsing TradeSummary".to_string()),
                 }
             }
 
@@ -1043,22 +2634,29 @@ impl std::str::FromStr for TradeStatus {
         }
 
         // Use the intermediate representation to return the struct
-        std::result::Result::Ok(TradeStatus { state: intermediate_rep.state.into_iter().next() })
+        std::result::Result::Ok(TradeSummary {
+            trade_id: intermediate_rep.trade_id.into_iter().next(),
+            state: intermediate_rep.state.into_iter().next(),
+            requester: intermediate_rep.requester.into_iter().next(),
+            created_at: intermediate_rep.created_at.into_iter().next(),
+            version: intermediate_rep.version.into_iter().next(),
+            details: intermediate_rep.details.into_iter().next(),
+        })
     }
 }
 
-// Methods for converting between header::IntoHeaderValue<TradeStatus> and HeaderValue
+// Methods for converting between header::IntoHeaderValue<TradeSummary> and HeaderValue
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<header::IntoHeaderValue<TradeStatus>> for HeaderValue {
+impl std::convert::TryFrom<header::IntoHeaderValue<TradeSummary>> for HeaderValue {
     type Error = String;
 
-    fn try_from(hdr_value: header::IntoHeaderValue<TradeStatus>) -> std::result::Result<Self, Self::Error> {
+    fn try_from(hdr_value: header::IntoHeaderValue<TradeSummary>) -> std::result::Result<Self, Self::Error> {
         let hdr_value = hdr_value.to_string();
         match HeaderValue::from_str(&hdr_value) {
             std::result::Result::Ok(value) => std::result::Result::Ok(value),
             std::result::Result::Err(e) => std::result::Result::Err(format!(
-                "Invalid header value for TradeStatus - value: {} is invalid {}",
+                "Invalid header value for TradeSummary - value: {} is invalid {}",
                 hdr_value, e
             )),
         }
@@ -1066,15 +2664,15 @@ impl std::convert::TryFrom<header::IntoHeaderValue<TradeStatus>> for HeaderValue
 }
 
 #[cfg(feature = "server")]
-impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeStatus> {
+impl std::convert::TryFrom<HeaderValue> for header::IntoHeaderValue<TradeSummary> {
     type Error = String;
 
     fn try_from(hdr_value: HeaderValue) -> std::result::Result<Self, Self::Error> {
         match hdr_value.to_str() {
-            std::result::Result::Ok(value) => match <TradeStatus as std::str::FromStr>::from_str(value) {
+            std::result::Result::Ok(value) => match <TradeSummary as std::str::FromStr>::from_str(value) {
                 std::result::Result::Ok(value) => std::result::Result::Ok(header::IntoHeaderValue(value)),
                 std::result::Result::Err(err) => std::result::Result::Err(format!(
-                    "Unable to convert header value '{}' into TradeStatus - {}",
+                    "Unable to convert header value '{}' into TradeSummary - {}",
                     value, err
                 )),
             },
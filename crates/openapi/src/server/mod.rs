@@ -13,9 +13,9 @@ use crate::{header, types::*};
 use crate::models;
 
 use crate::{
-    Api, ApproveTradeResponse, BookTradeResponse, CancelTradeResponse, CreateTradeResponse, GetTradeDetailsResponse,
-    GetTradeHistoryResponse, GetTradeStatusResponse, HelloResponse, ListTradesResponse, SendTradeResponse,
-    SubmitTradeResponse, TradeDiffResponse, UpdateTradeResponse,
+    Api, ApproveTradeResponse, BatchTradesResponse, BookTradeResponse, CancelTradeResponse, CreateTradeResponse,
+    GetTradeDetailsResponse, GetTradeHistoryResponse, GetTradeStatusResponse, HelloResponse, ListTradesResponse,
+    RevertTradeResponse, SendTradeResponse, SubmitTradeResponse, TradeDiffResponse, UpdateTradeResponse,
 };
 
 /// Setup API Server.
@@ -28,12 +28,14 @@ where
     Router::new()
         .route("/hello", get(hello::<I, A>))
         .route("/trade", get(list_trades::<I, A>).post(create_trade::<I, A>))
+        .route("/trade/batch", post(batch_trades::<I, A>))
         .route("/trade/:id", delete(cancel_trade::<I, A>).get(get_trade_status::<I, A>))
         .route("/trade/:id/approve", post(approve_trade::<I, A>))
         .route("/trade/:id/book", post(book_trade::<I, A>))
         .route("/trade/:id/details", get(get_trade_details::<I, A>).put(update_trade::<I, A>))
         .route("/trade/:id/diff", get(trade_diff::<I, A>))
         .route("/trade/:id/history", get(get_trade_history::<I, A>))
+        .route("/trade/:id/revert", post(revert_trade::<I, A>))
         .route("/trade/:id/send", post(send_trade::<I, A>))
         .route("/trade/:id/submit", post(submit_trade::<I, A>))
         .with_state(api_impl)
@@ -41,11 +43,13 @@ where
 
 #[tracing::instrument(skip_all)]
 fn approve_trade_validation(
+    header_params: models::ApproveTradeHeaderParams,
     path_params: models::ApproveTradePathParams,
-) -> std::result::Result<(models::ApproveTradePathParams,), ValidationErrors> {
+) -> std::result::Result<(models::ApproveTradeHeaderParams, models::ApproveTradePathParams), ValidationErrors> {
+    header_params.validate()?;
     path_params.validate()?;
 
-    Ok((path_params,))
+    Ok((header_params, path_params))
 }
 
 /// ApproveTrade - POST /trade/{id}/approve
@@ -54,6 +58,7 @@ async fn approve_trade<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
+    headers: HeaderMap,
     Path(path_params): Path<models::ApproveTradePathParams>,
     State(api_impl): State<I>,
 ) -> Result<Response, StatusCode>
@@ -61,17 +66,108 @@ where
     I: AsRef<A> + Send + Sync,
     A: Api,
 {
+    // Header parameters
+    let header_params = {
+        let header_x_user_id = headers.get(HeaderName::from_static("x-user-id"));
+
+        let header_x_user_id = match header_x_user_id {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => result.0,
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-User-Id - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from("Missing required header X-User-Id"))
+                    .map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    });
+            }
+        };
+
+        let header_if_match = headers.get(HeaderName::from_static("if-match"));
+
+        let header_if_match = match header_if_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header If-Match - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_x_comment = headers.get(HeaderName::from_static("x-comment"));
+
+        let header_x_comment = match header_x_comment {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-Comment - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_idempotency_key = headers.get(HeaderName::from_static("idempotency-key"));
+
+        let header_idempotency_key = match header_idempotency_key {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Idempotency-Key - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::ApproveTradeHeaderParams {
+            x_user_id: header_x_user_id,
+            if_match: header_if_match,
+            x_comment: header_x_comment,
+            idempotency_key: header_idempotency_key,
+        }
+    };
+
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || approve_trade_validation(path_params)).await.unwrap();
+    let validation =
+        tokio::task::spawn_blocking(move || approve_trade_validation(header_params, path_params)).await.unwrap();
 
-    let Ok((path_params,)) = validation else {
+    let Ok((header_params, path_params)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().approve_trade(method, host, cookies, path_params).await;
+    let result = api_impl.as_ref().approve_trade(method, host, cookies, header_params, path_params).await;
 
     let mut response = Response::builder();
 
@@ -81,6 +177,98 @@ where
                 let mut response = response.status(204);
                 response.body(Body::empty())
             }
+            ApproveTradeResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            ApproveTradeResponse::Status403_ActionNotPermitted(body) => {
+                let mut response = response.status(403);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            ApproveTradeResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            ApproveTradeResponse::Status412_VersionConflict(body) => {
+                let mut response = response.status(412);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
         },
         Err(_) => {
             // Application code returned an error. This should not happen, as the implementation should
@@ -95,13 +283,168 @@ where
     })
 }
 
+#[derive(validator::Validate)]
+#[allow(dead_code)]
+struct BatchTradesBodyValidator<'a> {
+    #[validate]
+    body: &'a models::TradeBatchRequest,
+}
+
+#[tracing::instrument(skip_all)]
+fn batch_trades_validation(
+    header_params: models::BatchTradesHeaderParams,
+    body: models::TradeBatchRequest,
+) -> std::result::Result<(models::BatchTradesHeaderParams, models::TradeBatchRequest), ValidationErrors> {
+    header_params.validate()?;
+    let b = BatchTradesBodyValidator { body: &body };
+    b.validate()?;
+
+    Ok((header_params, body))
+}
+
+/// BatchTrades - POST /trade/batch
+#[tracing::instrument(skip_all)]
+async fn batch_trades<I, A>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    State(api_impl): State<I>,
+    Json(body): Json<models::TradeBatchRequest>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync,
+    A: Api,
+{
+    // Header parameters
+    let header_params = {
+        let header_x_user_id = headers.get(HeaderName::from_static("x-user-id"));
+
+        let header_x_user_id = match header_x_user_id {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => result.0,
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-User-Id - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from("Missing required header X-User-Id"))
+                    .map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    });
+            }
+        };
+
+        models::BatchTradesHeaderParams { x_user_id: header_x_user_id }
+    };
+
+    #[allow(clippy::redundant_closure)]
+    let validation = tokio::task::spawn_blocking(move || batch_trades_validation(header_params, body)).await.unwrap();
+
+    let Ok((header_params, body)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl.as_ref().batch_trades(method, host, cookies, header_params, body).await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            BatchTradesResponse::Status200_OneResultPerCommand(body) => {
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            BatchTradesResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(_) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            response.status(500).body(Body::empty())
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[derive(validator::Validate)]
+#[allow(dead_code)]
+struct BookTradeBodyValidator<'a> {
+    #[validate]
+    body: &'a models::ExecutionConfirmation,
+}
+
 #[tracing::instrument(skip_all)]
 fn book_trade_validation(
+    header_params: models::BookTradeHeaderParams,
     path_params: models::BookTradePathParams,
-) -> std::result::Result<(models::BookTradePathParams,), ValidationErrors> {
+    body: models::ExecutionConfirmation,
+) -> std::result::Result<
+    (models::BookTradeHeaderParams, models::BookTradePathParams, models::ExecutionConfirmation),
+    ValidationErrors,
+> {
+    header_params.validate()?;
     path_params.validate()?;
+    let b = BookTradeBodyValidator { body: &body };
+    b.validate()?;
 
-    Ok((path_params,))
+    Ok((header_params, path_params, body))
 }
 
 /// BookTrade - POST /trade/{id}/book
@@ -110,24 +453,98 @@ async fn book_trade<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
+    headers: HeaderMap,
     Path(path_params): Path<models::BookTradePathParams>,
     State(api_impl): State<I>,
+    Json(body): Json<models::ExecutionConfirmation>,
 ) -> Result<Response, StatusCode>
 where
     I: AsRef<A> + Send + Sync,
     A: Api,
 {
+    // Header parameters
+    let header_params = {
+        let header_x_user_id = headers.get(HeaderName::from_static("x-user-id"));
+
+        let header_x_user_id = match header_x_user_id {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => result.0,
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-User-Id - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from("Missing required header X-User-Id"))
+                    .map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    });
+            }
+        };
+
+        let header_if_match = headers.get(HeaderName::from_static("if-match"));
+
+        let header_if_match = match header_if_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header If-Match - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_idempotency_key = headers.get(HeaderName::from_static("idempotency-key"));
+
+        let header_idempotency_key = match header_idempotency_key {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Idempotency-Key - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::BookTradeHeaderParams {
+            x_user_id: header_x_user_id,
+            if_match: header_if_match,
+            idempotency_key: header_idempotency_key,
+        }
+    };
+
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || book_trade_validation(path_params)).await.unwrap();
+    let validation =
+        tokio::task::spawn_blocking(move || book_trade_validation(header_params, path_params, body)).await.unwrap();
 
-    let Ok((path_params,)) = validation else {
+    let Ok((header_params, path_params, body)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().book_trade(method, host, cookies, path_params).await;
+    let result = api_impl.as_ref().book_trade(method, host, cookies, header_params, path_params, body).await;
 
     let mut response = Response::builder();
 
@@ -137,6 +554,340 @@ where
                 let mut response = response.status(204);
                 response.body(Body::empty())
             }
+            BookTradeResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            BookTradeResponse::Status403_ActionNotPermitted(body) => {
+                let mut response = response.status(403);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            BookTradeResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            BookTradeResponse::Status412_VersionConflict(body) => {
+                let mut response = response.status(412);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+        },
+        Err(_) => {
+            // Application code returned an error. This should not happen, as the implementation should
+            // return a valid response.
+            response.status(500).body(Body::empty())
+        }
+    };
+
+    resp.map_err(|e| {
+        error!(error = ?e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+#[tracing::instrument(skip_all)]
+fn cancel_trade_validation(
+    header_params: models::CancelTradeHeaderParams,
+    path_params: models::CancelTradePathParams,
+) -> std::result::Result<(models::CancelTradeHeaderParams, models::CancelTradePathParams), ValidationErrors> {
+    header_params.validate()?;
+    path_params.validate()?;
+
+    Ok((header_params, path_params))
+}
+
+/// CancelTrade - DELETE /trade/{id}
+#[tracing::instrument(skip_all)]
+async fn cancel_trade<I, A>(
+    method: Method,
+    host: Host,
+    cookies: CookieJar,
+    headers: HeaderMap,
+    Path(path_params): Path<models::CancelTradePathParams>,
+    State(api_impl): State<I>,
+) -> Result<Response, StatusCode>
+where
+    I: AsRef<A> + Send + Sync,
+    A: Api,
+{
+    // Header parameters
+    let header_params = {
+        let header_x_user_id = headers.get(HeaderName::from_static("x-user-id"));
+
+        let header_x_user_id = match header_x_user_id {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => result.0,
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-User-Id - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from("Missing required header X-User-Id"))
+                    .map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    });
+            }
+        };
+
+        let header_if_match = headers.get(HeaderName::from_static("if-match"));
+
+        let header_if_match = match header_if_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header If-Match - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_x_comment = headers.get(HeaderName::from_static("x-comment"));
+
+        let header_x_comment = match header_x_comment {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-Comment - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_idempotency_key = headers.get(HeaderName::from_static("idempotency-key"));
+
+        let header_idempotency_key = match header_idempotency_key {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Idempotency-Key - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::CancelTradeHeaderParams {
+            x_user_id: header_x_user_id,
+            if_match: header_if_match,
+            x_comment: header_x_comment,
+            idempotency_key: header_idempotency_key,
+        }
+    };
+
+    #[allow(clippy::redundant_closure)]
+    let validation =
+        tokio::task::spawn_blocking(move || cancel_trade_validation(header_params, path_params)).await.unwrap();
+
+    let Ok((header_params, path_params)) = validation else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(validation.unwrap_err().to_string()))
+            .map_err(|_| StatusCode::BAD_REQUEST);
+    };
+
+    let result = api_impl.as_ref().cancel_trade(method, host, cookies, header_params, path_params).await;
+
+    let mut response = Response::builder();
+
+    let resp = match result {
+        Ok(rsp) => match rsp {
+            CancelTradeResponse::Status204_TradeCancelled => {
+                let mut response = response.status(204);
+                response.body(Body::empty())
+            }
+            CancelTradeResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            CancelTradeResponse::Status403_ActionNotPermitted(body) => {
+                let mut response = response.status(403);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            CancelTradeResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            CancelTradeResponse::Status412_VersionConflict(body) => {
+                let mut response = response.status(412);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
         },
         Err(_) => {
             // Application code returned an error. This should not happen, as the implementation should
@@ -151,47 +902,146 @@ where
     })
 }
 
+#[derive(validator::Validate)]
+#[allow(dead_code)]
+struct CreateTradeBodyValidator<'a> {
+    #[validate]
+    body: &'a models::TradeCreateRequest,
+}
+
 #[tracing::instrument(skip_all)]
-fn cancel_trade_validation(
-    path_params: models::CancelTradePathParams,
-) -> std::result::Result<(models::CancelTradePathParams,), ValidationErrors> {
-    path_params.validate()?;
+fn create_trade_validation(
+    header_params: models::CreateTradeHeaderParams,
+    body: models::TradeCreateRequest,
+) -> std::result::Result<(models::CreateTradeHeaderParams, models::TradeCreateRequest), ValidationErrors> {
+    header_params.validate()?;
+    let b = CreateTradeBodyValidator { body: &body };
+    b.validate()?;
 
-    Ok((path_params,))
+    Ok((header_params, body))
 }
 
-/// CancelTrade - DELETE /trade/{id}
+/// CreateTrade - POST /trade
 #[tracing::instrument(skip_all)]
-async fn cancel_trade<I, A>(
+async fn create_trade<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
-    Path(path_params): Path<models::CancelTradePathParams>,
+    headers: HeaderMap,
     State(api_impl): State<I>,
+    Json(body): Json<models::TradeCreateRequest>,
 ) -> Result<Response, StatusCode>
 where
     I: AsRef<A> + Send + Sync,
     A: Api,
 {
+    // Header parameters
+    let header_params = {
+        let header_idempotency_key = headers.get(HeaderName::from_static("idempotency-key"));
+
+        let header_idempotency_key = match header_idempotency_key {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Idempotency-Key - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::CreateTradeHeaderParams { idempotency_key: header_idempotency_key }
+    };
+
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || cancel_trade_validation(path_params)).await.unwrap();
+    let validation = tokio::task::spawn_blocking(move || create_trade_validation(header_params, body)).await.unwrap();
 
-    let Ok((path_params,)) = validation else {
+    let Ok((header_params, body)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().cancel_trade(method, host, cookies, path_params).await;
+    let result = api_impl.as_ref().create_trade(method, host, cookies, header_params, body).await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            CancelTradeResponse::Status204_TradeCancelled => {
-                let mut response = response.status(204);
-                response.body(Body::empty())
+            CreateTradeResponse::Status200_TradeCreated(body) => {
+                let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            CreateTradeResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            CreateTradeResponse::Status403_ActionNotPermitted(body) => {
+                let mut response = response.status(403);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
             }
         },
         Err(_) => {
@@ -207,54 +1057,112 @@ where
     })
 }
 
-#[derive(validator::Validate)]
-#[allow(dead_code)]
-struct CreateTradeBodyValidator<'a> {
-    #[validate]
-    body: &'a models::TradeCreateRequest,
-}
-
 #[tracing::instrument(skip_all)]
-fn create_trade_validation(
-    body: models::TradeCreateRequest,
-) -> std::result::Result<(models::TradeCreateRequest,), ValidationErrors> {
-    let b = CreateTradeBodyValidator { body: &body };
-    b.validate()?;
+fn get_trade_details_validation(
+    path_params: models::GetTradeDetailsPathParams,
+) -> std::result::Result<(models::GetTradeDetailsPathParams,), ValidationErrors> {
+    path_params.validate()?;
 
-    Ok((body,))
+    Ok((path_params,))
 }
 
-/// CreateTrade - POST /trade
+/// GetTradeDetails - GET /trade/{id}/details
 #[tracing::instrument(skip_all)]
-async fn create_trade<I, A>(
+async fn get_trade_details<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
+    Path(path_params): Path<models::GetTradeDetailsPathParams>,
     State(api_impl): State<I>,
-    Json(body): Json<models::TradeCreateRequest>,
 ) -> Result<Response, StatusCode>
 where
     I: AsRef<A> + Send + Sync,
     A: Api,
 {
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || create_trade_validation(body)).await.unwrap();
+    let validation = tokio::task::spawn_blocking(move || get_trade_details_validation(path_params)).await.unwrap();
 
-    let Ok((body,)) = validation else {
+    let Ok((path_params,)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().create_trade(method, host, cookies, body).await;
+    let result = api_impl.as_ref().get_trade_details(method, host, cookies, path_params).await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            CreateTradeResponse::Status200_TradeCreated(body) => {
+            GetTradeDetailsResponse::Status200_FullTradeDetails { body, e_tag } => {
+                let e_tag = match header::IntoHeaderValue(e_tag).try_into() {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::from(format!(
+                                "An internal server error occurred handling e_tag header - {}",
+                                e
+                            )))
+                            .map_err(|e| {
+                                error!(error = ?e);
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            });
+                    }
+                };
+
                 let mut response = response.status(200);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(HeaderName::from_static("etag"), e_tag);
+                }
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            GetTradeDetailsResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            GetTradeDetailsResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
                     response_headers.insert(
@@ -291,21 +1199,21 @@ where
 }
 
 #[tracing::instrument(skip_all)]
-fn get_trade_details_validation(
-    path_params: models::GetTradeDetailsPathParams,
-) -> std::result::Result<(models::GetTradeDetailsPathParams,), ValidationErrors> {
+fn get_trade_history_validation(
+    path_params: models::GetTradeHistoryPathParams,
+) -> std::result::Result<(models::GetTradeHistoryPathParams,), ValidationErrors> {
     path_params.validate()?;
 
     Ok((path_params,))
 }
 
-/// GetTradeDetails - GET /trade/{id}/details
+/// GetTradeHistory - GET /trade/{id}/history
 #[tracing::instrument(skip_all)]
-async fn get_trade_details<I, A>(
+async fn get_trade_history<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
-    Path(path_params): Path<models::GetTradeDetailsPathParams>,
+    Path(path_params): Path<models::GetTradeHistoryPathParams>,
     State(api_impl): State<I>,
 ) -> Result<Response, StatusCode>
 where
@@ -313,7 +1221,7 @@ where
     A: Api,
 {
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || get_trade_details_validation(path_params)).await.unwrap();
+    let validation = tokio::task::spawn_blocking(move || get_trade_history_validation(path_params)).await.unwrap();
 
     let Ok((path_params,)) = validation else {
         return Response::builder()
@@ -322,13 +1230,13 @@ where
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().get_trade_details(method, host, cookies, path_params).await;
+    let result = api_impl.as_ref().get_trade_history(method, host, cookies, path_params).await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            GetTradeDetailsResponse::Status200_FullTradeDetails(body) => {
+            GetTradeHistoryResponse::Status200_FullTradeState(body) => {
                 let mut response = response.status(200);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
@@ -366,21 +1274,24 @@ where
 }
 
 #[tracing::instrument(skip_all)]
-fn get_trade_history_validation(
-    path_params: models::GetTradeHistoryPathParams,
-) -> std::result::Result<(models::GetTradeHistoryPathParams,), ValidationErrors> {
+fn get_trade_status_validation(
+    path_params: models::GetTradeStatusPathParams,
+    query_params: models::GetTradeStatusQueryParams,
+) -> std::result::Result<(models::GetTradeStatusPathParams, models::GetTradeStatusQueryParams), ValidationErrors> {
     path_params.validate()?;
+    query_params.validate()?;
 
-    Ok((path_params,))
+    Ok((path_params, query_params))
 }
 
-/// GetTradeHistory - GET /trade/{id}/history
+/// GetTradeStatus - GET /trade/{id}
 #[tracing::instrument(skip_all)]
-async fn get_trade_history<I, A>(
+async fn get_trade_status<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
-    Path(path_params): Path<models::GetTradeHistoryPathParams>,
+    Path(path_params): Path<models::GetTradeStatusPathParams>,
+    Query(query_params): Query<models::GetTradeStatusQueryParams>,
     State(api_impl): State<I>,
 ) -> Result<Response, StatusCode>
 where
@@ -388,22 +1299,23 @@ where
     A: Api,
 {
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || get_trade_history_validation(path_params)).await.unwrap();
+    let validation =
+        tokio::task::spawn_blocking(move || get_trade_status_validation(path_params, query_params)).await.unwrap();
 
-    let Ok((path_params,)) = validation else {
+    let Ok((path_params, query_params)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().get_trade_history(method, host, cookies, path_params).await;
+    let result = api_impl.as_ref().get_trade_status(method, host, cookies, path_params, query_params).await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            GetTradeHistoryResponse::Status200_FullTradeState(body) => {
+            GetTradeStatusResponse::Status200_CurrentTradeStatus(body) => {
                 let mut response = response.status(200);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
@@ -416,6 +1328,52 @@ where
                     );
                 }
 
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            GetTradeStatusResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            GetTradeStatusResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
                 let body_content = tokio::task::spawn_blocking(move || {
                     serde_json::to_vec(&body).map_err(|e| {
                         error!(error = ?e);
@@ -441,21 +1399,16 @@ where
 }
 
 #[tracing::instrument(skip_all)]
-fn get_trade_status_validation(
-    path_params: models::GetTradeStatusPathParams,
-) -> std::result::Result<(models::GetTradeStatusPathParams,), ValidationErrors> {
-    path_params.validate()?;
-
-    Ok((path_params,))
+fn hello_validation() -> std::result::Result<(), ValidationErrors> {
+    Ok(())
 }
 
-/// GetTradeStatus - GET /trade/{id}
+/// Hello - GET /hello
 #[tracing::instrument(skip_all)]
-async fn get_trade_status<I, A>(
+async fn hello<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
-    Path(path_params): Path<models::GetTradeStatusPathParams>,
     State(api_impl): State<I>,
 ) -> Result<Response, StatusCode>
 where
@@ -463,22 +1416,22 @@ where
     A: Api,
 {
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || get_trade_status_validation(path_params)).await.unwrap();
+    let validation = tokio::task::spawn_blocking(move || hello_validation()).await.unwrap();
 
-    let Ok((path_params,)) = validation else {
+    let Ok(()) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().get_trade_status(method, host, cookies, path_params).await;
+    let result = api_impl.as_ref().hello(method, host, cookies).await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            GetTradeStatusResponse::Status200_CurrentTradeStatus(body) => {
+            HelloResponse::Status200_ReturnsAWelcomeMessage(body) => {
                 let mut response = response.status(200);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
@@ -516,16 +1469,21 @@ where
 }
 
 #[tracing::instrument(skip_all)]
-fn hello_validation() -> std::result::Result<(), ValidationErrors> {
-    Ok(())
+fn list_trades_validation(
+    query_params: models::ListTradesQueryParams,
+) -> std::result::Result<(models::ListTradesQueryParams,), ValidationErrors> {
+    query_params.validate()?;
+
+    Ok((query_params,))
 }
 
-/// Hello - GET /hello
+/// ListTrades - GET /trade
 #[tracing::instrument(skip_all)]
-async fn hello<I, A>(
+async fn list_trades<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
+    Query(query_params): Query<models::ListTradesQueryParams>,
     State(api_impl): State<I>,
 ) -> Result<Response, StatusCode>
 where
@@ -533,22 +1491,22 @@ where
     A: Api,
 {
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || hello_validation()).await.unwrap();
+    let validation = tokio::task::spawn_blocking(move || list_trades_validation(query_params)).await.unwrap();
 
-    let Ok(()) = validation else {
+    let Ok((query_params,)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().hello(method, host, cookies).await;
+    let result = api_impl.as_ref().list_trades(method, host, cookies, query_params).await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            HelloResponse::Status200_ReturnsAWelcomeMessage(body) => {
+            ListTradesResponse::Status200_APageOfTrades(body) => {
                 let mut response = response.status(200);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
@@ -561,6 +1519,29 @@ where
                     );
                 }
 
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            ListTradesResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
                 let body_content = tokio::task::spawn_blocking(move || {
                     serde_json::to_vec(&body).map_err(|e| {
                         error!(error = ?e);
@@ -586,45 +1567,220 @@ where
 }
 
 #[tracing::instrument(skip_all)]
-fn list_trades_validation(
-    query_params: models::ListTradesQueryParams,
-) -> std::result::Result<(models::ListTradesQueryParams,), ValidationErrors> {
+fn revert_trade_validation(
+    header_params: models::RevertTradeHeaderParams,
+    path_params: models::RevertTradePathParams,
+    query_params: models::RevertTradeQueryParams,
+) -> std::result::Result<
+    (models::RevertTradeHeaderParams, models::RevertTradePathParams, models::RevertTradeQueryParams),
+    ValidationErrors,
+> {
+    header_params.validate()?;
+    path_params.validate()?;
     query_params.validate()?;
 
-    Ok((query_params,))
+    Ok((header_params, path_params, query_params))
 }
 
-/// ListTrades - GET /trade
+/// RevertTrade - POST /trade/{id}/revert
 #[tracing::instrument(skip_all)]
-async fn list_trades<I, A>(
+async fn revert_trade<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
-    Query(query_params): Query<models::ListTradesQueryParams>,
+    headers: HeaderMap,
+    Path(path_params): Path<models::RevertTradePathParams>,
+    Query(query_params): Query<models::RevertTradeQueryParams>,
     State(api_impl): State<I>,
 ) -> Result<Response, StatusCode>
 where
     I: AsRef<A> + Send + Sync,
     A: Api,
 {
+    // Header parameters
+    let header_params = {
+        let header_x_user_id = headers.get(HeaderName::from_static("x-user-id"));
+
+        let header_x_user_id = match header_x_user_id {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => result.0,
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-User-Id - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from("Missing required header X-User-Id"))
+                    .map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    });
+            }
+        };
+
+        let header_if_match = headers.get(HeaderName::from_static("if-match"));
+
+        let header_if_match = match header_if_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header If-Match - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_x_comment = headers.get(HeaderName::from_static("x-comment"));
+
+        let header_x_comment = match header_x_comment {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-Comment - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_idempotency_key = headers.get(HeaderName::from_static("idempotency-key"));
+
+        let header_idempotency_key = match header_idempotency_key {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Idempotency-Key - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::RevertTradeHeaderParams {
+            x_user_id: header_x_user_id,
+            if_match: header_if_match,
+            x_comment: header_x_comment,
+            idempotency_key: header_idempotency_key,
+        }
+    };
+
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || list_trades_validation(query_params)).await.unwrap();
+    let validation =
+        tokio::task::spawn_blocking(move || revert_trade_validation(header_params, path_params, query_params))
+            .await
+            .unwrap();
 
-    let Ok((query_params,)) = validation else {
+    let Ok((header_params, path_params, query_params)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().list_trades(method, host, cookies, query_params).await;
+    let result = api_impl.as_ref().revert_trade(method, host, cookies, header_params, path_params, query_params).await;
 
     let mut response = Response::builder();
 
     let resp = match result {
         Ok(rsp) => match rsp {
-            ListTradesResponse::Status200_ListOfTradeIDs(body) => {
-                let mut response = response.status(200);
+            RevertTradeResponse::Status204_TradeReverted => {
+                let mut response = response.status(204);
+                response.body(Body::empty())
+            }
+            RevertTradeResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            RevertTradeResponse::Status403_ActionNotPermitted(body) => {
+                let mut response = response.status(403);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            RevertTradeResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            RevertTradeResponse::Status412_VersionConflict(body) => {
+                let mut response = response.status(412);
                 {
                     let mut response_headers = response.headers_mut().unwrap();
                     response_headers.insert(
@@ -662,11 +1818,13 @@ where
 
 #[tracing::instrument(skip_all)]
 fn send_trade_validation(
+    header_params: models::SendTradeHeaderParams,
     path_params: models::SendTradePathParams,
-) -> std::result::Result<(models::SendTradePathParams,), ValidationErrors> {
+) -> std::result::Result<(models::SendTradeHeaderParams, models::SendTradePathParams), ValidationErrors> {
+    header_params.validate()?;
     path_params.validate()?;
 
-    Ok((path_params,))
+    Ok((header_params, path_params))
 }
 
 /// SendTrade - POST /trade/{id}/send
@@ -675,6 +1833,7 @@ async fn send_trade<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
+    headers: HeaderMap,
     Path(path_params): Path<models::SendTradePathParams>,
     State(api_impl): State<I>,
 ) -> Result<Response, StatusCode>
@@ -682,17 +1841,108 @@ where
     I: AsRef<A> + Send + Sync,
     A: Api,
 {
+    // Header parameters
+    let header_params = {
+        let header_x_user_id = headers.get(HeaderName::from_static("x-user-id"));
+
+        let header_x_user_id = match header_x_user_id {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => result.0,
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-User-Id - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from("Missing required header X-User-Id"))
+                    .map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    });
+            }
+        };
+
+        let header_if_match = headers.get(HeaderName::from_static("if-match"));
+
+        let header_if_match = match header_if_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header If-Match - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_x_comment = headers.get(HeaderName::from_static("x-comment"));
+
+        let header_x_comment = match header_x_comment {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-Comment - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_idempotency_key = headers.get(HeaderName::from_static("idempotency-key"));
+
+        let header_idempotency_key = match header_idempotency_key {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Idempotency-Key - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::SendTradeHeaderParams {
+            x_user_id: header_x_user_id,
+            if_match: header_if_match,
+            x_comment: header_x_comment,
+            idempotency_key: header_idempotency_key,
+        }
+    };
+
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || send_trade_validation(path_params)).await.unwrap();
+    let validation =
+        tokio::task::spawn_blocking(move || send_trade_validation(header_params, path_params)).await.unwrap();
 
-    let Ok((path_params,)) = validation else {
+    let Ok((header_params, path_params)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().send_trade(method, host, cookies, path_params).await;
+    let result = api_impl.as_ref().send_trade(method, host, cookies, header_params, path_params).await;
 
     let mut response = Response::builder();
 
@@ -702,6 +1952,98 @@ where
                 let mut response = response.status(204);
                 response.body(Body::empty())
             }
+            SendTradeResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            SendTradeResponse::Status403_ActionNotPermitted(body) => {
+                let mut response = response.status(403);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            SendTradeResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            SendTradeResponse::Status412_VersionConflict(body) => {
+                let mut response = response.status(412);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
         },
         Err(_) => {
             // Application code returned an error. This should not happen, as the implementation should
@@ -718,11 +2060,13 @@ where
 
 #[tracing::instrument(skip_all)]
 fn submit_trade_validation(
+    header_params: models::SubmitTradeHeaderParams,
     path_params: models::SubmitTradePathParams,
-) -> std::result::Result<(models::SubmitTradePathParams,), ValidationErrors> {
+) -> std::result::Result<(models::SubmitTradeHeaderParams, models::SubmitTradePathParams), ValidationErrors> {
+    header_params.validate()?;
     path_params.validate()?;
 
-    Ok((path_params,))
+    Ok((header_params, path_params))
 }
 
 /// SubmitTrade - POST /trade/{id}/submit
@@ -731,6 +2075,7 @@ async fn submit_trade<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
+    headers: HeaderMap,
     Path(path_params): Path<models::SubmitTradePathParams>,
     State(api_impl): State<I>,
 ) -> Result<Response, StatusCode>
@@ -738,17 +2083,89 @@ where
     I: AsRef<A> + Send + Sync,
     A: Api,
 {
+    // Header parameters
+    let header_params = {
+        let header_x_user_id = headers.get(HeaderName::from_static("x-user-id"));
+
+        let header_x_user_id = match header_x_user_id {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => result.0,
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-User-Id - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from("Missing required header X-User-Id"))
+                    .map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    });
+            }
+        };
+
+        let header_if_match = headers.get(HeaderName::from_static("if-match"));
+
+        let header_if_match = match header_if_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header If-Match - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_idempotency_key = headers.get(HeaderName::from_static("idempotency-key"));
+
+        let header_idempotency_key = match header_idempotency_key {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Idempotency-Key - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::SubmitTradeHeaderParams {
+            x_user_id: header_x_user_id,
+            if_match: header_if_match,
+            idempotency_key: header_idempotency_key,
+        }
+    };
+
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || submit_trade_validation(path_params)).await.unwrap();
+    let validation =
+        tokio::task::spawn_blocking(move || submit_trade_validation(header_params, path_params)).await.unwrap();
 
-    let Ok((path_params,)) = validation else {
+    let Ok((header_params, path_params)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().submit_trade(method, host, cookies, path_params).await;
+    let result = api_impl.as_ref().submit_trade(method, host, cookies, header_params, path_params).await;
 
     let mut response = Response::builder();
 
@@ -758,6 +2175,98 @@ where
                 let mut response = response.status(204);
                 response.body(Body::empty())
             }
+            SubmitTradeResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            SubmitTradeResponse::Status403_ActionNotPermitted(body) => {
+                let mut response = response.status(403);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            SubmitTradeResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            SubmitTradeResponse::Status412_VersionConflict(body) => {
+                let mut response = response.status(412);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
         },
         Err(_) => {
             // Application code returned an error. This should not happen, as the implementation should
@@ -827,6 +2336,52 @@ where
                     );
                 }
 
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            TradeDiffResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            TradeDiffResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
                 let body_content = tokio::task::spawn_blocking(move || {
                     serde_json::to_vec(&body).map_err(|e| {
                         error!(error = ?e);
@@ -860,14 +2415,19 @@ struct UpdateTradeBodyValidator<'a> {
 
 #[tracing::instrument(skip_all)]
 fn update_trade_validation(
+    header_params: models::UpdateTradeHeaderParams,
     path_params: models::UpdateTradePathParams,
     body: models::TradeDetails,
-) -> std::result::Result<(models::UpdateTradePathParams, models::TradeDetails), ValidationErrors> {
+) -> std::result::Result<
+    (models::UpdateTradeHeaderParams, models::UpdateTradePathParams, models::TradeDetails),
+    ValidationErrors,
+> {
+    header_params.validate()?;
     path_params.validate()?;
     let b = UpdateTradeBodyValidator { body: &body };
     b.validate()?;
 
-    Ok((path_params, body))
+    Ok((header_params, path_params, body))
 }
 
 /// UpdateTrade - PUT /trade/{id}/details
@@ -876,6 +2436,7 @@ async fn update_trade<I, A>(
     method: Method,
     host: Host,
     cookies: CookieJar,
+    headers: HeaderMap,
     Path(path_params): Path<models::UpdateTradePathParams>,
     State(api_impl): State<I>,
     Json(body): Json<models::TradeDetails>,
@@ -884,17 +2445,108 @@ where
     I: AsRef<A> + Send + Sync,
     A: Api,
 {
+    // Header parameters
+    let header_params = {
+        let header_x_user_id = headers.get(HeaderName::from_static("x-user-id"));
+
+        let header_x_user_id = match header_x_user_id {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => result.0,
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-User-Id - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => {
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::from("Missing required header X-User-Id"))
+                    .map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    });
+            }
+        };
+
+        let header_if_match = headers.get(HeaderName::from_static("if-match"));
+
+        let header_if_match = match header_if_match {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header If-Match - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_x_comment = headers.get(HeaderName::from_static("x-comment"));
+
+        let header_x_comment = match header_x_comment {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header X-Comment - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        let header_idempotency_key = headers.get(HeaderName::from_static("idempotency-key"));
+
+        let header_idempotency_key = match header_idempotency_key {
+            Some(v) => match header::IntoHeaderValue::<String>::try_from((*v).clone()) {
+                Ok(result) => Some(result.0),
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from(format!("Invalid header Idempotency-Key - {}", err)))
+                        .map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        });
+                }
+            },
+            None => None,
+        };
+
+        models::UpdateTradeHeaderParams {
+            x_user_id: header_x_user_id,
+            if_match: header_if_match,
+            x_comment: header_x_comment,
+            idempotency_key: header_idempotency_key,
+        }
+    };
+
     #[allow(clippy::redundant_closure)]
-    let validation = tokio::task::spawn_blocking(move || update_trade_validation(path_params, body)).await.unwrap();
+    let validation =
+        tokio::task::spawn_blocking(move || update_trade_validation(header_params, path_params, body)).await.unwrap();
 
-    let Ok((path_params, body)) = validation else {
+    let Ok((header_params, path_params, body)) = validation else {
         return Response::builder()
             .status(StatusCode::BAD_REQUEST)
             .body(Body::from(validation.unwrap_err().to_string()))
             .map_err(|_| StatusCode::BAD_REQUEST);
     };
 
-    let result = api_impl.as_ref().update_trade(method, host, cookies, path_params, body).await;
+    let result = api_impl.as_ref().update_trade(method, host, cookies, header_params, path_params, body).await;
 
     let mut response = Response::builder();
 
@@ -904,6 +2556,98 @@ where
                 let mut response = response.status(204);
                 response.body(Body::empty())
             }
+            UpdateTradeResponse::Status400_RequestRejected(body) => {
+                let mut response = response.status(400);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            UpdateTradeResponse::Status403_ActionNotPermitted(body) => {
+                let mut response = response.status(403);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            UpdateTradeResponse::Status404_TradeNotFound(body) => {
+                let mut response = response.status(404);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
+            UpdateTradeResponse::Status412_VersionConflict(body) => {
+                let mut response = response.status(412);
+                {
+                    let mut response_headers = response.headers_mut().unwrap();
+                    response_headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str("application/json").map_err(|e| {
+                            error!(error = ?e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?,
+                    );
+                }
+
+                let body_content = tokio::task::spawn_blocking(move || {
+                    serde_json::to_vec(&body).map_err(|e| {
+                        error!(error = ?e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })
+                })
+                .await
+                .unwrap()?;
+                response.body(Body::from(body_content))
+            }
         },
         Err(_) => {
             // Application code returned an error. This should not happen, as the implementation should
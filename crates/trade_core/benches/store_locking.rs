@@ -0,0 +1,84 @@
+//! Concurrent modifies on the InMemoryStore: its own per-trade locking vs the same work
+//! behind one global lock (what the engine did before).
+//!
+//! Run with `cargo bench -p trade_core --bench store_locking`
+
+use chrono::Utc;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::thread;
+use trade_core::model::{Currency, Direction, Product, Trade, TradeDetails};
+use trade_core::store::{InMemoryStore, TradeStore};
+
+const THREADS: usize = 8;
+const TRADES: u64 = 64;
+const OPS_PER_THREAD: usize = 500;
+
+fn details() -> TradeDetails {
+    let now = Utc::now();
+    TradeDetails {
+        trading_entity: "Entity".into(),
+        counterparty: "Counterparty".into(),
+        direction: Direction::Buy,
+        notional_currency: Currency::USD,
+        notional_amount: Decimal::from(1_000),
+        underlying: vec![Currency::EUR, Currency::USD],
+        trade_date: now,
+        value_date: now,
+        delivery_date: now,
+        strike: None,
+        product: Product::Forward,
+    }
+}
+
+fn store() -> Arc<InMemoryStore> {
+    let store = InMemoryStore::new();
+    for id in 0..TRADES {
+        store.push(Trade::new(id, details(), "alice".into())).unwrap();
+    }
+    Arc::new(store)
+}
+
+/// Every thread appends a snapshot to the trades in turn, optionally holding `global` for each modify
+fn hammer(store: &Arc<InMemoryStore>, global: Option<Arc<Mutex<()>>>) {
+    let workers = (0..THREADS)
+        .map(|t| {
+            let store = Arc::clone(store);
+            let global = global.clone();
+            thread::spawn(move || {
+                for op in 0..OPS_PER_THREAD {
+                    let trade_id = ((t * OPS_PER_THREAD + op) as u64) % TRADES;
+                    let _guard = global.as_ref().map(|lock| lock.lock());
+                    store
+                        .modify(trade_id, &mut |trade| {
+                            let details = trade.latest_details().cloned().unwrap();
+                            trade.add_snapshot(format!("user{t}"), trade.current_state(), details);
+                            Ok(())
+                        })
+                        .unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for worker in workers {
+        worker.join().unwrap();
+    }
+}
+
+fn bench_locking(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("store_locking/{THREADS}x{OPS_PER_THREAD}"));
+    group.sample_size(20);
+    group.bench_function(BenchmarkId::new("per_trade", TRADES), |b| {
+        b.iter_batched(store, |store| hammer(&store, None), criterion::BatchSize::LargeInput)
+    });
+    group.bench_function(BenchmarkId::new("global_lock", TRADES), |b| {
+        b.iter_batched(store, |store| hammer(&store, Some(Arc::new(Mutex::new(())))), criterion::BatchSize::LargeInput)
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_locking);
+criterion_main!(benches);
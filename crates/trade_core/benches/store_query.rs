@@ -0,0 +1,91 @@
+//! Index lookups vs a full scan of the InMemoryStore, for the same queries.
+//!
+//! Run with `cargo bench -p trade_core --bench store_query`
+
+use chrono::Utc;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_decimal::Decimal;
+use trade_core::model::{Currency, Direction, Product, Trade, TradeDetails, TradeState};
+use trade_core::query::TradeQuery;
+use trade_core::store::{InMemoryStore, TradeStore};
+
+const STATES: [TradeState; 7] = [
+    TradeState::Draft,
+    TradeState::PendingApproval,
+    TradeState::NeedsReapproval,
+    TradeState::Approved,
+    TradeState::SentToCounterparty,
+    TradeState::Executed,
+    TradeState::Cancelled,
+];
+const COUNTERPARTIES: usize = 50;
+const REQUESTERS: usize = 20;
+
+fn details(i: usize) -> TradeDetails {
+    let now = Utc::now();
+    TradeDetails {
+        trading_entity: format!("Entity {}", i % 3),
+        counterparty: format!("Counterparty {}", i % COUNTERPARTIES),
+        direction: if i.is_multiple_of(2) { Direction::Buy } else { Direction::Sell },
+        notional_currency: Currency::USD,
+        notional_amount: Decimal::from(1_000 + i),
+        underlying: vec![Currency::EUR, Currency::USD],
+        trade_date: now,
+        value_date: now,
+        delivery_date: now,
+        strike: None,
+        product: Product::Forward,
+    }
+}
+
+fn store_with(size: usize) -> InMemoryStore {
+    let store = InMemoryStore::new();
+    for i in 0..size {
+        let user = format!("user{}", i % REQUESTERS);
+        let mut trade = Trade::new(i as u64 + 1, details(i), user.clone());
+        let state = STATES[i % STATES.len()];
+        if state != TradeState::Draft {
+            trade.add_snapshot(&user, state, details(i));
+        }
+        store.push(trade).unwrap();
+    }
+    store
+}
+
+/// What the store did before it had indexes
+fn full_scan(store: &InMemoryStore, query: &TradeQuery) -> usize {
+    let trades = store.keys().unwrap().into_iter().filter_map(|trade_id| store.get(trade_id).unwrap());
+    query.page(trades).unwrap().trades.len()
+}
+
+fn indexed(store: &InMemoryStore, query: &TradeQuery) -> usize {
+    query.page(store.candidates(query).unwrap()).unwrap().trades.len()
+}
+
+fn bench_queries(c: &mut Criterion) {
+    let queries = [
+        ("state", TradeQuery { state: Some(TradeState::PendingApproval), ..Default::default() }),
+        ("counterparty", TradeQuery { counterparty: Some("Counterparty 7".into()), ..Default::default() }),
+        (
+            "state+requester",
+            TradeQuery { state: Some(TradeState::Approved), requester: Some("user3".into()), ..Default::default() },
+        ),
+    ];
+
+    for size in [10_000, 100_000] {
+        let store = store_with(size);
+        let mut group = c.benchmark_group(format!("store_query/{size}"));
+        for (name, query) in &queries {
+            group.bench_with_input(BenchmarkId::new("indexed", name), query, |b, q| {
+                b.iter(|| indexed(black_box(&store), black_box(q)))
+            });
+            group.bench_with_input(BenchmarkId::new("full_scan", name), query, |b, q| {
+                b.iter(|| full_scan(black_box(&store), black_box(q)))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_queries);
+criterion_main!(benches);
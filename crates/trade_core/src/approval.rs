@@ -0,0 +1,427 @@
+//! Approval quorum (N-eyes) rules and approval limits
+//!
+//! By default one approval takes a trade from `PendingApproval` to `Approved`. Rules can ask for
+//! more: N distinct approvers, none of them the requester, for trades of a given trading entity,
+//! notional currency and/or notional band. The strictest matching rule wins.
+//!
+//! Until the quorum is met, each approval is recorded as a snapshot that leaves the trade in
+//! `PendingApproval`. Rules come from the `[approval]` config section, e.g.
+//!
+//! ```toml
+//! [[approval.rules]]
+//! trading_entity = "BigBank"
+//! notional_currency = "USD"
+//! notional_min = 10000000
+//! approvers = 2
+//! ```
+//!
+//! Re-approvals (from `NeedsReapproval`) need the quorum too. Where a rule asks for more than one
+//! approver, it replaces the workflow's `Approve` guards: the requester may not approve, not even a
+//! re-approval that `RequesterOnly` would otherwise leave to them alone.
+//!
+//! Approval limits (delegated authority) cap the notional each approver may approve, per user
+//! or per role, in the trade's own currency or in base currency equivalent. They apply to every
+//! approval, re-approvals included, and come from `[approval.limits]`, e.g.
+//!
+//! ```toml
+//! [approval.limits]
+//! base_currency = "USD"
+//! rates = { EUR = 1.08, GBP = 1.27 }
+//!
+//! [approval.limits.roles.Approver]
+//! base = 5000000
+//!
+//! [approval.limits.users.alice]
+//! currencies = { JPY = 100000000 }
+//! base = 20000000
+//! ```
+
+use crate::errors::ValidationError;
+use crate::model::{Currency, TradeDetails, UserId};
+use crate::policy::{Principal, Role};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// How many approvers trades matching every given filter need. Filters left out match anything.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ApprovalRule {
+    #[serde(default)]
+    pub trading_entity: Option<String>,
+    #[serde(default)]
+    pub notional_currency: Option<Currency>,
+    /// Inclusive
+    #[serde(default)]
+    pub notional_min: Option<Decimal>,
+    /// Exclusive
+    #[serde(default)]
+    pub notional_max: Option<Decimal>,
+    pub approvers: usize,
+}
+
+impl ApprovalRule {
+    pub fn matches(&self, details: &TradeDetails) -> bool {
+        self.trading_entity.as_ref().is_none_or(|entity| *entity == details.trading_entity)
+            && self.notional_currency.is_none_or(|ccy| ccy == details.notional_currency)
+            && self.notional_min.is_none_or(|min| details.notional_amount >= min)
+            && self.notional_max.is_none_or(|max| details.notional_amount < max)
+    }
+}
+
+/// The `[approval]` config section, no rules means a single approval will do
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ApprovalRules {
+    #[serde(default)]
+    pub rules: Vec<ApprovalRule>,
+
+    /// Delegated authority of each approver, none configured means no limits
+    #[serde(default)]
+    pub limits: ApprovalLimits,
+}
+
+impl ApprovalRules {
+    pub fn new(rules: Vec<ApprovalRule>) -> Result<Self, ValidationError> {
+        let rules = Self { rules, limits: ApprovalLimits::default() };
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// Same rules, with these approval limits
+    pub fn with_limits(mut self, limits: ApprovalLimits) -> Result<Self, ValidationError> {
+        limits.validate()?;
+        self.limits = limits;
+        Ok(self)
+    }
+
+    /// Every rule asks for at least one approver, over a band that isn't empty, and the limits make sense
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        for (i, rule) in self.rules.iter().enumerate() {
+            if rule.approvers == 0 {
+                return Err(ValidationError::InvalidApprovalRules(format!("rule {i} asks for no approvers")));
+            }
+            if let (Some(min), Some(max)) = (rule.notional_min, rule.notional_max) {
+                if min >= max {
+                    return Err(ValidationError::InvalidApprovalRules(format!(
+                        "rule {i} has notional_min {min} not below notional_max {max}"
+                    )));
+                }
+            }
+        }
+        self.limits.validate()
+    }
+
+    /// Distinct approvers a trade with these details needs, 1 if no rule matches
+    pub fn required_approvers(&self, details: &TradeDetails) -> usize {
+        self.rules.iter().filter(|rule| rule.matches(details)).map(|rule| rule.approvers).max().unwrap_or(1)
+    }
+}
+
+/// The largest notional one approver may approve. A currency listed in `currencies` is capped
+/// in that currency, any other one by `base` in base currency equivalent. A currency covered
+/// by neither (or with no rate to the base currency) counts as a limit of zero.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ApprovalLimit {
+    #[serde(default)]
+    pub currencies: HashMap<Currency, Decimal>,
+    #[serde(default)]
+    pub base: Option<Decimal>,
+}
+
+impl ApprovalLimit {
+    /// A limit in base currency equivalent
+    pub fn base(max: Decimal) -> Self {
+        Self { currencies: HashMap::new(), base: Some(max) }
+    }
+
+    /// Adds (or replaces) the limit for one currency
+    pub fn with_currency(mut self, currency: Currency, max: Decimal) -> Self {
+        self.currencies.insert(currency, max);
+        self
+    }
+}
+
+/// Approval limit registry: who may approve how much. A user's own limit takes precedence over
+/// the limits of their roles; with several roles the most generous one applies. Approvers with
+/// neither are not limited, so the registry only bites for the users and roles it names.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ApprovalLimits {
+    /// Currency `base` limits are expressed in
+    #[serde(default)]
+    pub base_currency: Option<Currency>,
+
+    /// Units of base currency per unit of each currency, the base currency itself is 1
+    #[serde(default)]
+    pub rates: HashMap<Currency, Decimal>,
+
+    #[serde(default)]
+    pub users: HashMap<UserId, ApprovalLimit>,
+
+    #[serde(default)]
+    pub roles: HashMap<Role, ApprovalLimit>,
+}
+
+impl ApprovalLimits {
+    pub fn with_user(mut self, user_id: impl Into<UserId>, limit: ApprovalLimit) -> Self {
+        self.users.insert(user_id.into(), limit);
+        self
+    }
+
+    pub fn with_role(mut self, role: Role, limit: ApprovalLimit) -> Self {
+        self.roles.insert(role, limit);
+        self
+    }
+
+    /// Base currency and the rate of one unit of `currency` in it
+    pub fn with_rate(mut self, base_currency: Currency, currency: Currency, rate: Decimal) -> Self {
+        self.base_currency = Some(base_currency);
+        self.rates.insert(currency, rate);
+        self
+    }
+
+    /// No negative limits, only positive rates, and a base currency for any base limit
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let invalid = |reason: String| Err(ValidationError::InvalidApprovalRules(reason));
+
+        let named = self.users.iter().map(|(user_id, limit)| (format!("user {user_id}"), limit));
+        let named = named.chain(self.roles.iter().map(|(role, limit)| (format!("role {role:?}"), limit)));
+        for (name, limit) in named {
+            if limit.base.is_some() && self.base_currency.is_none() {
+                return invalid(format!("limit for {name} is in base currency, but no base_currency is set"));
+            }
+            if limit.base.into_iter().chain(limit.currencies.values().copied()).any(|max| max.is_sign_negative()) {
+                return invalid(format!("limit for {name} is negative"));
+            }
+        }
+
+        if let Some((currency, rate)) = self.rates.iter().find(|(_, rate)| **rate <= Decimal::ZERO) {
+            return invalid(format!("rate for {currency} is {rate}, rates must be positive"));
+        }
+        Ok(())
+    }
+
+    /// Ok if the principal may approve a trade with these details, otherwise
+    /// `ApprovalLimitExceeded` naming their limit and the trade's notional
+    pub fn check(&self, principal: &Principal, details: &TradeDetails) -> Result<(), ValidationError> {
+        let limits: Vec<&ApprovalLimit> = match self.users.get(&principal.user_id) {
+            Some(limit) => vec![limit],
+            None => self.roles.iter().filter(|(role, _)| principal.has_role(**role)).map(|(_, limit)| limit).collect(),
+        };
+        if limits.is_empty() {
+            return Ok(());
+        }
+
+        let mut breaches = Vec::new();
+        for limit in limits {
+            match self.breach(limit, details) {
+                None => return Ok(()),
+                Some(breach) => breaches.push(breach),
+            }
+        }
+
+        // None of them allows it, report the one that comes closest
+        let (_, limit, attempted) = breaches.into_iter().max_by(|a, b| a.0.cmp(&b.0)).unwrap();
+        Err(ValidationError::ApprovalLimitExceeded(principal.user_id.clone(), limit, attempted))
+    }
+
+    /// None if the limit allows the trade's notional. Otherwise the share of the notional
+    /// the limit does cover, the limit and the attempted amount, for the error.
+    fn breach(&self, limit: &ApprovalLimit, details: &TradeDetails) -> Option<(Decimal, String, String)> {
+        let (currency, amount) = (details.notional_currency, details.notional_amount);
+        let attempted = format!("{amount} {currency}");
+
+        if let Some(max) = limit.currencies.get(&currency) {
+            return (amount > *max).then(|| (*max / amount, format!("{max} {currency}"), attempted));
+        }
+
+        // Otherwise in base currency, if the limit has one and there is a rate to get there
+        let base = limit.base.zip(self.base_currency).and_then(|(max, base)| {
+            let rate = if currency == base { Some(Decimal::ONE) } else { self.rates.get(&currency).copied() };
+            rate.map(|rate| (max, base, amount * rate))
+        });
+        let Some((max, base, equivalent)) = base else {
+            return Some((Decimal::ZERO, format!("0 {currency}"), attempted));
+        };
+
+        (equivalent > max).then(|| {
+            let attempted =
+                if currency == base { attempted } else { format!("{attempted} ({} {base})", equivalent.round_dp(2)) };
+            (max / equivalent, format!("{max} {base} equivalent"), attempted)
+        })
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for the approval rules
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Direction, Product};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn details(entity: &str, currency: Currency, amount: Decimal) -> TradeDetails {
+        let now = Utc::now();
+        TradeDetails {
+            trading_entity: entity.into(),
+            counterparty: "ClientCo".into(),
+            direction: Direction::Buy,
+            notional_currency: currency,
+            notional_amount: amount,
+            underlying: vec![currency],
+            trade_date: now,
+            value_date: now,
+            delivery_date: now,
+            strike: None,
+            product: Product::Forward,
+        }
+    }
+
+    fn rule(approvers: usize) -> ApprovalRule {
+        ApprovalRule {
+            trading_entity: None,
+            notional_currency: None,
+            notional_min: None,
+            notional_max: None,
+            approvers,
+        }
+    }
+
+    #[test]
+    fn test_no_rules_means_one_approver() {
+        assert_eq!(ApprovalRules::default().required_approvers(&details("BigBank", Currency::USD, dec!(1))), 1);
+    }
+
+    #[test]
+    fn test_strictest_matching_rule_wins() {
+        let rules = ApprovalRules::new(vec![
+            ApprovalRule { trading_entity: Some("BigBank".into()), ..rule(2) },
+            ApprovalRule { notional_currency: Some(Currency::USD), notional_min: Some(dec!(10_000_000)), ..rule(3) },
+            ApprovalRule { notional_max: Some(dec!(1_000)), ..rule(1) },
+        ])
+        .unwrap();
+
+        assert_eq!(rules.required_approvers(&details("BigBank", Currency::EUR, dec!(5_000))), 2);
+        assert_eq!(rules.required_approvers(&details("BigBank", Currency::USD, dec!(10_000_000))), 3);
+        assert_eq!(rules.required_approvers(&details("SmallBank", Currency::USD, dec!(9_999_999))), 1);
+        assert_eq!(rules.required_approvers(&details("SmallBank", Currency::USD, dec!(500))), 1);
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        let err = ApprovalRules::new(vec![rule(0)]).unwrap_err();
+        assert_eq!(err, ValidationError::InvalidApprovalRules("rule 0 asks for no approvers".into()));
+
+        let band = ApprovalRule { notional_min: Some(dec!(10)), notional_max: Some(dec!(10)), ..rule(2) };
+        assert!(matches!(ApprovalRules::new(vec![band]), Err(ValidationError::InvalidApprovalRules(_))));
+    }
+
+    #[test]
+    fn test_rules_from_toml() {
+        let rules: ApprovalRules = toml::from_str(
+            r#"
+            [[rules]]
+            trading_entity = "BigBank"
+            notional_currency = "USD"
+            notional_min = 10000000
+            approvers = 2
+            "#,
+        )
+        .expect("Rules should parse");
+
+        assert_eq!(rules.rules.len(), 1);
+        assert_eq!(rules.required_approvers(&details("BigBank", Currency::USD, dec!(20_000_000))), 2);
+    }
+
+    fn limits() -> ApprovalLimits {
+        ApprovalLimits::default()
+            .with_rate(Currency::USD, Currency::EUR, dec!(1.10))
+            .with_role(Role::Approver, ApprovalLimit::base(dec!(5_000_000)))
+            .with_role(Role::Operations, ApprovalLimit::default().with_currency(Currency::EUR, dec!(8_000_000)))
+            .with_user("junior", ApprovalLimit::default().with_currency(Currency::USD, dec!(100_000)))
+    }
+
+    fn check(
+        limits: &ApprovalLimits,
+        principal: &Principal,
+        currency: Currency,
+        amount: Decimal,
+    ) -> Result<(), String> {
+        limits.check(principal, &details("BigBank", currency, amount)).map_err(|err| match err {
+            ValidationError::ApprovalLimitExceeded(_, limit, attempted) => format!("{limit} / {attempted}"),
+            other => panic!("Unexpected error {other:?}"),
+        })
+    }
+
+    #[test]
+    fn test_limit_in_base_currency_equivalent() {
+        let limits = limits();
+        let approver = Principal::new("bob", [Role::Approver]);
+
+        assert!(check(&limits, &approver, Currency::USD, dec!(5_000_000)).is_ok());
+        assert!(check(&limits, &approver, Currency::EUR, dec!(4_000_000)).is_ok());
+        assert_eq!(
+            check(&limits, &approver, Currency::EUR, dec!(5_000_000)),
+            Err("5000000 USD equivalent / 5000000 EUR (5500000.00 USD)".into())
+        );
+
+        // No rate to the base currency, no authority
+        assert_eq!(check(&limits, &approver, Currency::GBP, dec!(1)), Err("0 GBP / 1 GBP".into()));
+    }
+
+    #[test]
+    fn test_user_limit_overrides_roles() {
+        let limits = limits();
+
+        let junior = Principal::new("junior", [Role::Approver]);
+        assert!(check(&limits, &junior, Currency::USD, dec!(100_000)).is_ok());
+        assert_eq!(check(&limits, &junior, Currency::USD, dec!(200_000)), Err("100000 USD / 200000 USD".into()));
+        assert_eq!(check(&limits, &junior, Currency::EUR, dec!(1)), Err("0 EUR / 1 EUR".into()));
+
+        // Most generous role wins, and anyone not in the registry is not limited
+        let both = Principal::new("dave", [Role::Approver, Role::Operations]);
+        assert!(check(&limits, &both, Currency::EUR, dec!(7_000_000)).is_ok());
+        assert!(check(&limits, &both, Currency::USD, dec!(5_000_000)).is_ok());
+        assert_eq!(check(&limits, &both, Currency::EUR, dec!(9_000_000)), Err("8000000 EUR / 9000000 EUR".into()));
+        assert!(check(&limits, &Principal::new("erin", [Role::Trader]), Currency::USD, dec!(1e9)).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_limits_are_rejected() {
+        let no_base = ApprovalLimits::default().with_user("bob", ApprovalLimit::base(dec!(1)));
+        assert!(matches!(no_base.validate(), Err(ValidationError::InvalidApprovalRules(_))));
+
+        let negative = limits().with_user("bob", ApprovalLimit::default().with_currency(Currency::USD, dec!(-1)));
+        assert_eq!(
+            negative.validate(),
+            Err(ValidationError::InvalidApprovalRules("limit for user bob is negative".into()))
+        );
+
+        let zero_rate = limits().with_rate(Currency::USD, Currency::GBP, Decimal::ZERO);
+        assert!(ApprovalRules::default().with_limits(zero_rate).is_err());
+    }
+
+    #[test]
+    fn test_limits_from_toml() {
+        let rules: ApprovalRules = toml::from_str(
+            r#"
+            [limits]
+            base_currency = "USD"
+            rates = { EUR = 1.08 }
+
+            [limits.roles.Approver]
+            base = 5000000
+
+            [limits.users.alice]
+            currencies = { JPY = 100000000 }
+            "#,
+        )
+        .expect("Limits should parse");
+
+        assert!(rules.rules.is_empty());
+        assert!(rules.validate().is_ok());
+        assert_eq!(rules.limits.rates[&Currency::EUR], dec!(1.08));
+        assert_eq!(rules.limits.roles[&Role::Approver], ApprovalLimit::base(dec!(5_000_000)));
+        assert_eq!(rules.limits.users["alice"].currencies[&Currency::JPY], dec!(100_000_000));
+    }
+}
@@ -0,0 +1,72 @@
+//! Many commands in one call, see `TradeEngine::execute_batch`
+//!
+//! A best-effort batch runs each command as if it had been sent on its own. An all-or-nothing
+//! batch has the engine to itself while it runs: its commands act on copies of the trades, staged
+//! here, and only once every one of them succeeded does the store get the lot in one `commit_batch`.
+//! The first failure drops the staged copies, so the store never sees part of the batch.
+
+use crate::errors::ValidationError;
+use crate::events::TradeEvent;
+use crate::model::{Trade, TradeId};
+use crate::store::{TradeMutation, TradeStore};
+use app_core::AppError;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+/// What happens to the rest of a batch when a command fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatchMode {
+    /// Every command is applied or none is, the first failure rolls the whole batch back
+    AllOrNothing,
+    /// Each command stands alone, a failure doesn't stop the others
+    BestEffort,
+}
+
+/// The trades an all-or-nothing batch created or changed so far, as they stand after its commands,
+/// and the events to publish once the store has them
+pub(crate) struct Staging<'s> {
+    store: &'s dyn TradeStore,
+    trades: Mutex<BTreeMap<TradeId, Trade>>,
+    events: Mutex<Vec<TradeEvent>>,
+}
+
+impl<'s> Staging<'s> {
+    pub(crate) fn new(store: &'s dyn TradeStore) -> Self {
+        Self { store, trades: Mutex::new(BTreeMap::new()), events: Mutex::new(Vec::new()) }
+    }
+
+    pub(crate) fn push(&self, trade: Trade) {
+        self.trades.lock().insert(trade.id, trade);
+    }
+
+    /// `TradeStore::modify` against the staged copy of the trade, taken from the store on first use.
+    /// A failed action leaves the copy as it was.
+    pub(crate) fn modify(&self, trade_id: TradeId, action: &mut TradeMutation) -> Result<(), AppError> {
+        let mut trades = self.trades.lock();
+        let trade = match trades.entry(trade_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let trade = self.store.get(trade_id).map_err(ValidationError::Internal)?;
+                entry.insert(trade.ok_or(ValidationError::TradeNotFound(trade_id))?)
+            }
+        };
+
+        let (stored, rejected) = (trade.history.len(), trade.rejections.len());
+        action(trade).inspect_err(|_| {
+            trade.history.truncate(stored);
+            trade.rejections.truncate(rejected);
+        })
+    }
+
+    /// Held back until the batch is committed
+    pub(crate) fn publish(&self, event: TradeEvent) {
+        self.events.lock().push(event);
+    }
+
+    /// What to commit, and what to publish after
+    pub(crate) fn into_parts(self) -> (Vec<Trade>, Vec<TradeEvent>) {
+        (self.trades.into_inner().into_values().collect(), self.events.into_inner())
+    }
+}
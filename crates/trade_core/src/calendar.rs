@@ -0,0 +1,320 @@
+//! Business-day calendars, one per currency
+//!
+//! A trade's value and delivery dates have to be business days in every currency of its
+//! `underlying`: not a weekend (Saturday, Sunday), and not a holiday in that currency's calendar.
+//! Currencies without a calendar are not checked, and neither is anything when no calendars
+//! are loaded at all (the engine's default). A failed check is an invalid value date (TVD12).
+//!
+//! Calendars are plain files, one per currency, named after it, e.g. a directory holding:
+//!
+//! ```text
+//! GBP.csv   # date,name - one holiday per line, `#` starts a comment
+//! EUR.ics   # iCalendar, every VEVENT's DTSTART (to DTEND, if it spans days) is a holiday
+//! ```
+//!
+//! Recurring events (`RRULE`) are not expanded, list each year's holidays instead.
+
+use crate::errors::ValidationError;
+use crate::model::{Currency, TradeDetails};
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Holidays of one currency, by date, with their names (empty if the file gave none)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HolidayCalendar {
+    holidays: BTreeMap<NaiveDate, String>,
+}
+
+impl HolidayCalendar {
+    pub fn new(holidays: impl IntoIterator<Item = (NaiveDate, String)>) -> Self {
+        Self { holidays: holidays.into_iter().collect() }
+    }
+
+    /// One holiday per line, `YYYY-MM-DD` optionally followed by `,name`.
+    /// Blank lines, `#` comments and a `date,...` header line are skipped.
+    pub fn from_csv(text: &str) -> Result<Self, ValidationError> {
+        let mut holidays = BTreeMap::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let (date, name) = line.split_once(',').unwrap_or((line, ""));
+            let date = date.trim();
+            if date.is_empty() || date.eq_ignore_ascii_case("date") {
+                continue;
+            }
+
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|e| calendar_error(format!("line {}: '{date}' {e}", n + 1)))?;
+            holidays.insert(date, name.trim().trim_matches('"').to_string());
+        }
+        Ok(Self { holidays })
+    }
+
+    /// Every `VEVENT` is a holiday named by its `SUMMARY`, from `DTSTART` up to (not including)
+    /// `DTEND` when there is one, the day of `DTSTART` only otherwise.
+    pub fn from_ics(text: &str) -> Result<Self, ValidationError> {
+        let mut holidays = BTreeMap::new();
+        let mut event: Option<(Option<NaiveDate>, Option<NaiveDate>, String)> = None;
+
+        for (n, line) in unfold_ics(text).iter().enumerate() {
+            let (key, value) = line.split_once(':').unwrap_or((line, ""));
+            // Parameters (e.g. DTSTART;VALUE=DATE) don't change what the date is
+            let key = key.split(';').next().unwrap_or_default().to_ascii_uppercase();
+            let ics_date = || {
+                value
+                    .get(..8)
+                    .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+                    .ok_or_else(|| calendar_error(format!("line {}: '{value}' is not a date", n + 1)))
+            };
+
+            match (key.as_str(), event.as_mut()) {
+                ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => event = Some((None, None, String::new())),
+                ("DTSTART", Some((start, _, _))) => *start = Some(ics_date()?),
+                ("DTEND", Some((_, end, _))) => *end = Some(ics_date()?),
+                ("SUMMARY", Some((_, _, name))) => *name = value.trim().to_string(),
+                ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                    let Some((Some(start), end, name)) = event.take() else {
+                        return Err(calendar_error(format!("line {}: event without a DTSTART", n + 1)));
+                    };
+                    let end = end.filter(|end| *end > start).unwrap_or_else(|| start.succ_opt().unwrap_or(start));
+                    for date in start.iter_days().take_while(|date| *date < end) {
+                        holidays.insert(date, name.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(Self { holidays })
+    }
+
+    /// Loads a `.csv` or `.ics` calendar file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ValidationError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| calendar_error(format!("{}: {e}", path.display())))?;
+        let in_file = |e: ValidationError| match e {
+            ValidationError::InvalidCalendar(reason) => calendar_error(format!("{}: {reason}", path.display())),
+            other => other,
+        };
+
+        match extension(path).as_deref() {
+            Some("csv") => Self::from_csv(&text).map_err(in_file),
+            Some("ics") => Self::from_ics(&text).map_err(in_file),
+            _ => Err(calendar_error(format!("{}: expected a .csv or .ics file", path.display()))),
+        }
+    }
+
+    /// The holiday's name if the date is one ("" when unnamed), None otherwise
+    pub fn holiday(&self, date: NaiveDate) -> Option<&str> {
+        self.holidays.get(&date).map(String::as_str)
+    }
+
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !is_weekend(date) && self.holiday(date).is_none()
+    }
+
+    /// Why the date is not a business day, None if it is one
+    fn closed_because(&self, date: NaiveDate) -> Option<&str> {
+        if is_weekend(date) {
+            return Some("weekend");
+        }
+        self.holiday(date).map(|name| if name.is_empty() { "holiday" } else { name })
+    }
+}
+
+/// The calendar of each currency that has one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Calendars {
+    by_currency: HashMap<Currency, HolidayCalendar>,
+}
+
+impl Calendars {
+    /// Sets the currency's calendar, replacing any it had
+    pub fn with_calendar(mut self, currency: Currency, calendar: HolidayCalendar) -> Self {
+        self.by_currency.insert(currency, calendar);
+        self
+    }
+
+    /// Loads every `<currency>.csv` and `<currency>.ics` in the directory, e.g. `GBP.csv`.
+    /// Other files are ignored, a currency with two files is refused.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, ValidationError> {
+        let dir = dir.as_ref();
+        let entries = std::fs::read_dir(dir).map_err(|e| calendar_error(format!("{}: {e}", dir.display())))?;
+
+        let mut calendars = Self::default();
+        for entry in entries {
+            let path = entry.map_err(|e| calendar_error(format!("{}: {e}", dir.display())))?.path();
+            let Some(currency) = path.file_stem().and_then(|stem| stem.to_str()?.parse::<Currency>().ok()) else {
+                continue;
+            };
+            if !matches!(extension(&path).as_deref(), Some("csv" | "ics")) {
+                continue;
+            }
+            if calendars.by_currency.contains_key(&currency) {
+                return Err(calendar_error(format!("{}: more than one calendar for {currency}", dir.display())));
+            }
+            calendars.by_currency.insert(currency, HolidayCalendar::load(&path)?);
+        }
+        Ok(calendars)
+    }
+
+    pub fn get(&self, currency: Currency) -> Option<&HolidayCalendar> {
+        self.by_currency.get(&currency)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_currency.is_empty()
+    }
+
+    /// A business day for the currency, always true for currencies without a calendar
+    pub fn is_business_day(&self, currency: Currency, date: NaiveDate) -> bool {
+        self.get(currency).is_none_or(|calendar| calendar.is_business_day(date))
+    }
+
+    /// Value and delivery dates must be business days in every currency of the underlying
+    pub fn check(&self, details: &TradeDetails) -> Result<(), ValidationError> {
+        for (what, date) in [("Value", details.value_date), ("Delivery", details.delivery_date)] {
+            for &currency in &details.underlying {
+                let Some(calendar) = self.get(currency) else {
+                    continue;
+                };
+                if let Some(why) = calendar.closed_because(date.date_naive()) {
+                    let reason =
+                        format!("{what} date {} is not a business day in {currency}: {why}", date.date_naive());
+                    return Err(ValidationError::InvalidValueDate(date, reason));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase)
+}
+
+/// ICS lines longer than 75 octets carry on in the next line, after a space or tab
+fn unfold_ics(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.trim_end().to_string()),
+        }
+    }
+    lines
+}
+
+fn calendar_error(reason: String) -> ValidationError {
+    ValidationError::InvalidCalendar(reason)
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for holiday calendars
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Direction, Product};
+    use chrono::{TimeZone, Utc};
+    use rust_decimal_macros::dec;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn gbp() -> HolidayCalendar {
+        HolidayCalendar::from_csv("# London\ndate,name\n2025-12-25,Christmas Day\n2025-12-26 # Boxing Day\n").unwrap()
+    }
+
+    fn details(value: (i32, u32, u32), delivery: (i32, u32, u32)) -> TradeDetails {
+        let at = |(y, m, d)| Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap();
+        TradeDetails {
+            trading_entity: "BigBank".into(),
+            counterparty: "ClientCo".into(),
+            direction: Direction::Buy,
+            notional_currency: Currency::GBP,
+            notional_amount: dec!(1000000),
+            underlying: vec![Currency::GBP, Currency::USD],
+            trade_date: at((2025, 12, 19)),
+            value_date: at(value),
+            delivery_date: at(delivery),
+            strike: None,
+            product: Product::Forward,
+        }
+    }
+
+    #[test]
+    fn test_csv_calendar() {
+        let calendar = gbp();
+        assert_eq!(calendar.holiday(date(2025, 12, 25)), Some("Christmas Day"));
+        assert_eq!(calendar.holiday(date(2025, 12, 26)), Some(""));
+        assert!(!calendar.is_business_day(date(2025, 12, 27)), "Saturday");
+        assert!(calendar.is_business_day(date(2025, 12, 29)));
+
+        let err = HolidayCalendar::from_csv("2025-12-25\n2025-13-01,Nope").unwrap_err();
+        assert!(matches!(err, ValidationError::InvalidCalendar(reason) if reason.starts_with("line 2")));
+    }
+
+    #[test]
+    fn test_ics_calendar() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\nDTSTART;VALUE=DATE:20251225\r\nDTEND;VALUE=DATE:20251227\r\nSUMMARY:Christmas\r\n  holidays\r\nEND:VEVENT\r\n\
+            BEGIN:VEVENT\r\nSUMMARY:Labour Day\r\nDTSTART:20250501T000000Z\r\nEND:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+        let calendar = HolidayCalendar::from_ics(ics).unwrap();
+        assert_eq!(calendar.holiday(date(2025, 12, 25)), Some("Christmas holidays"));
+        assert_eq!(calendar.holiday(date(2025, 12, 26)), Some("Christmas holidays"));
+        assert_eq!(calendar.holiday(date(2025, 12, 27)), None, "DTEND is exclusive");
+        assert_eq!(calendar.holiday(date(2025, 5, 1)), Some("Labour Day"));
+
+        let no_start = "BEGIN:VEVENT\nSUMMARY:Someday\nEND:VEVENT\n";
+        assert!(matches!(HolidayCalendar::from_ics(no_start), Err(ValidationError::InvalidCalendar(_))));
+    }
+
+    #[test]
+    fn test_dates_must_be_business_days_in_every_underlying_currency() {
+        let calendars = Calendars::default()
+            .with_calendar(Currency::GBP, gbp())
+            .with_calendar(Currency::USD, HolidayCalendar::new([(date(2025, 12, 24), "Half day".into())]));
+
+        assert!(calendars.check(&details((2025, 12, 23), (2025, 12, 29))).is_ok());
+
+        // Boxing Day in London
+        let err = calendars.check(&details((2025, 12, 23), (2025, 12, 26))).unwrap_err();
+        assert!(matches!(err, ValidationError::InvalidValueDate(_, ref reason)
+            if reason == "Delivery date 2025-12-26 is not a business day in GBP: holiday"));
+
+        // Fine in London, not in New York
+        let err = calendars.check(&details((2025, 12, 24), (2025, 12, 29))).unwrap_err();
+        assert!(matches!(err, ValidationError::InvalidValueDate(_, ref reason) if reason.ends_with("USD: Half day")));
+
+        // Weekends are out wherever there is a calendar
+        let err = calendars.check(&details((2025, 12, 20), (2025, 12, 29))).unwrap_err();
+        assert!(matches!(err, ValidationError::InvalidValueDate(_, ref reason) if reason.ends_with("GBP: weekend")));
+
+        // No calendars, no checks
+        assert!(Calendars::default().check(&details((2025, 12, 20), (2025, 12, 26))).is_ok());
+    }
+
+    #[test]
+    fn test_load_dir_picks_calendars_by_currency() {
+        let dir = std::env::temp_dir().join(format!("validus_calendars_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("GBP.csv"), "2025-12-25,Christmas Day\n").unwrap();
+        std::fs::write(dir.join("EUR.ics"), "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20250501\nEND:VEVENT\n").unwrap();
+        std::fs::write(dir.join("README.txt"), "not a calendar").unwrap();
+
+        let calendars = Calendars::load_dir(&dir).unwrap();
+        assert!(!calendars.is_business_day(Currency::GBP, date(2025, 12, 25)));
+        assert!(!calendars.is_business_day(Currency::EUR, date(2025, 5, 1)));
+        assert!(calendars.is_business_day(Currency::USD, date(2025, 12, 25)), "No USD calendar");
+
+        std::fs::write(dir.join("GBP.ics"), "").unwrap();
+        assert!(matches!(Calendars::load_dir(&dir), Err(ValidationError::InvalidCalendar(_))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,97 @@
+//! Engine commands as data: what `TradeEngine::execute` runs
+//!
+//! Each command is one of the engine's actions with everything it needs, the acting user included,
+//! so a command can be compared with another (e.g. when replaying an idempotency key) or queued up.
+
+use crate::model::{ExecutionConfirmation, SnapshotId, TradeDetails, TradeId, TradeState, UserId};
+
+/// One engine action, with its arguments. `expected_version` is the optimistic concurrency check
+/// of the engine's actions (TVC15 if the trade has moved on), `comment` the user's reason.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TradeCommand {
+    Create {
+        user_id: UserId,
+        details: TradeDetails,
+    },
+    Submit {
+        user_id: UserId,
+        trade_id: TradeId,
+        expected_version: Option<SnapshotId>,
+    },
+    Approve {
+        user_id: UserId,
+        trade_id: TradeId,
+        expected_version: Option<SnapshotId>,
+        comment: Option<String>,
+    },
+    Cancel {
+        user_id: UserId,
+        trade_id: TradeId,
+        expected_version: Option<SnapshotId>,
+        comment: Option<String>,
+    },
+    Update {
+        user_id: UserId,
+        trade_id: TradeId,
+        details: TradeDetails,
+        expected_version: Option<SnapshotId>,
+        comment: Option<String>,
+    },
+    /// Brings back the details of an earlier snapshot, see `TradeEngine::revert`
+    Revert {
+        user_id: UserId,
+        trade_id: TradeId,
+        snapshot_id: SnapshotId,
+        expected_version: Option<SnapshotId>,
+        comment: Option<String>,
+    },
+    SendToExecute {
+        user_id: UserId,
+        trade_id: TradeId,
+        expected_version: Option<SnapshotId>,
+        comment: Option<String>,
+    },
+    Book {
+        user_id: UserId,
+        trade_id: TradeId,
+        expected_version: Option<SnapshotId>,
+        confirmation: ExecutionConfirmation,
+    },
+}
+
+impl TradeCommand {
+    /// Who is acting
+    pub fn user_id(&self) -> &str {
+        match self {
+            TradeCommand::Create { user_id, .. }
+            | TradeCommand::Submit { user_id, .. }
+            | TradeCommand::Approve { user_id, .. }
+            | TradeCommand::Cancel { user_id, .. }
+            | TradeCommand::Update { user_id, .. }
+            | TradeCommand::Revert { user_id, .. }
+            | TradeCommand::SendToExecute { user_id, .. }
+            | TradeCommand::Book { user_id, .. } => user_id,
+        }
+    }
+
+    /// The trade acted on, None when creating one
+    pub fn trade_id(&self) -> Option<TradeId> {
+        match self {
+            TradeCommand::Create { .. } => None,
+            TradeCommand::Submit { trade_id, .. }
+            | TradeCommand::Approve { trade_id, .. }
+            | TradeCommand::Cancel { trade_id, .. }
+            | TradeCommand::Update { trade_id, .. }
+            | TradeCommand::Revert { trade_id, .. }
+            | TradeCommand::SendToExecute { trade_id, .. }
+            | TradeCommand::Book { trade_id, .. } => Some(*trade_id),
+        }
+    }
+}
+
+/// What a command did: the trade it acted on (or created), and the state it left the trade in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandOutcome {
+    pub trade_id: TradeId,
+    pub state: TradeState,
+}
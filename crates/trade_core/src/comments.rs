@@ -0,0 +1,125 @@
+//! Why a trade changed: the comment (reason) a user gives with a workflow action
+//!
+//! `cancel`, `update`, `approve` and `send_to_execute` take an optional comment, kept on the
+//! snapshot the action adds. Comments are trimmed, a blank one counts as none.
+//!
+//! They can be made mandatory for cancelling, and for updating a trade that has already been
+//! approved, from the `[comments]` config section, e.g.
+//!
+//! ```toml
+//! [comments]
+//! cancel = true
+//! update_after_approval = true
+//! ```
+//!
+//! An action missing a mandatory comment is refused with `ValidationError::CommentRequired` (TCR22).
+
+use crate::errors::ValidationError;
+use crate::model::{Trade, TradeAction};
+use serde::Deserialize;
+
+/// Which actions need a comment, nothing by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct CommentRules {
+    #[serde(default)]
+    pub cancel: bool,
+
+    /// Updates to a trade that has been approved at some point (i.e. that send it back for re-approval)
+    #[serde(default)]
+    pub update_after_approval: bool,
+}
+
+impl CommentRules {
+    /// Whether the action needs a comment on this trade, as it stands before the action
+    pub fn is_required(&self, action: TradeAction, trade: &Trade) -> bool {
+        match action {
+            TradeAction::Cancel => self.cancel,
+            TradeAction::Update => self.update_after_approval && trade.was_approved(),
+            _ => false,
+        }
+    }
+
+    /// The comment to keep on the snapshot, or `CommentRequired` if it is missing but mandatory
+    pub fn check(
+        &self,
+        action: TradeAction,
+        trade: &Trade,
+        comment: Option<&str>,
+    ) -> Result<Option<String>, ValidationError> {
+        let comment = comment.map(str::trim).filter(|c| !c.is_empty());
+        if comment.is_none() && self.is_required(action, trade) {
+            return Err(ValidationError::CommentRequired(action));
+        }
+        Ok(comment.map(String::from))
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for the comment rules
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Currency, Direction, Product, TradeDetails, TradeState};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn trade() -> Trade {
+        let now = Utc::now();
+        let details = TradeDetails {
+            trading_entity: "BigBank".into(),
+            counterparty: "ClientCo".into(),
+            direction: Direction::Buy,
+            notional_currency: Currency::USD,
+            notional_amount: dec!(1_000),
+            underlying: vec![Currency::USD],
+            trade_date: now,
+            value_date: now,
+            delivery_date: now,
+            strike: None,
+            product: Product::Forward,
+        };
+        Trade::new(1, details, "alice".into())
+    }
+
+    #[test]
+    fn test_comments_are_optional_by_default() {
+        let rules = CommentRules::default();
+        assert_eq!(rules.check(TradeAction::Cancel, &trade(), None), Ok(None));
+        assert_eq!(rules.check(TradeAction::Approve, &trade(), Some("  fine  ")), Ok(Some("fine".into())));
+    }
+
+    #[test]
+    fn test_cancel_needs_a_comment() {
+        let rules = CommentRules { cancel: true, ..Default::default() };
+        assert_eq!(
+            rules.check(TradeAction::Cancel, &trade(), Some("   ")),
+            Err(ValidationError::CommentRequired(TradeAction::Cancel))
+        );
+        assert_eq!(rules.check(TradeAction::Cancel, &trade(), Some("Duplicate")), Ok(Some("Duplicate".into())));
+        assert_eq!(rules.check(TradeAction::Update, &trade(), None), Ok(None));
+    }
+
+    #[test]
+    fn test_update_needs_a_comment_once_approved() {
+        let rules = CommentRules { update_after_approval: true, ..Default::default() };
+        let mut trade = trade();
+        assert!(!rules.is_required(TradeAction::Update, &trade));
+
+        let details = trade.latest_details_shared().unwrap();
+        trade.add_snapshot("alice", TradeState::PendingApproval, details.clone());
+        trade.add_snapshot("bob", TradeState::Approved, details.clone());
+        assert!(rules.is_required(TradeAction::Update, &trade));
+
+        // Still so after the update that sent it back for re-approval
+        trade.add_snapshot("alice", TradeState::NeedsReapproval, details);
+        assert!(rules.is_required(TradeAction::Update, &trade));
+        assert!(!rules.is_required(TradeAction::Approve, &trade));
+    }
+
+    #[test]
+    fn test_rules_from_toml() {
+        let rules: CommentRules = toml::from_str("cancel = true").expect("Rules should parse");
+        assert_eq!(rules, CommentRules { cancel: true, update_after_approval: false });
+    }
+}
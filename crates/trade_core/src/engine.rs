@@ -1,315 +1,859 @@
 use app_core::config::config_int;
-use app_core::AppError;
+use app_core::{AppError, ErrorCode};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::RwLock;
 use serde_json::json;
-use std::sync::{Arc, Mutex};
-
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::approval::ApprovalRules;
+use crate::batch::{BatchMode, Staging};
+use crate::calendar::Calendars;
+use crate::clock::{Clock, SystemClock};
+use crate::command::{CommandOutcome, TradeCommand};
+use crate::comments::CommentRules;
 use crate::errors::{ErrCodes, ValidationError};
-use crate::model::TradeState::NeedsReapproval;
+use crate::events::{EventBus, TradeEvent};
+use crate::idempotency::{IdempotencyCache, DEFAULT_IDEMPOTENCY_WINDOW};
 use crate::model::*;
-use crate::snowflake::SnowflakeIdGenerator;
-use crate::state::StateMachine;
+use crate::policy::{AllowAll, Operation, Policy};
+use crate::query::{TradePage, TradeQuery};
+use crate::snowflake::{IdGenerator, SnowflakeIdGenerator};
+use crate::state::{Guard, StateMachine};
 use crate::store::{InMemoryStore, TradeStore};
+use crate::tenor::{DateRoller, Tenor, TenorDates};
 use crate::util::{diff_details, TradeDiff};
 
 pub struct TradeEngine {
-    /// Snowflake generator encapsulated in the engine
-    id_gen: SnowflakeIdGenerator,
+    /// ID generator encapsulated in the engine (Snowflake unless the builder says otherwise)
+    id_gen: Box<dyn IdGenerator>,
 
-    /// Shared, thread-safe, and mutable trade store:
-    /// - `Arc<Mutex dyn`: shared ownership across threads with mutability supporting trait objects.
+    /// Shared, thread-safe trade store:
+    /// - `Arc<dyn`: shared ownership across threads, supporting trait objects.
+    /// - No engine level lock, the store locks per trade (see `TradeStore::modify`),
+    ///   only an all-or-nothing batch keeps everyone else out while it runs.
     /// - `Send + Sync + 'static`: safe cross-thread usage.
-    store: Arc<Mutex<dyn TradeStore + Send + Sync + 'static>>,
+    store: Arc<dyn TradeStore + Send + Sync + 'static>,
 
     /// State machine logic can be updated without much touching engine code
     state_machine: StateMachine,
+
+    /// Where snapshot timestamps come from
+    clock: Box<dyn Clock>,
+
+    /// Lifecycle events go out here once the store has the change
+    events: EventBus,
+
+    /// Who may do what, asked before every action
+    policy: Box<dyn Policy>,
+
+    /// How many distinct approvers a trade needs
+    approval_rules: ApprovalRules,
+
+    /// Which actions need a comment
+    comment_rules: CommentRules,
+
+    /// Business days of each currency, value and delivery dates must fall on one
+    calendars: Calendars,
+
+    /// Outcomes of commands run under an idempotency key, see `execute`
+    idempotency: IdempotencyCache,
+
+    /// Single actions share it, an all-or-nothing batch has it to itself (see `execute_batch`)
+    batch_lock: RwLock<()>,
 }
 
-/// Meat and potatoes of the trade engine
-impl<'a> TradeEngine {
-    /// Helper method to access the store properly
-    fn store_lock(&self) -> Result<std::sync::MutexGuard<'_, dyn TradeStore + Send + Sync + 'static>, ValidationError> {
-        self.store.lock().map_err(|_| ValidationError::Internal("Failed to acquire store lock".into()))
+/// Where an action is applied: straight to the store, or to the staged copies of an all-or-nothing batch
+#[derive(Clone, Copy)]
+enum Target<'t> {
+    Store,
+    Staged(&'t Staging<'t>),
+}
+
+/// Assembles a TradeEngine from its parts, anything not given falls back to the defaults:
+/// - store: `InMemoryStore`
+/// - id generator: Snowflake, machine ID from `engine.machine_id` config
+/// - state machine: the standard trade workflow
+/// - clock: system time
+/// - event bus: default capacity, no subscribers
+/// - policy: `AllowAll`, anyone may do anything
+/// - approval rules: none, one approval will do
+/// - comment rules: comments are optional everywhere
+/// - calendars: none, any day will do
+/// - idempotency window: 24 hours
+#[derive(Default)]
+pub struct TradeEngineBuilder {
+    store: Option<Arc<dyn TradeStore + Send + Sync + 'static>>,
+    id_gen: Option<Box<dyn IdGenerator>>,
+    state_machine: Option<StateMachine>,
+    clock: Option<Box<dyn Clock>>,
+    events: Option<EventBus>,
+    policy: Option<Box<dyn Policy>>,
+    approval_rules: Option<ApprovalRules>,
+    comment_rules: Option<CommentRules>,
+    calendars: Option<Calendars>,
+    idempotency_window: Option<Duration>,
+}
+
+impl TradeEngineBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Any TradeStore implementation - in-memory, SQLite etc
+    pub fn store(mut self, store: impl TradeStore + 'static) -> Self {
+        self.store = Some(Arc::new(store));
+        self
+    }
+
+    pub fn id_generator(mut self, id_gen: impl IdGenerator + 'static) -> Self {
+        self.id_gen = Some(Box::new(id_gen));
+        self
+    }
+
+    pub fn state_machine(mut self, state_machine: StateMachine) -> Self {
+        self.state_machine = Some(state_machine);
+        self
+    }
+
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    /// Event bus with subscribers already attached, or a non-default capacity
+    pub fn event_bus(mut self, events: EventBus) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Authorization for every action, e.g. a `RolePolicy`
+    pub fn policy(mut self, policy: impl Policy + 'static) -> Self {
+        self.policy = Some(Box::new(policy));
+        self
+    }
+
+    /// Approval quorum (N-eyes) rules
+    pub fn approval_rules(mut self, approval_rules: ApprovalRules) -> Self {
+        self.approval_rules = Some(approval_rules);
+        self
+    }
+
+    /// Actions that need a comment (reason)
+    pub fn comment_rules(mut self, comment_rules: CommentRules) -> Self {
+        self.comment_rules = Some(comment_rules);
+        self
+    }
+
+    /// Holiday calendars, per currency
+    pub fn calendars(mut self, calendars: Calendars) -> Self {
+        self.calendars = Some(calendars);
+        self
     }
 
+    /// How long the outcome of a command run under an idempotency key is remembered
+    pub fn idempotency_window(mut self, window: Duration) -> Self {
+        self.idempotency_window = Some(window);
+        self
+    }
+
+    pub fn build(self) -> TradeEngine {
+        let id_gen = self.id_gen.unwrap_or_else(|| {
+            // For the snowflake ID generator, use a config-based machine ID
+            let machine_id = config_int("engine.machine_id").unwrap_or(10) as u16;
+            Box::new(SnowflakeIdGenerator::new(machine_id))
+        });
+
+        TradeEngine {
+            id_gen,
+            store: self.store.unwrap_or_else(|| Arc::new(InMemoryStore::new())),
+            state_machine: self.state_machine.unwrap_or_default(),
+            clock: self.clock.unwrap_or_else(|| Box::new(SystemClock)),
+            events: self.events.unwrap_or_default(),
+            policy: self.policy.unwrap_or_else(|| Box::new(AllowAll)),
+            approval_rules: self.approval_rules.unwrap_or_default(),
+            comment_rules: self.comment_rules.unwrap_or_default(),
+            calendars: self.calendars.unwrap_or_default(),
+            idempotency: IdempotencyCache::new(self.idempotency_window.unwrap_or(DEFAULT_IDEMPOTENCY_WINDOW)),
+            batch_lock: RwLock::new(()),
+        }
+    }
+}
+
+/// Meat and potatoes of the trade engine
+///
+/// Every workflow action takes an optional `expected_snapshot_id`, the version the caller last saw.
+/// If the trade has moved on since then, the action is refused with a version conflict (TVC15).
+/// Actions return the state they leave the trade in.
+impl<'a> TradeEngine {
     /// Internal function to fetch a trade by ID
     /// Returns a Result with the trade or an error
     /// ValidationError is an internal enum, we expose AppError to the outside world
     fn fetch_trade(&self, trade_id: TradeId) -> Result<Trade, ValidationError> {
-        let store = self.store_lock()?;
-        store.get(trade_id).ok_or(ValidationError::TradeNotFound(trade_id))
+        self.store.get(trade_id).map_err(ValidationError::Internal)?.ok_or(ValidationError::TradeNotFound(trade_id))
+    }
+
+    /// Runs an engine action against the stored trade, in place and under that trade's lock.
+    /// Checks and the new snapshot happen against the same version, so concurrent
+    /// actions on one trade queue up instead of overwriting each other.
+    /// Once the store has the new snapshot, it is published as a `TradeEvent`.
+    /// In an all-or-nothing batch, the staged copy of the trade is changed instead and the event held back.
+    fn modify_trade<F>(
+        &self,
+        target: Target,
+        user_id: &str,
+        trade_id: TradeId,
+        trade_action: TradeAction,
+        expected_snapshot_id: Option<SnapshotId>,
+        mut action: F,
+    ) -> Result<TradeState, AppError>
+    where
+        F: FnMut(&mut Trade) -> Result<(), AppError>,
+    {
+        let tag = action_tag(trade_action.into());
+        let mut added = None;
+        let mut state_after = None;
+        let mut checked_action = |trade: &mut Trade| {
+            // Is the user allowed to do this at all
+            self.authorize(user_id, trade_action.into(), Some(trade))?;
+
+            // Optimistic concurrency: the caller acted on a version that is no longer current
+            if let Some(expected) = expected_snapshot_id {
+                if trade.version() != expected {
+                    let err: AppError = ValidationError::VersionConflict(expected, trade.version()).into();
+                    return Err(err.with_tags(&[tag]).with_data("trade_id", json!(trade_id)));
+                }
+            }
+            let stored = trade.history.len();
+            action(trade)?;
+            added = trade.history[stored..].last().cloned(); // None if the action only recorded a rejection
+            state_after = Some(trade.current_state());
+            Ok(())
+        };
+
+        let modified = match target {
+            Target::Store => {
+                let _shared = self.batch_lock.read();
+                self.store.modify(trade_id, &mut checked_action)
+            }
+            Target::Staged(staging) => staging.modify(trade_id, &mut checked_action),
+        };
+        modified.map_err(|err| {
+            // Errors from the action are already tagged, the store's "not found" is not
+            if err.code() == ErrCodes::TNF01.code() {
+                err.with_tags(&[tag])
+            } else {
+                err
+            }
+        })?;
+
+        // Committed, outside the trade's lock, so subscribers can't hold it up for others.
+        // Racing actions on the trade may publish out of order, subscribers order by snapshot id.
+        if let Some(snapshot) = added {
+            self.publish(target, TradeEvent::for_action(trade_action, trade_id, snapshot));
+        }
+        state_after.ok_or_else(|| ValidationError::Internal("Trade store skipped the action".into()).into())
+    }
+
+    /// Where lifecycle events are published, subscribe here to react to trades changing state
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    fn publish(&self, target: Target, event: TradeEvent) {
+        match target {
+            Target::Store => self.events.publish(event),
+            Target::Staged(staging) => staging.publish(event),
+        }
+    }
+
+    /// Asks the policy whether the user may perform the operation, on this trade unless creating one
+    fn authorize(&self, user_id: &str, operation: Operation, trade: Option<&Trade>) -> Result<(), AppError> {
+        let principal = self.policy.principal(user_id);
+        self.policy.authorize(&principal, operation, trade).map_err(|err| {
+            let err: AppError = err.into();
+            err.with_tags(&[action_tag(operation)]).with_data("user_id", json!(user_id))
+        })
+    }
+
+    /// The action's comment as it goes on the snapshot, refused if it is missing but mandatory
+    fn comment(&self, action: TradeAction, trade: &Trade, comment: Option<&str>) -> Result<Option<String>, AppError> {
+        self.comment_rules.check(action, trade, comment).map_err(|err| {
+            let err: AppError = err.into();
+            err.with_tags(&[action_tag(action.into())]).with_data("trade_id", json!(trade.id))
+        })
+    }
+
+    /// Where the action takes the trade, per the workflow table.
+    /// Runs the transition's guard for this user first, if it has one.
+    fn next_state(&self, action: TradeAction, trade: &Trade, user_id: &str) -> Result<TradeState, AppError> {
+        let tag = action_tag(action.into());
+        let transition = self.state_machine.transition(action, trade.current_state())?;
+        let Some(guard) = transition.guard else {
+            return Ok(transition.to);
+        };
+
+        // In real life we'd hook into a proper authentication / user system
+        let err_data = json!({"user_id": user_id, "trade_id": trade.id});
+        let is_requester = trade.get_requester() == user_id;
+        match guard {
+            // e.g. the original requester may not approve a trade (only re-approve)
+            Guard::NotRequester if is_requester => {
+                Err(AppError::from_code(ErrCodes::TOR14, err_data).with_tags(&[tag, "requester"]))
+            }
+            // e.g. only the original requester may RE-approve a trade
+            Guard::RequesterOnly if !is_requester => {
+                Err(AppError::from_code(ErrCodes::T0001, err_data).with_tags(&[tag, "re-approval"]))
+            }
+            _ => Ok(transition.to),
+        }
     }
 
     /// Creates a new instance of the TradeEngine
     /// The instance is thread safe and contains the storage (whether in-memory or other)
-    pub fn new(store: InMemoryStore) -> Self {
-        // For the snowflake ID generator, use a config-based machine ID
-        let machine_id = config_int("engine.machine_id").unwrap_or(10) as u16;
-
-        // wrap the store in an Arc<Mutex for thread safety
-        let store: Arc<Mutex<dyn TradeStore>> = Arc::new(Mutex::new(store));
+    /// Everything else is defaulted, see `TradeEngineBuilder` for full control
+    pub fn new(store: impl TradeStore + 'static) -> Self {
+        TradeEngineBuilder::new().store(store).build()
+    }
 
-        Self { id_gen: SnowflakeIdGenerator::new(machine_id), store, state_machine: StateMachine::default() }
+    /// Start building an engine with custom parts
+    pub fn builder() -> TradeEngineBuilder {
+        TradeEngineBuilder::new()
     }
 
     /// Creates a DRAFT trade on the system and returns the trade ID.
     pub fn create(&self, user_id: &str, details: TradeDetails) -> Result<TradeId, AppError> {
+        self.create_on(Target::Store, user_id, details)
+    }
+
+    fn create_on(&self, target: Target, user_id: &str, details: TradeDetails) -> Result<TradeId, AppError> {
+        self.authorize(user_id, Operation::Create, None)?;
+
         // Ensure the trade details are all present and correct
-        details.validate()?; // Converts to AppError with "From"
+        details.validate_unexecuted()?; // Converts to AppError with "From"
+        self.calendars.check(&details)?;
 
         let trade_id = self.id_gen.generate(); // Snowflake ID generation
-        let trade = Trade::new(trade_id, details, user_id.to_string());
+        let trade = Trade::new_at(trade_id, details, user_id.to_string(), self.clock.now());
 
-        let mut store_guard = self.store.lock().map_err(
-            // Should never happen
-            |_| ValidationError::Internal("Failed to acquire store lock".into()),
-        )?;
+        let created = trade.history[0].clone();
+        match target {
+            Target::Store => {
+                let _shared = self.batch_lock.read();
+                self.store.push(trade).map_err(ValidationError::Internal)?;
+            }
+            Target::Staged(staging) => staging.push(trade),
+        }
 
-        store_guard.push(trade);
+        self.publish(target, TradeEvent::Created(trade_id, created));
         Ok(trade_id)
     }
 
     /// Transition a draft trade to a pending approval state.
-    pub fn submit(&self, user_id: &str, trade_id: TradeId) -> Result<(), AppError> {
-        // Grab the trade from the trade id
-        let mut trade = self.fetch_trade(trade_id)?; // ValidationError becomes AppError with "From"
-
-        let state_now = trade.current_state();
-        let state_new = self.state_machine.next_state(TradeAction::Submit, state_now)?; // PendingApproval
-
-        // Check if the transition is allowed (we don't assume a submission from draft state)
-        // Only DRAFT trades can be submitted
-        if !self.state_machine.can_transition(state_now, state_new) {
-            return Err(ValidationError::InvalidTransition(state_now, state_new).into());
-            // Converts to AppError
-        }
-
-        // Get a copy of the latest details
-        let details = trade.latest_details().cloned().ok_or_else(|| {
-            // This should never happen, but if it does, we need to handle it
-            ValidationError::Internal("Missing trade details during submit".into())
-        })?;
+    pub fn submit(
+        &self,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+    ) -> Result<TradeState, AppError> {
+        self.submit_on(Target::Store, user_id, trade_id, expected_snapshot_id)
+    }
 
-        // Record the event snapshot, preserving all state and details
-        // TODO :: NOTE:: details are entirely unchanged in this case
-        //  There probably is no point duplicating the details here
-        trade.add_snapshot(user_id, state_new, details);
+    fn submit_on(
+        &self,
+        target: Target,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+    ) -> Result<TradeState, AppError> {
+        // The trade is edited in place, under its lock in the store
+        self.modify_trade(target, user_id, trade_id, TradeAction::Submit, expected_snapshot_id, |trade| {
+            let state_now = trade.current_state();
+            let state_new = self.next_state(TradeAction::Submit, trade, user_id)?; // PendingApproval
+
+            // Check if the transition is allowed (we don't assume a submission from draft state)
+            // Only DRAFT trades can be submitted
+            if !self.state_machine.can_transition(state_now, state_new) {
+                return Err(ValidationError::InvalidTransition(state_now, state_new).into());
+                // Converts to AppError
+            }
 
-        // put the modified trade back into the store
-        // Later we'll come back and refactor to edit trade in place
-        self.store_lock()?.update(trade)?;
+            // The details are unchanged, the new snapshot shares them with the last one
+            let details = trade.latest_details_shared().ok_or_else(|| {
+                // This should never happen, but if it does, we need to handle it
+                ValidationError::Internal("Missing trade details during submit".into())
+            })?;
 
-        Ok(())
+            // Record the event snapshot, preserving all state and details
+            trade.add_snapshot_at(user_id, state_new, details, self.clock.now());
+            Ok(())
+        })
     }
 
     /// A user is approving a trade for execution
     /// Applies to trades in PendingApproval or NeedsReapproval
     /// Business rule: only the original requester can re-approve a trade
-    pub fn approve(&self, user_id: &str, trade_id: TradeId) -> Result<(), AppError> {
-        // Grab the trade from the trade id
-        let mut trade = self.fetch_trade(trade_id).map_err(|err| {
-            let app_err: AppError = err.into();
-            app_err.with_tags(&["approve"])
-        })?;
-
-        // Determine the state transition
-        let state_now = trade.current_state();
-        let state_new = self.state_machine.next_state(TradeAction::Approve, state_now)?; // Expecting "Approved"
-
-        // Bundle up some data for error reporting
-        let err_data = json!({"user_id" : user_id, "trade_id": trade_id});
+    /// Approval rules may ask for several distinct approvers first, see `ApprovalRules`
+    /// Approvals over the approver's limit are refused (TAL21) and kept in the trade's rejections
+    pub fn approve(
+        &self,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        self.approve_on(Target::Store, user_id, trade_id, expected_snapshot_id, comment)
+    }
 
-        // Check if the transition is allowed (don't assume submission from correct state)
-        if !self.state_machine.can_transition(state_now, state_new) {
-            let err: AppError = ValidationError::InvalidTransition(state_now, state_new).into();
-            return Err(err.with_tags(&["approve"]).with_data("state", err_data));
-        }
+    fn approve_on(
+        &self,
+        target: Target,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        let mut refused = None;
+        let state =
+            self.modify_trade(target, user_id, trade_id, TradeAction::Approve, expected_snapshot_id, |trade| {
+                // Share the latest trade details, they don't change here
+                let details = trade
+                    .latest_details_shared()
+                    .ok_or_else(|| ValidationError::Internal("Missing trade details on approve".into()))?;
+
+                // N-eyes: trades matching a quorum rule need that many distinct approvers, other than the
+                // requester, on first approval and on re-approval after an update alike. The quorum stands in
+                // for the workflow's approval guards (e.g. the requester re-approving alone).
+                let state_now = trade.current_state();
+                let required = self.approval_rules.required_approvers(&details);
+                let quorum =
+                    required > 1 && matches!(state_now, TradeState::PendingApproval | TradeState::NeedsReapproval);
+
+                // Determine the state transition, approval guards (who may approve) are checked here
+                let state_new = match quorum {
+                    true => self.state_machine.next_state(TradeAction::Approve, state_now)?,
+                    false => self.next_state(TradeAction::Approve, trade, user_id)?, // Expecting "Approved"
+                };
+
+                // Bundle up some data for error reporting
+                let err_data = json!({"user_id" : user_id, "trade_id": trade_id});
+
+                // Check if the transition is allowed (don't assume submission from correct state)
+                if !self.state_machine.can_transition(state_now, state_new) {
+                    let err: AppError = ValidationError::InvalidTransition(state_now, state_new).into();
+                    return Err(err.with_tags(&["approve"]).with_data("state", err_data));
+                }
+
+                let comment = self.comment(TradeAction::Approve, trade, comment)?;
+
+                // Delegated authority: the refusal is committed to the trade's audit trail, not rolled back
+                let principal = self.policy.principal(user_id);
+                if let Err(err) = self.approval_rules.limits.check(&principal, &details) {
+                    let err: AppError = err.into();
+                    let err = err.with_tags(&["approve"]).with_data("trade_id", json!(trade_id));
+                    trade.add_rejection(user_id, TradeAction::Approve, err.code(), err.message(), self.clock.now());
+                    refused = Some(err);
+                    return Ok(());
+                }
+
+                // Short of the quorum, the approval is recorded but the trade stays where it is
+                if quorum {
+                    if trade.get_requester() == user_id {
+                        return Err(AppError::from_code(ErrCodes::TOR14, err_data).with_tags(&["approve", "quorum"]));
+                    }
+
+                    let approvers = trade.pending_approvers();
+                    if approvers.iter().any(|approver| approver == user_id) {
+                        let err: AppError = ValidationError::AlreadyApproved(user_id.to_string()).into();
+                        return Err(err.with_tags(&["approve"]).with_data("trade_id", json!(trade_id)));
+                    }
+
+                    if approvers.len() + 1 < required {
+                        trade.add_commented_snapshot_at(user_id, state_now, details, self.clock.now(), comment);
+                        return Ok(());
+                    }
+                }
+
+                // Save the event snapshot
+                trade.add_commented_snapshot_at(user_id, state_new, details, self.clock.now(), comment);
+                Ok(())
+            })?;
+
+        refused.map_or(Ok(state), Err)
+    }
 
-        // -----------------------------------------------------------------------------------------
-        // Business rule:
-        // -----------------------------------------------------------------------------------------
-        // We do not allow the original requester to approve a trade (only re-approve)
-        // In real life we'd hook into a proper authentication / user system
-        if state_now != NeedsReapproval && trade.get_requester() == user_id {
-            return Err(AppError::from_code(ErrCodes::TOR14, err_data).with_tags(&["approve", "requester"]));
-        }
+    /// Cancel a trade
+    /// Applies to trades in Draft, PendingApproval, NeedsReapproval, Approved
+    /// and possibly SentToCounterparty, but not Executed or Cancelled
+    /// The comment (reason) may be mandatory, see `CommentRules`
+    pub fn cancel(
+        &self,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        self.cancel_on(Target::Store, user_id, trade_id, expected_snapshot_id, comment)
+    }
 
-        // -----------------------------------------------------------------------------------------
-        // Special business rule:
-        // -----------------------------------------------------------------------------------------
-        // We only allow the original requester to RE-approve a trade
-        // (Original requester is not the first approver, but the user who created the trade)
-        if trade.needs_re_approval() {
-            if trade.get_requester() != user_id {
-                return Err(AppError::from_code(ErrCodes::T0001, err_data).with_tags(&["approve", "re-approval"]));
+    fn cancel_on(
+        &self,
+        target: Target,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        self.modify_trade(target, user_id, trade_id, TradeAction::Cancel, expected_snapshot_id, |trade| {
+            let state_now = trade.current_state();
+            let state_new = TradeState::Cancelled;
+
+            // Check if the transition to cancelled is allowed
+            if !self.state_machine.can_transition(state_now, state_new) {
+                let err_data = json!({"user_id": user_id, "trade_id": trade_id});
+                let err: AppError = ValidationError::InvalidTransition(state_now, state_new).into();
+                return Err(err.with_tags(&["cancel"]).with_data("state", err_data));
             }
-            // If we get here, the user is the original requester, so we're fine
-        }
-        // -----------------------------------------------------------------------------------------
 
-        // Get a copy of the latest trade details
-        let details = trade
-            .latest_details()
-            .cloned()
-            .ok_or_else(|| ValidationError::Internal("Missing trade details on approve".into()))?;
+            // Same state as above, but also runs the guard if the workflow puts one on cancelling
+            let state_new = self.next_state(TradeAction::Cancel, trade, user_id)?;
 
-        // Save the event snapshot
-        // TODO :: NOTE:: details are entirely unchanged in this case
-        //  There probably is no point duplicating the details here
-        trade.add_snapshot(user_id, state_new, details);
+            let details = trade
+                .latest_details_shared()
+                .ok_or_else(|| ValidationError::Internal("Missing trade details on cancel".into()))?;
+            let comment = self.comment(TradeAction::Cancel, trade, comment)?;
 
-        // put the modified trade back into the store
-        // Later we'll come back and refactor to edit trade in place
-        self.store_lock()?.update(trade)?;
-
-        Ok(())
+            trade.add_commented_snapshot_at(user_id, state_new, details, self.clock.now(), comment);
+            Ok(())
+        })
     }
 
-    /// Cancel a trade
-    /// Applies to trades in Draft, PendingApproval, NeedsReapproval, Approved
-    /// and possibly SentToCounterparty, but not Executed or Cancelled
-    pub fn cancel(&self, user_id: &str, trade_id: TradeId) -> Result<(), AppError> {
-        // Grab the trade from the trade id
-        let mut trade = self.fetch_trade(trade_id).map_err(|err| {
-            let app_err: AppError = err.into();
-            app_err.with_tags(&["cancel"])
-        })?;
-
-        let state_now = trade.current_state();
-        let state_new = TradeState::Cancelled;
-
-        // Check if the transition to cancelled is allowed
-        if !self.state_machine.can_transition(state_now, state_new) {
-            let err_data = json!({"user_id": user_id, "trade_id": trade_id});
-            let err: AppError = ValidationError::InvalidTransition(state_now, state_new).into();
+    /// Update trade details
+    /// Can only be done if trade has not been sent to counterparty and beyond
+    /// Once the trade has been approved, the comment (reason) may be mandatory, see `CommentRules`
+    pub fn update(
+        &self,
+        user_id: &str,
+        trade_id: TradeId,
+        details: TradeDetails,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        self.update_on(Target::Store, user_id, trade_id, details, expected_snapshot_id, comment)
+    }
 
-            // write that we are here
-            println!(
-                "TradeEngine::cancel: trade_id: {}, state_now: {:?}, state_new: {:?} - failed",
-                trade_id, state_now, state_new
-            );
+    fn update_on(
+        &self,
+        target: Target,
+        user_id: &str,
+        trade_id: TradeId,
+        details: TradeDetails,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        // Ensure the incoming trade details are all present and correct
+        details.validate_unexecuted()?;
+        self.calendars.check(&details)?;
+        let details = Arc::new(details);
 
-            return Err(err.with_tags(&["cancel"]).with_data("state", err_data));
-        }
+        self.amend(target, user_id, trade_id, expected_snapshot_id, comment, |_| Ok((details.clone(), None)))
+    }
 
-        let details = trade
-            .latest_details()
-            .cloned()
-            .ok_or_else(|| ValidationError::Internal("Missing trade details on cancel".into()))?;
+    /// Brings back the details of an earlier snapshot, as an update to them would (e.g. an approved
+    /// trade needs reapproval). The new snapshot records which one it was reverted from.
+    /// Refused once the trade has been sent to the counterparty, whatever the workflow allows.
+    pub fn revert(
+        &self,
+        user_id: &str,
+        trade_id: TradeId,
+        snapshot_id: SnapshotId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        self.revert_on(Target::Store, user_id, trade_id, snapshot_id, expected_snapshot_id, comment)
+    }
 
-        // TODO :: NOTE:: details are entirely unchanged in this case
-        //  There probably is no point duplicating the details here
-        trade.add_snapshot(user_id, state_new, details);
+    fn revert_on(
+        &self,
+        target: Target,
+        user_id: &str,
+        trade_id: TradeId,
+        snapshot_id: SnapshotId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        self.amend(target, user_id, trade_id, expected_snapshot_id, comment, |trade| {
+            let err_data = json!({"user_id": user_id, "trade_id": trade_id, "snapshot_id": snapshot_id});
+            let state_now = trade.current_state();
+            if matches!(state_now, TradeState::SentToCounterparty | TradeState::Executed) {
+                let e: AppError = ValidationError::InvalidAction(TradeAction::Update, state_now).into();
+                return Err(e.with_data("info", err_data).with_tags(&["revert"]));
+            }
 
-        // put the modified trade back into the store
-        // Later we'll come back and refactor to edit trade in place
-        self.store_lock()?.update(trade)?;
+            let source = trade.history.get(snapshot_id).ok_or_else(|| {
+                let e: AppError = ValidationError::SnapshotNotFound(trade_id, snapshot_id).into();
+                e.with_data("info", err_data.clone()).with_tags(&["revert"])
+            })?;
 
-        Ok(())
+            // Same checks as an update: the old details may not pass the rules (or calendars) of today
+            let checked = source.details.validate_unexecuted().and_then(|_| self.calendars.check(&source.details));
+            checked.map_err(|err| AppError::from(err).with_data("info", err_data).with_tags(&["revert"]))?;
+            Ok((source.details.clone(), Some(snapshot_id)))
+        })
     }
 
-    /// Update trade details
-    /// Can only be done if trade has not been sent to counterparty and beyond
-    pub fn update(&self, user_id: &str, trade_id: TradeId, details: TradeDetails) -> Result<(), AppError> {
-        // Ensure the incoming trade details are all present and correct
-        details.validate()?;
+    /// What an update and a revert share: the state the trade moves to, the no-op check and the comment rules.
+    /// `new_details` picks the details of the new snapshot, and the snapshot they are reverted from if any.
+    fn amend<F>(
+        &self,
+        target: Target,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+        new_details: F,
+    ) -> Result<TradeState, AppError>
+    where
+        F: Fn(&Trade) -> Result<(Arc<TradeDetails>, Option<SnapshotId>), AppError>,
+    {
+        self.modify_trade(target, user_id, trade_id, TradeAction::Update, expected_snapshot_id, |trade| {
+            // Figure out the current state, and the state we would transition to
+            let state_now = trade.current_state();
+            let state_new = self.next_state(TradeAction::Update, trade, user_id)?;
+
+            // Validate the proposed state transition
+            let err_data = json!({"user_id": user_id, "trade_id": trade_id});
+            if !self.state_machine.can_transition(state_now, state_new) {
+                let e: AppError = ValidationError::InvalidTransition(state_now, state_new).into();
+                return Err(e.with_data("info", err_data).with_tags(&["update"]));
+            }
 
-        // Grab the trade from the trade id
-        let mut trade = self.fetch_trade(trade_id).map_err(|err| {
-            let app_err: AppError = err.into();
-            app_err.with_tags(&["update"])
-        })?;
+            // No-op if details are identical
+            let (details, reverted_from) = new_details(trade)?;
+            if let Some(current) = trade.latest_details() {
+                if current == details.as_ref() {
+                    return Err(AppError::from_code(ErrCodes::TDI13, err_data)
+                        .with_data("reason", json!("No change in trade details"))
+                        .with_tags(&["update", "noop"]));
+                }
+            }
 
-        // Figure out the current state, and the state we would transition to
-        let state_now = trade.current_state();
-        let state_new = self.state_machine.next_state(TradeAction::Update, state_now)?;
+            // One or more within details have now definitely changed, say why if need be
+            let comment = self.comment(TradeAction::Update, trade, comment)?;
+            let now = self.clock.now();
+            if let Some(source) = reverted_from {
+                trade.add_reverted_snapshot_at(user_id, state_new, source, now, comment);
+            } else {
+                trade.add_commented_snapshot_at(user_id, state_new, details, now, comment);
+            }
+            Ok(())
+        })
+    }
 
-        // Validate the proposed state transition
-        let err_data = json!({"user_id": user_id, "trade_id": trade_id});
-        if !self.state_machine.can_transition(state_now, state_new) {
-            let e: AppError = ValidationError::InvalidTransition(state_now, state_new).into();
-            return Err(e.with_data("info", err_data).with_tags(&["update"]));
-        }
+    /// Send a trade to the counterparty for execution
+    pub fn send_to_execute(
+        &self,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        self.send_to_execute_on(Target::Store, user_id, trade_id, expected_snapshot_id, comment)
+    }
 
-        // No-op if details are identical
-        if let Some(current) = trade.latest_details() {
-            if current == &details {
-                return Err(AppError::from_code(ErrCodes::TDI13, err_data)
-                    .with_data("reason", json!("No change in trade details"))
-                    .with_tags(&["update", "noop"]));
+    fn send_to_execute_on(
+        &self,
+        target: Target,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        self.modify_trade(target, user_id, trade_id, TradeAction::SendToExecute, expected_snapshot_id, |trade| {
+            let state_now = trade.current_state();
+            let state_new = self.next_state(TradeAction::SendToExecute, trade, user_id)?;
+            if !self.state_machine.can_transition(state_now, state_new) {
+                let e: AppError = ValidationError::InvalidTransition(state_now, state_new).into();
+                let err_data = json!({"user_id": user_id, "trade_id": trade_id});
+                return Err(e.with_data("info", err_data).with_tags(&["send"]));
             }
-        }
 
-        // One or more within details have now definitely changed
-        trade.add_snapshot(user_id, state_new, details);
+            // Share the latest trade details, they don't change here
+            let details = trade
+                .latest_details_shared()
+                .ok_or_else(|| ValidationError::Internal("Missing trade details on send_to_execute".into()))?;
+            let comment = self.comment(TradeAction::SendToExecute, trade, comment)?;
 
-        // put the modified trade back into the store
-        // Later we'll come back and refactor to edit trade in place
-        self.store_lock()?.update(trade)?;
+            trade.add_commented_snapshot_at(user_id, state_new, details, self.clock.now(), comment);
+            Ok(())
+        })
+    }
 
-        Ok(())
+    /// Marks a trade as executed, as confirmed by the counterparty
+    /// Applies to trades in SentToCounterparty only
+    /// The confirmed rate becomes the trade's strike, the confirmation is kept on the booking snapshot
+    pub fn book(
+        &self,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        confirmation: ExecutionConfirmation,
+    ) -> Result<TradeState, AppError> {
+        self.book_on(Target::Store, user_id, trade_id, expected_snapshot_id, &confirmation)
     }
 
-    /// Send a trade to the counterparty for execution
-    pub fn send_to_execute(&self, user_id: &str, trade_id: TradeId) -> Result<(), AppError> {
-        // Grab the trade from the trade id
-        let mut trade = self.fetch_trade(trade_id).map_err(|err| {
-            let app_err: AppError = err.into();
-            app_err.with_tags(&["send"])
-        })?;
+    fn book_on(
+        &self,
+        target: Target,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        confirmation: &ExecutionConfirmation,
+    ) -> Result<TradeState, AppError> {
+        self.modify_trade(target, user_id, trade_id, TradeAction::Book, expected_snapshot_id, |trade| {
+            let state_now = trade.current_state();
+            let state_new = self.next_state(TradeAction::Book, trade, user_id)?;
+            if !self.state_machine.can_transition(state_now, state_new) {
+                let err_data = json!({ "user_id": user_id, "trade_id": trade_id });
+                let err: AppError = ValidationError::InvalidTransition(state_now, state_new).into();
+                return Err(err.with_data("info", err_data).with_tags(&["book"]));
+            }
 
-        let state_now = trade.current_state();
-        let state_new = self.state_machine.next_state(TradeAction::SendToExecute, state_now)?;
-        if !self.state_machine.can_transition(state_now, TradeState::SentToCounterparty) {
-            let e: AppError = ValidationError::InvalidTransition(state_now, TradeState::SentToCounterparty).into();
-            let err_data = json!({"user_id": user_id, "trade_id": trade_id});
-            return Err(e.with_data("info", err_data).with_tags(&["send"]));
-        }
+            let details = trade
+                .latest_details()
+                .ok_or_else(|| ValidationError::Internal("Missing trade details on book".into()))?;
+            let now = self.clock.now();
+            confirmation.validate(details, now)?;
 
-        // Get a copy of the latest trade details
-        let details = trade
-            .latest_details()
-            .cloned()
-            .ok_or_else(|| ValidationError::Internal("Missing trade details on send_to_execute".into()))?;
+            let mut executed = TradeDetails { strike: Some(confirmation.rate), ..details.clone() };
+            if let Product::Swap(far) = &mut executed.product {
+                far.rate = confirmation.far_rate;
+            }
+            trade.add_executed_snapshot_at(user_id, state_new, executed, now, confirmation.clone());
+            Ok(())
+        })
+    }
 
-        // TODO :: NOTE:: details are entirely unchanged in this case
-        //  There probably is no point duplicating the details here
-        trade.add_snapshot(user_id, state_new, details);
+    /// Marks a trade as expired, i.e. left too long to be approved or sent
+    /// Applies to trades in Draft, PendingApproval, NeedsReapproval and Approved
+    /// Meant for a system user, the standard `RolePolicy` only lets admins do it
+    pub fn expire(
+        &self,
+        user_id: &str,
+        trade_id: TradeId,
+        expected_snapshot_id: Option<SnapshotId>,
+        comment: Option<&str>,
+    ) -> Result<TradeState, AppError> {
+        self.modify_trade(Target::Store, user_id, trade_id, TradeAction::Expire, expected_snapshot_id, |trade| {
+            let state_new = self.next_state(TradeAction::Expire, trade, user_id)?;
+
+            let details = trade
+                .latest_details_shared()
+                .ok_or_else(|| ValidationError::Internal("Missing trade details on expire".into()))?;
+            let comment = self.comment(TradeAction::Expire, trade, comment)?;
+
+            trade.add_commented_snapshot_at(user_id, state_new, details, self.clock.now(), comment);
+            Ok(())
+        })
+    }
 
-        // put the modified trade back into the store
-        // Later we'll come back and refactor to edit trade in place
-        self.store_lock()?.update(trade)?;
+    /// Runs a command, the same as calling the action itself.
+    ///
+    /// With an idempotency key, the outcome is remembered for a while (see `TradeEngineBuilder::idempotency_window`)
+    /// and running the same command under the key again returns it, without running the command twice.
+    /// Reusing the key for another command is refused (TIK23), as is a replay while the first run
+    /// is still going (TIP24). Failures are not remembered, so a failed command can simply be retried.
+    pub fn execute(&self, command: TradeCommand, idempotency_key: Option<&str>) -> Result<CommandOutcome, AppError> {
+        let Some(key) = idempotency_key else {
+            return self.run(Target::Store, &command);
+        };
+
+        let with_context = |err: ValidationError| {
+            let err: AppError = err.into();
+            err.with_data("user_id", json!(command.user_id()))
+        };
+        if let Some(outcome) = self.idempotency.begin(key, &command, self.clock.now()).map_err(with_context)? {
+            return Ok(outcome);
+        }
 
-        Ok(())
+        let result = self.run(Target::Store, &command);
+        self.idempotency.finish(key, &command, result.as_ref().ok().copied(), self.clock.now());
+        result
     }
 
-    /// Marks a trade as executed
-    /// Applies to trades in SentToCounterparty only
-    pub fn book(&self, user_id: &str, trade_id: TradeId) -> Result<(), AppError> {
-        let mut trade = self.fetch_trade(trade_id).map_err(|err| {
-            let app_err: AppError = err.into();
-            app_err.with_tags(&["book"])
-        })?;
-
-        let state_now = trade.current_state();
-        let state_new = self.state_machine.next_state(TradeAction::Book, state_now)?;
-        if !self.state_machine.can_transition(state_now, TradeState::Executed) {
-            let err_data = json!({ "user_id": user_id, "trade_id": trade_id });
-            let err: AppError = ValidationError::InvalidTransition(state_now, TradeState::Executed).into();
-            return Err(err.with_data("info", err_data).with_tags(&["book"]));
+    /// Runs commands one after the other, one result per command in the same order.
+    ///
+    /// - `BestEffort`: each command is run as by `execute`, whatever happened to the ones before it.
+    /// - `AllOrNothing`: the store gets every change of the batch or none. The first failing command
+    ///   gets its error and all the others TBA25, the trades are left as they were, no event is published,
+    ///   and no rejection or refusal is recorded either. Other actions wait while such a batch runs.
+    ///
+    /// Later commands see what earlier ones did, e.g. submit then approve the same trade.
+    pub fn execute_batch(&self, commands: Vec<TradeCommand>, mode: BatchMode) -> Vec<Result<CommandOutcome, AppError>> {
+        if mode == BatchMode::BestEffort {
+            return commands.iter().map(|command| self.run(Target::Store, command)).collect();
         }
 
-        let details = trade
-            .latest_details()
-            .cloned()
-            .ok_or_else(|| ValidationError::Internal("Missing trade details on book".into()))?;
+        let _exclusive = self.batch_lock.write();
+        let staging = Staging::new(&*self.store);
+        let mut outcomes = Vec::with_capacity(commands.len());
+        for (index, command) in commands.iter().enumerate() {
+            match self.run(Target::Staged(&staging), command) {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(err) => return Self::batch_aborted(commands.len(), index, err),
+            }
+        }
 
-        trade.add_snapshot(user_id, state_new, details);
+        let (trades, events) = staging.into_parts();
+        if let Err(reason) = self.store.commit_batch(trades) {
+            return outcomes.iter().map(|_| Err(ValidationError::Internal(reason.clone()).into())).collect();
+        }
+        for event in events {
+            self.events.publish(event);
+        }
+        outcomes.into_iter().map(Ok).collect()
+    }
 
-        // put the modified trade back into the store
-        // Later we'll come back and refactor to edit trade in place
-        self.store_lock()?.update(trade)?;
+    fn batch_aborted(count: usize, failed: usize, err: AppError) -> Vec<Result<CommandOutcome, AppError>> {
+        let mut err = Some(err.with_data("index", json!(failed)));
+        (0..count)
+            .map(|index| match index == failed {
+                true => Err(err.take().expect("Only one item failed")),
+                false => Err(ValidationError::BatchAborted(failed).into()),
+            })
+            .collect()
+    }
 
-        Ok(())
+    fn run(&self, target: Target, command: &TradeCommand) -> Result<CommandOutcome, AppError> {
+        let (trade_id, state) = match command {
+            TradeCommand::Create { user_id, details } => {
+                (self.create_on(target, user_id, details.clone())?, TradeState::Draft)
+            }
+            TradeCommand::Submit { user_id, trade_id, expected_version } => {
+                (*trade_id, self.submit_on(target, user_id, *trade_id, *expected_version)?)
+            }
+            TradeCommand::Approve { user_id, trade_id, expected_version, comment } => {
+                (*trade_id, self.approve_on(target, user_id, *trade_id, *expected_version, comment.as_deref())?)
+            }
+            TradeCommand::Cancel { user_id, trade_id, expected_version, comment } => {
+                (*trade_id, self.cancel_on(target, user_id, *trade_id, *expected_version, comment.as_deref())?)
+            }
+            TradeCommand::Update { user_id, trade_id, details, expected_version, comment } => (
+                *trade_id,
+                self.update_on(target, user_id, *trade_id, details.clone(), *expected_version, comment.as_deref())?,
+            ),
+            TradeCommand::Revert { user_id, trade_id, snapshot_id, expected_version, comment } => {
+                let comment = comment.as_deref();
+                (*trade_id, self.revert_on(target, user_id, *trade_id, *snapshot_id, *expected_version, comment)?)
+            }
+            TradeCommand::SendToExecute { user_id, trade_id, expected_version, comment } => {
+                (*trade_id, self.send_to_execute_on(target, user_id, *trade_id, *expected_version, comment.as_deref())?)
+            }
+            TradeCommand::Book { user_id, trade_id, expected_version, confirmation } => {
+                (*trade_id, self.book_on(target, user_id, *trade_id, *expected_version, confirmation)?)
+            }
+        };
+        Ok(CommandOutcome { trade_id, state })
     }
 
     /// Gets the status of the given trade id
@@ -324,13 +868,18 @@ impl<'a> TradeEngine {
 
     /// Fetch a simple list of trade IDs
     pub fn trade_ids(&self, should_sort: bool) -> Result<Vec<TradeId>, AppError> {
-        let store = self.store_lock()?;
+        let mut keys = self.store.keys().map_err(ValidationError::Internal)?;
         if should_sort {
-            let mut keys = store.keys();
             keys.sort();
-            return Ok(keys);
         }
-        Ok(store.keys())
+        Ok(keys)
+    }
+
+    /// Trades matching the query's filters, sorted and paged (see `TradeQuery`)
+    pub fn query(&self, query: TradeQuery) -> Result<TradePage, AppError> {
+        // The store narrows the candidates down where it can (e.g. indexes), the query does the rest
+        let candidates = self.store.candidates(&query).map_err(ValidationError::Internal)?;
+        query.page(candidates).map_err(|err| AppError::from(err).with_tags(&["query"]))
     }
 
     /// Fetch a vector of TradeEventSnapshot objects
@@ -344,6 +893,38 @@ impl<'a> TradeEngine {
         Ok(trade.history)
     }
 
+    /// Actions refused on the trade but kept for the audit trail (e.g. approvals over limit), oldest first
+    pub fn trade_rejections(&self, trade_id: TradeId) -> Result<Vec<RejectedAction>, AppError> {
+        let trade = self.fetch_trade(trade_id).map_err(|err| {
+            let app_err: AppError = err.into();
+            app_err.with_tags(&["rejections"])
+        })?;
+
+        Ok(trade.rejections)
+    }
+
+    /// The trade as it stood at the given instant: the snapshot in force then, carrying
+    /// the state, the details and the user who made that version
+    pub fn trade_as_of(&self, trade_id: TradeId, at: DateTime<Utc>) -> Result<TradeEventSnapshot, AppError> {
+        let trade = self.fetch_trade(trade_id).map_err(|err| {
+            let app_err: AppError = err.into();
+            app_err.with_tags(&["as_of"])
+        })?;
+
+        trade.snapshot_as_of(at).cloned().ok_or_else(|| ValidationError::NotYetCreated(trade_id, at).into())
+    }
+
+    /// The whole book as it stood at the given instant (e.g. end of day), by trade ID.
+    /// Trades created after that instant are left out.
+    /// A trade that can't be read fails the whole book rather than dropping out of it.
+    pub fn trades_as_of(&self, at: DateTime<Utc>) -> Result<BTreeMap<TradeId, TradeEventSnapshot>, AppError> {
+        let trades = self.store.candidates(&TradeQuery::default()).map_err(ValidationError::Internal)?;
+        Ok(trades
+            .into_iter()
+            .filter_map(|trade| trade.snapshot_as_of(at).cloned().map(|snapshot| (trade.id, snapshot)))
+            .collect())
+    }
+
     /// Fetch the latest (current) trade details for the given trade id
     pub fn trade_details(&self, trade_id: TradeId) -> Result<TradeDetails, AppError> {
         let trade = self.fetch_trade(trade_id).map_err(|err| {
@@ -355,6 +936,31 @@ impl<'a> TradeEngine {
         trade.latest_details().cloned().ok_or_else(|| ValidationError::Internal("Missing trade details".into()).into())
     }
 
+    /// The latest trade details together with the version they belong to,
+    /// read in one go so the two always match (e.g. for an ETag)
+    pub fn trade_details_with_version(&self, trade_id: TradeId) -> Result<(TradeDetails, SnapshotId), AppError> {
+        let trade = self.fetch_trade(trade_id).map_err(|err| {
+            let app_err: AppError = err.into();
+            app_err.with_tags(&["trade_details"])
+        })?;
+
+        let details =
+            trade.latest_details().cloned().ok_or_else(|| ValidationError::Internal("Missing trade details".into()))?;
+        Ok((details, trade.version()))
+    }
+
+    /// Value and delivery dates of a trade in the currencies, done at `trade_date` for the tenor
+    /// (e.g. "SPOT", "3M"), rolled over weekends and the holidays of the engine's calendars
+    pub fn tenor_dates(
+        &self,
+        trade_date: DateTime<Utc>,
+        currencies: &[Currency],
+        tenor: Tenor,
+    ) -> Result<TenorDates, AppError> {
+        let dates = DateRoller::new(&self.calendars, currencies).dates(trade_date, tenor)?;
+        Ok(dates)
+    }
+
     /// Returns a structure of differences between two snapshots of a trade
     ///
     /// # Arguments
@@ -387,11 +993,29 @@ impl<'a> TradeEngine {
             to_user: to.user_id.clone(),
             from_timestamp: from.timestamp,
             to_timestamp: to.timestamp,
+            from_comment: from.comment.clone(),
+            to_comment: to.comment.clone(),
+            from_reverted_from: from.reverted_from,
+            to_reverted_from: to.reverted_from,
             differences,
         })
     }
 }
 
+/// Tag put on errors from an engine action
+fn action_tag(operation: Operation) -> &'static str {
+    match operation {
+        Operation::Create => "create",
+        Operation::Submit => "submit",
+        Operation::Approve => "approve",
+        Operation::Cancel => "cancel",
+        Operation::Update => "update",
+        Operation::SendToExecute => "send",
+        Operation::Book => "book",
+        Operation::Expire => "expire",
+    }
+}
+
 // = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
 // Basic unit tests for engine logic
 // = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
@@ -413,7 +1037,18 @@ mod tests {
             trade_date: Utc.with_ymd_and_hms(2025, 4, 10, 0, 0, 0).unwrap(),
             value_date: Utc.with_ymd_and_hms(2025, 4, 12, 0, 0, 0).unwrap(),
             delivery_date: Utc.with_ymd_and_hms(2025, 4, 13, 0, 0, 0).unwrap(),
-            strike: Some(dec!(1.2345)),
+            strike: None,
+            product: Product::Forward,
+        }
+    }
+
+    fn confirmation() -> ExecutionConfirmation {
+        ExecutionConfirmation {
+            rate: dec!(1.2345),
+            executed_at: Utc.with_ymd_and_hms(2025, 4, 10, 14, 0, 0).unwrap(),
+            counterparty_ref: "CPB-1001".into(),
+            executed_notional: dec!(1_000_000.00),
+            far_rate: None,
         }
     }
 
@@ -431,7 +1066,7 @@ mod tests {
         let trade_id = engine.create(user_id, details).expect("Trade creation failed");
 
         // 2: Submit trade
-        let result = engine.submit(user_id, trade_id);
+        let result = engine.submit(user_id, trade_id, None);
         assert!(result.is_ok(), "Submit failed: {:?}", result);
 
         // 3: Check trade state is now PendingApproval
@@ -439,7 +1074,7 @@ mod tests {
         assert_eq!(state, TradeState::PendingApproval, "Trade state should be PendingApproval");
 
         // 4: Try to submit again, should fail (InvalidTransition)
-        let result_again = engine.submit(user_id, trade_id);
+        let result_again = engine.submit(user_id, trade_id, None);
         assert!(result_again.is_err(), "Resubmitting should fail");
 
         let err = result_again.unwrap_err();
@@ -466,6 +1101,58 @@ mod tests {
         assert_eq!(fetched_details, details, "Trade details mismatch");
     }
 
+    #[test]
+    fn test_dates_must_be_business_days_with_calendars() {
+        use crate::calendar::HolidayCalendar;
+        use chrono::NaiveDate;
+
+        let good_friday = NaiveDate::from_ymd_opt(2025, 4, 18).unwrap();
+        let calendars = Calendars::default()
+            .with_calendar(Currency::GBP, HolidayCalendar::new([(good_friday, "Good Friday".into())]));
+        let engine = TradeEngine::builder().calendars(calendars).build();
+
+        // The sample settles on a weekend
+        let err = engine.create("alice", sample_trade_details()).unwrap_err();
+        assert_eq!(err.code(), "TVD12");
+
+        let mut details = sample_trade_details();
+        details.value_date = Utc.with_ymd_and_hms(2025, 4, 17, 0, 0, 0).unwrap();
+        details.delivery_date = Utc.with_ymd_and_hms(2025, 4, 22, 0, 0, 0).unwrap();
+        let trade_id = engine.create("alice", details.clone()).expect("Create failed");
+
+        // Good Friday is a GBP holiday, and GBP is in the underlying
+        details.delivery_date = Utc.with_ymd_and_hms(2025, 4, 18, 0, 0, 0).unwrap();
+        let err = engine.update("alice", trade_id, details, None, None).unwrap_err();
+        assert_eq!(err.code(), "TVD12");
+        assert!(err.message().contains("Good Friday"), "{}", err.message());
+    }
+
+    #[test]
+    fn test_revert_is_checked_like_an_update() {
+        use crate::calendar::HolidayCalendar;
+        use chrono::NaiveDate;
+
+        // Stored before the GBP calendar was loaded: v0 settles on Good Friday, v1 the day before
+        let mut holiday = sample_trade_details();
+        holiday.value_date = Utc.with_ymd_and_hms(2025, 4, 17, 0, 0, 0).unwrap();
+        holiday.delivery_date = Utc.with_ymd_and_hms(2025, 4, 18, 0, 0, 0).unwrap();
+        let business_day = TradeDetails { delivery_date: holiday.value_date, ..holiday.clone() };
+        let mut trade = Trade::new(7, holiday, "alice".into());
+        trade.add_snapshot("alice", TradeState::NeedsReapproval, business_day);
+        let store = InMemoryStore::new();
+        store.push(trade).unwrap();
+
+        let good_friday = NaiveDate::from_ymd_opt(2025, 4, 18).unwrap();
+        let calendars = Calendars::default()
+            .with_calendar(Currency::GBP, HolidayCalendar::new([(good_friday, "Good Friday".into())]));
+        let engine = TradeEngine::builder().store(store).calendars(calendars).build();
+
+        let err = engine.revert("alice", 7, 0, None, None).unwrap_err();
+        assert_eq!(err.code(), "TVD12");
+        assert!(err.tags().contains(&"revert".into()));
+        assert_eq!(engine.trade_history(7).unwrap().len(), 2);
+    }
+
     #[test]
     fn test_approve_trade_happy_path() {
         let engine = new_engine();
@@ -477,10 +1164,10 @@ mod tests {
         let trade_id = engine.create(requester, details).expect("Trade creation failed");
 
         // Submit it
-        engine.submit(requester, trade_id).expect("Submit failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
 
         // Approver (not requester) approves it
-        let result = engine.approve(approver, trade_id);
+        let result = engine.approve(approver, trade_id, None, None);
         assert!(result.is_ok(), "Approve failed: {:?}", result);
 
         // 4: Verify new state is Approved
@@ -498,10 +1185,10 @@ mod tests {
         let trade_id = engine.create(requester, details).expect("Trade creation failed");
 
         // Submit trade
-        engine.submit(requester, trade_id).expect("Submit failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
 
         // Requester tries to approve — this should fail
-        let result = engine.approve(requester, trade_id);
+        let result = engine.approve(requester, trade_id, None, None);
         assert!(result.is_err(), "Requester should not be allowed to approve");
 
         let err = result.unwrap_err();
@@ -521,18 +1208,18 @@ mod tests {
 
         // 1: Create + Submit
         let trade_id = engine.create(requester, details.clone()).expect("Trade creation failed");
-        engine.submit(requester, trade_id).expect("Submit failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
 
         // 2: Approver approves
-        engine.approve(approver, trade_id).expect("Initial approval failed");
+        engine.approve(approver, trade_id, None, None).expect("Initial approval failed");
 
         // 3: Approver updates the trade (triggers NeedsReapproval)
         let mut new_details = details.clone();
-        new_details.strike = Some(dec!(1.2500)); // small change
-        engine.update(approver, trade_id, new_details).expect("Update failed");
+        new_details.notional_amount = dec!(1_250_000.00); // small change
+        engine.update(approver, trade_id, new_details, None, None).expect("Update failed");
 
         // 4: Now requester re-approves
-        let result = engine.approve(requester, trade_id);
+        let result = engine.approve(requester, trade_id, None, None);
         assert!(result.is_ok(), "Re-approval by requester should succeed: {:?}", result);
 
         // 5: Check final state is Approved
@@ -540,6 +1227,23 @@ mod tests {
         assert_eq!(state, TradeState::Approved, "Expected trade to be in Approved after re-approval");
     }
 
+    #[test]
+    fn test_update_while_needing_reapproval() {
+        let engine = new_engine();
+        let details = sample_trade_details();
+        let trade_id = engine.create("alice", details.clone()).expect("Trade creation failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+
+        // A second update before anyone re-approves the first keeps the trade where it is
+        for amount in [dec!(1_250_000.00), dec!(1_300_000.00)] {
+            let mut new_details = details.clone();
+            new_details.notional_amount = amount;
+            let state = engine.update("bob", trade_id, new_details, None, None).expect("Update failed");
+            assert_eq!(state, TradeState::NeedsReapproval);
+        }
+        assert_eq!(engine.trade_history(trade_id).unwrap().len(), 4);
+    }
+
     #[test]
     fn test_reapproval_rejected_for_non_requester() {
         let engine = new_engine();
@@ -550,18 +1254,18 @@ mod tests {
 
         // 1: Create + Submit
         let trade_id = engine.create(requester, details.clone()).expect("Trade creation failed");
-        engine.submit(requester, trade_id).expect("Submit failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
 
         // 2: Approver approves
-        engine.approve(approver, trade_id).expect("Approval by bob failed");
+        engine.approve(approver, trade_id, None, None).expect("Approval by bob failed");
 
         // 3: Approver updates (triggers NeedsReapproval)
         let mut modified_details = details.clone();
-        modified_details.strike = Some(dec!(1.3456));
-        engine.update(approver, trade_id, modified_details).expect("Update failed");
+        modified_details.notional_amount = dec!(1_345_600.00);
+        engine.update(approver, trade_id, modified_details, None, None).expect("Update failed");
 
         // 4: Non-requester (charlie) tries to re-approve — should be rejected
-        let result = engine.approve(intruder, trade_id);
+        let result = engine.approve(intruder, trade_id, None, None);
         assert!(result.is_err(), "Non-requester re-approval should fail");
 
         let err = result.unwrap_err();
@@ -582,7 +1286,7 @@ mod tests {
         let trade_id = engine.create(user, details).expect("Create failed");
 
         // 2: Cancel it
-        let result = engine.cancel(user, trade_id);
+        let result = engine.cancel(user, trade_id, None, None);
         if result.is_err() {
             result.as_ref().err().unwrap().display_with_trace();
         }
@@ -602,17 +1306,17 @@ mod tests {
 
         // 1: Create → Submit → Approve
         let trade_id = engine.create(requester, details.clone()).expect("Create failed");
-        engine.submit(requester, trade_id).expect("Submit failed");
-        engine.approve(approver, trade_id).expect("Approval failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
+        engine.approve(approver, trade_id, None, None).expect("Approval failed");
 
         // 2: Send to counterparty
-        engine.send_to_execute(approver, trade_id).expect("Send to counterparty failed");
+        engine.send_to_execute(approver, trade_id, None, None).expect("Send to counterparty failed");
 
         // 3: Book (Executed)
-        engine.book(approver, trade_id).expect("Booking failed");
+        engine.book(approver, trade_id, None, confirmation()).expect("Booking failed");
 
         // 4: Attempt to cancel — should fail
-        let result = engine.cancel(approver, trade_id);
+        let result = engine.cancel(approver, trade_id, None, None);
         assert!(result.is_err(), "Cancel after execution should fail");
 
         let err = result.unwrap_err();
@@ -633,10 +1337,10 @@ mod tests {
         let trade_id = engine.create(user, details).expect("Create failed");
 
         // Step 2: Cancel it once (valid)
-        engine.cancel(user, trade_id).expect("Initial cancel should succeed");
+        engine.cancel(user, trade_id, None, None).expect("Initial cancel should succeed");
 
         // Step 3: Try cancel again — should fail
-        let result = engine.cancel(user, trade_id);
+        let result = engine.cancel(user, trade_id, None, None);
         assert!(result.is_err(), "Second cancel should fail");
 
         let err = result.unwrap_err();
@@ -653,14 +1357,14 @@ mod tests {
 
         // 1: Create, submit, approve
         let trade_id = engine.create(requester, details.clone()).expect("Create failed");
-        engine.submit(requester, trade_id).expect("Submit failed");
-        engine.approve(approver, trade_id).expect("Approve failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
+        engine.approve(approver, trade_id, None, None).expect("Approve failed");
 
         // 2: Modify details
-        details.strike = Some(dec!(1.3333)); // small change
+        details.notional_amount = dec!(1_333_300.00); // small change
 
         // 3: Update trade
-        let result = engine.update(approver, trade_id, details.clone());
+        let result = engine.update(approver, trade_id, details.clone(), None, None);
         assert!(result.is_ok(), "Update failed: {:?}", result);
 
         // 4: Check state is now NeedsReapproval
@@ -672,6 +1376,79 @@ mod tests {
         assert_eq!(updated, details, "Trade details should match updated");
     }
 
+    #[test]
+    fn test_revert_to_an_earlier_snapshot() {
+        let engine = new_engine();
+        let details = sample_trade_details();
+        let trade_id = engine.create("alice", details.clone()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        engine.approve("bob", trade_id, None, None).expect("Approve failed");
+
+        // A mistaken update, approved again by the requester
+        let mut mistake = details.clone();
+        mistake.notional_amount = dec!(9_999_999.00);
+        engine.update("bob", trade_id, mistake, None, None).expect("Update failed");
+        engine.approve("alice", trade_id, None, None).expect("Reapproval failed");
+
+        // Reverting is an update to v2's details, so the trade needs reapproval again
+        let state = engine.revert("bob", trade_id, 2, Some(4), None).expect("Revert failed");
+        assert_eq!(state, TradeState::NeedsReapproval);
+        assert_eq!(engine.trade_details(trade_id).unwrap(), details);
+        let history = engine.trade_history(trade_id).unwrap();
+        assert_eq!(history[5].reverted_from, Some(2));
+        assert_eq!(history[3].reverted_from, None);
+
+        // The diff says which version was brought back
+        let diff = engine.diff(trade_id, 4, 5).expect("Diff failed");
+        assert_eq!((diff.from_reverted_from, diff.to_reverted_from), (None, Some(2)));
+        assert!(diff.differences.contains_key("notional_amount"));
+        assert!(diff.to_string().contains("v5 restores the details of v2"));
+
+        // Nothing to revert, or nowhere to revert to
+        engine.approve("alice", trade_id, None, None).expect("Reapproval failed");
+        assert_eq!(engine.revert("bob", trade_id, 0, None, None).unwrap_err().code(), "TDI13");
+        let err = engine.revert("bob", trade_id, 42, None, None).unwrap_err();
+        assert_eq!(err.code(), "TSN26");
+        assert!(err.tags().contains(&"revert".into()));
+
+        // Too late once sent
+        engine.send_to_execute("bob", trade_id, None, None).expect("Send failed");
+        assert!(engine.revert("bob", trade_id, 3, None, None).is_err(), "Revert after sending should fail");
+        assert_eq!(engine.trade_history(trade_id).unwrap().len(), 8);
+    }
+
+    #[test]
+    fn test_expire_a_pending_trade() {
+        let engine = new_engine();
+        let mut receiver = engine.events().subscribe_async();
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+
+        let state = engine.expire("system", trade_id, Some(1), Some("Past its value date")).expect("Expire failed");
+        assert_eq!(state, TradeState::Expired);
+        let last = engine.trade_history(trade_id).unwrap().pop().unwrap();
+        assert_eq!((last.user_id.as_str(), last.comment.as_deref()), ("system", Some("Past its value date")));
+
+        let events: Vec<_> = std::iter::from_fn(|| receiver.try_recv().ok()).collect();
+        assert!(matches!(events.last(), Some(TradeEvent::Expired(id, _)) if *id == trade_id));
+
+        // Expired is final, nobody gets to approve it any more
+        assert_eq!(engine.approve("bob", trade_id, None, None).unwrap_err().code(), "TAF06");
+        assert_eq!(engine.expire("system", trade_id, None, None).unwrap_err().code(), "TAF06");
+    }
+
+    #[test]
+    fn test_expire_is_for_admins_under_the_standard_matrix() {
+        use crate::policy::{Role, RolePolicy};
+
+        let policy = RolePolicy::default().with_user("alice", [Role::Trader]).with_user("root", [Role::Admin]);
+        let engine = TradeEngine::builder().policy(policy).build();
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+
+        assert_eq!(engine.expire("alice", trade_id, None, None).unwrap_err().code(), "TUA04");
+        assert_eq!(engine.expire("root", trade_id, None, None).unwrap(), TradeState::Expired);
+    }
+
     #[test]
     fn test_update_noop_should_fail() {
         let engine = new_engine();
@@ -681,11 +1458,11 @@ mod tests {
 
         // : Create, submit, approve
         let trade_id = engine.create(requester, details.clone()).expect("Create failed");
-        engine.submit(requester, trade_id).expect("Submit failed");
-        engine.approve(approver, trade_id).expect("Approve failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
+        engine.approve(approver, trade_id, None, None).expect("Approve failed");
 
         // 2: Try to update with the *same* details
-        let result = engine.update(approver, trade_id, details.clone());
+        let result = engine.update(approver, trade_id, details.clone(), None, None);
         assert!(result.is_err(), "No-op update should fail");
 
         let err = result.unwrap_err();
@@ -703,14 +1480,14 @@ mod tests {
 
         // 1: Create, Submit, Approve, Send, Book
         let trade_id = engine.create(requester, details.clone()).expect("Create failed");
-        engine.submit(requester, trade_id).expect("Submit failed");
-        engine.approve(approver, trade_id).expect("Approve failed");
-        engine.send_to_execute(approver, trade_id).expect("Send failed");
-        engine.book(approver, trade_id).expect("Booking failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
+        engine.approve(approver, trade_id, None, None).expect("Approve failed");
+        engine.send_to_execute(approver, trade_id, None, None).expect("Send failed");
+        engine.book(approver, trade_id, None, confirmation()).expect("Booking failed");
 
         // 2: Try to update (should fail)
-        details.strike = Some(dec!(2.2222));
-        let result = engine.update(approver, trade_id, details);
+        details.notional_amount = dec!(2_222_200.00);
+        let result = engine.update(approver, trade_id, details, None, None);
 
         assert!(result.is_err(), "Update after execution should fail");
 
@@ -728,11 +1505,11 @@ mod tests {
 
         // 1: Create ➡️ Submit ➡️ Approve
         let trade_id = engine.create(requester, details.clone()).expect("Create failed");
-        engine.submit(requester, trade_id).expect("Submit failed");
-        engine.approve(approver, trade_id).expect("Approve failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
+        engine.approve(approver, trade_id, None, None).expect("Approve failed");
 
         // 2: Send to counterparty
-        let result = engine.send_to_execute(approver, trade_id);
+        let result = engine.send_to_execute(approver, trade_id, None, None);
         assert!(result.is_ok(), "send_to_execute should succeed");
 
         // 3: Confirm state is now SentToCounterparty
@@ -750,7 +1527,7 @@ mod tests {
         let trade_id = engine.create(user, details).expect("Create failed");
 
         // 2: Attempt to send to execute (invalid from Draft)
-        let result = engine.send_to_execute(user, trade_id);
+        let result = engine.send_to_execute(user, trade_id, None, None);
         assert!(result.is_err(), "Send from Draft should fail");
 
         let err = result.unwrap_err();
@@ -766,12 +1543,12 @@ mod tests {
 
         // 1: Create, Submit, Approve, Send
         let trade_id = engine.create(requester, details.clone()).expect("Create failed");
-        engine.submit(requester, trade_id).expect("Submit failed");
-        engine.approve(approver, trade_id).expect("Approve failed");
-        engine.send_to_execute(approver, trade_id).expect("Send failed");
+        engine.submit(requester, trade_id, None).expect("Submit failed");
+        engine.approve(approver, trade_id, None, None).expect("Approve failed");
+        engine.send_to_execute(approver, trade_id, None, None).expect("Send failed");
 
         // 2: Book the trade
-        let result = engine.book(approver, trade_id);
+        let result = engine.book(approver, trade_id, None, confirmation());
         assert!(result.is_ok(), "Booking should succeed");
 
         // 3: Confirm final state
@@ -779,6 +1556,28 @@ mod tests {
         assert_eq!(state, TradeState::Executed, "Expected state to be Executed");
     }
 
+    #[test]
+    fn test_state_changes_share_details() {
+        let engine = new_engine();
+        let details = sample_trade_details();
+        let trade_id = engine.create("alice", details.clone()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        engine.approve("bob", trade_id, None, None).expect("Approve failed");
+
+        let mut changed = details.clone();
+        changed.notional_amount = dec!(2_000_000.00);
+        engine.update("alice", trade_id, changed.clone(), None, None).expect("Update failed");
+
+        let trade = engine.store.get(trade_id).unwrap().unwrap();
+        let shared = |a: usize, b: usize| Arc::ptr_eq(&trade.history[a].details, &trade.history[b].details);
+        assert!(shared(0, 1) && shared(1, 2), "Submit and approve should share the details");
+        assert!(!shared(2, 3), "An update brings its own details");
+
+        // Every snapshot still reads as the full details
+        assert_eq!(*trade.get_snapshot(2).unwrap().details, details);
+        assert_eq!(trade.latest_details(), Some(&changed));
+    }
+
     #[test]
     fn test_book_from_draft_should_fail() {
         let engine = new_engine();
@@ -789,10 +1588,701 @@ mod tests {
         let trade_id = engine.create(user, details).expect("Create failed");
 
         // 2: Try to book immediately — invalid
-        let result = engine.book(user, trade_id);
+        let result = engine.book(user, trade_id, None, confirmation());
         assert!(result.is_err(), "Booking from Draft should fail");
 
         let err = result.unwrap_err();
         assert_eq!(err.code(), "TST02", "Expected TST02 for invalid transition");
     }
+
+    #[test]
+    fn test_expected_snapshot_id_matches() {
+        let engine = new_engine();
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+
+        // Freshly created trades are at version 0
+        let (_, version) = engine.trade_details_with_version(trade_id).expect("Details failed");
+        assert_eq!(version, 0);
+
+        engine.submit("alice", trade_id, Some(version)).expect("Submit at current version failed");
+        engine.approve("bob", trade_id, Some(1), None).expect("Approve at current version failed");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::Approved);
+    }
+
+    #[test]
+    fn test_stale_update_is_rejected() {
+        let engine = new_engine();
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+
+        // Two users read version 1, the first update wins
+        let (details, version) = engine.trade_details_with_version(trade_id).unwrap();
+        let mut first = details.clone();
+        first.notional_amount = dec!(1_111_100.00);
+        let mut second = details;
+        second.notional_amount = dec!(2_222_200.00);
+
+        engine.update("bob", trade_id, first.clone(), Some(version), None).expect("First update failed");
+        let err = engine.update("carol", trade_id, second, Some(version), None).unwrap_err();
+
+        assert_eq!(err.code(), "TVC15", "Expected TVC15 for a stale version");
+        assert!(err.tags().contains(&"update".into()), "Expected 'update' tag");
+        assert!(err.tags().contains(&"version".into()), "Expected 'version' tag");
+
+        // Nothing of the second update made it in
+        assert_eq!(engine.trade_details(trade_id).unwrap(), first);
+        assert_eq!(engine.trade_history(trade_id).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_stale_cancel_is_rejected() {
+        let engine = new_engine();
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.submit("alice", trade_id, Some(0)).expect("Submit failed");
+
+        let err = engine.cancel("alice", trade_id, Some(0), None).unwrap_err();
+        assert_eq!(err.code(), "TVC15");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::PendingApproval);
+    }
+
+    #[test]
+    fn test_concurrent_approvals_only_one_wins() {
+        use std::thread;
+
+        let engine = Arc::new(new_engine());
+        let trade_ids = (0..16)
+            .map(|_| {
+                let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+                engine.submit("alice", trade_id, None).expect("Submit failed");
+                trade_id
+            })
+            .collect::<Vec<_>>();
+
+        // Every approver races for every trade, each trade can only be approved once
+        let approvers = (0..8)
+            .map(|n| {
+                let engine = Arc::clone(&engine);
+                let trade_ids = trade_ids.clone();
+                thread::spawn(move || {
+                    trade_ids
+                        .iter()
+                        .filter(|id| engine.approve(&format!("approver{n}"), **id, None, None).is_ok())
+                        .count()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let approved: usize = approvers.into_iter().map(|t| t.join().unwrap()).sum();
+        assert_eq!(approved, trade_ids.len(), "each trade approved exactly once");
+
+        for trade_id in trade_ids {
+            let history = engine.trade_history(trade_id).unwrap();
+            assert_eq!(history.len(), 3, "Draft, PendingApproval, Approved - nothing lost or doubled");
+            assert_eq!(history[2].to_state, TradeState::Approved);
+        }
+    }
+
+    #[test]
+    fn test_builder_with_custom_parts() {
+        use crate::clock::FixedClock;
+        use crate::store::SqliteStore;
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        struct CountingIds(AtomicU64);
+        impl IdGenerator for CountingIds {
+            fn generate(&self) -> TradeId {
+                self.0.fetch_add(1, Ordering::SeqCst)
+            }
+        }
+
+        let pinned = Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap();
+        let engine = TradeEngine::builder()
+            .store(SqliteStore::in_memory().unwrap())
+            .id_generator(CountingIds(AtomicU64::new(500)))
+            .state_machine(StateMachine::default())
+            .clock(FixedClock(pinned))
+            .build();
+
+        let first = engine.create("alice", sample_trade_details()).expect("Create failed");
+        let second = engine.create("alice", sample_trade_details()).expect("Create failed");
+        assert_eq!((first, second), (500, 501));
+
+        engine.submit("alice", first, None).expect("Submit failed");
+        let history = engine.trade_history(first).unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history.iter().all(|s| s.timestamp == pinned));
+        assert_eq!(engine.trade_ids(true).unwrap(), vec![500, 501]);
+    }
+
+    #[test]
+    fn test_lifecycle_events_are_published_in_order() {
+        let engine = new_engine();
+        let seen = Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        let log = seen.clone();
+        engine.events().subscribe(move |event: &TradeEvent| {
+            log.lock().push((event.trade_id(), event.snapshot().snapshot_id, event.snapshot().to_state));
+        });
+
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        engine.approve("bob", trade_id, None, None).expect("Approve failed");
+        engine.send_to_execute("bob", trade_id, None, None).expect("Send failed");
+        engine.book("bob", trade_id, None, confirmation()).expect("Book failed");
+
+        let expected = vec![
+            (trade_id, 0, TradeState::Draft),
+            (trade_id, 1, TradeState::PendingApproval),
+            (trade_id, 2, TradeState::Approved),
+            (trade_id, 3, TradeState::SentToCounterparty),
+            (trade_id, 4, TradeState::Executed),
+        ];
+        assert_eq!(*seen.lock(), expected);
+    }
+
+    #[test]
+    fn test_failed_action_publishes_nothing() {
+        let engine = new_engine();
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+
+        let mut receiver = engine.events().subscribe_async();
+        assert!(engine.approve("bob", trade_id, None, None).is_err(), "Approving a draft should fail");
+        assert!(
+            engine.update("bob", trade_id, sample_trade_details(), None, None).is_err(),
+            "No-op update should fail"
+        );
+
+        assert!(receiver.try_recv().is_err(), "Nothing should have been published");
+    }
+
+    #[test]
+    fn test_async_subscriber_sees_the_event_kind() {
+        let engine = new_engine();
+        let mut receiver = engine.events().subscribe_async();
+
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.cancel("alice", trade_id, None, None).expect("Cancel failed");
+
+        assert!(matches!(receiver.try_recv().unwrap(), TradeEvent::Created(id, _) if id == trade_id));
+        match receiver.try_recv().unwrap() {
+            TradeEvent::Cancelled(id, snapshot) => {
+                assert_eq!(id, trade_id);
+                assert_eq!(snapshot.user_id, "alice");
+                assert_eq!(snapshot.to_state, TradeState::Cancelled);
+            }
+            other => panic!("Expected a Cancelled event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_query_by_state_and_requester() {
+        use crate::query::{SortBy, SortOrder};
+
+        let engine = new_engine();
+        let mut ids = Vec::new();
+        for user in ["alice", "alice", "bob"] {
+            ids.push(engine.create(user, sample_trade_details()).expect("Create failed"));
+        }
+        engine.submit("alice", ids[1], None).expect("Submit failed");
+
+        let pending = TradeQuery { state: Some(TradeState::PendingApproval), ..Default::default() };
+        let page = engine.query(pending).expect("Query failed");
+        assert_eq!(page.trades.len(), 1);
+        assert_eq!(page.trades[0].trade_id, ids[1]);
+        assert_eq!(page.trades[0].version, 1);
+
+        let alice = TradeQuery { requester: Some("alice".into()), order: SortOrder::Desc, ..Default::default() };
+        let page = engine.query(alice).expect("Query failed");
+        let mut expected = vec![ids[0], ids[1]];
+        expected.sort_by(|a, b| b.cmp(a));
+        assert_eq!(page.trades.iter().map(|t| t.trade_id).collect::<Vec<_>>(), expected);
+
+        let err =
+            engine.query(TradeQuery { cursor: Some("x".into()), sort_by: SortBy::TradeDate, ..Default::default() });
+        let err = err.unwrap_err();
+        assert_eq!(err.code(), "TIQ17");
+        assert!(err.tags().contains(&"query".into()));
+    }
+    /// Moves on an hour every time it is asked, so each snapshot gets its own hour
+    struct HourlyClock(parking_lot::Mutex<DateTime<Utc>>);
+
+    impl Clock for HourlyClock {
+        fn now(&self) -> DateTime<Utc> {
+            let mut now = self.0.lock();
+            let current = *now;
+            *now += chrono::Duration::hours(1);
+            current
+        }
+    }
+
+    #[test]
+    fn test_trade_as_of() {
+        let at = |hour: u32| Utc.with_ymd_and_hms(2025, 4, 1, hour, 0, 0).unwrap();
+        let engine = TradeEngine::builder().clock(HourlyClock(parking_lot::Mutex::new(at(9)))).build();
+
+        let details = sample_trade_details();
+        let mut changed = details.clone();
+        changed.notional_amount = dec!(2_000_000.00);
+
+        let trade_id = engine.create("alice", details.clone()).expect("Create failed"); // 09:00
+        engine.submit("alice", trade_id, None).expect("Submit failed"); // 10:00
+        engine.approve("bob", trade_id, None, None).expect("Approve failed"); // 11:00
+        engine.update("carol", trade_id, changed.clone(), None, None).expect("Update failed"); // 12:00
+
+        let snapshot = engine.trade_as_of(trade_id, at(10) + chrono::Duration::minutes(30)).unwrap();
+        assert_eq!(snapshot.to_state, TradeState::PendingApproval);
+        assert_eq!(snapshot.user_id, "alice");
+        assert_eq!(*snapshot.details, details);
+
+        // A snapshot is in force from the instant it was made
+        let snapshot = engine.trade_as_of(trade_id, at(12)).unwrap();
+        assert_eq!((snapshot.to_state, snapshot.user_id.as_str()), (TradeState::NeedsReapproval, "carol"));
+        assert_eq!(*snapshot.details, changed);
+
+        let err = engine.trade_as_of(trade_id, at(8)).unwrap_err();
+        assert_eq!(err.code(), "TNY18");
+        assert_eq!(engine.trade_as_of(42, at(12)).unwrap_err().code(), "TNF01");
+    }
+
+    #[test]
+    fn test_trades_as_of() {
+        let at = |hour: u32| Utc.with_ymd_and_hms(2025, 4, 1, hour, 0, 0).unwrap();
+        let engine = TradeEngine::builder().clock(HourlyClock(parking_lot::Mutex::new(at(9)))).build();
+
+        let first = engine.create("alice", sample_trade_details()).expect("Create failed"); // 09:00
+        engine.submit("alice", first, None).expect("Submit failed"); // 10:00
+        let second = engine.create("bob", sample_trade_details()).expect("Create failed"); // 11:00
+
+        let book = engine.trades_as_of(at(10)).unwrap();
+        assert_eq!(book.len(), 1);
+        assert_eq!(book[&first].to_state, TradeState::PendingApproval);
+
+        let book = engine.trades_as_of(at(11)).unwrap();
+        let mut ids = vec![first, second];
+        ids.sort();
+        assert_eq!(book.keys().copied().collect::<Vec<_>>(), ids);
+        assert_eq!(book[&second].to_state, TradeState::Draft);
+        assert!(engine.trades_as_of(at(8)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_role_policy_is_checked_before_every_action() {
+        use crate::policy::{Role, RolePolicy};
+
+        let policy = RolePolicy::default()
+            .with_user("alice", [Role::Trader])
+            .with_user("bob", [Role::Approver])
+            .with_user("olga", [Role::Operations]);
+        let engine = TradeEngine::builder().policy(policy).build();
+
+        let err = engine.create("olga", sample_trade_details()).unwrap_err();
+        assert_eq!(err.code(), "TUA04");
+        assert!(err.tags().contains(&"create".into()));
+
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        assert_eq!(engine.approve("alice", trade_id, None, None).unwrap_err().code(), "TUA04");
+        engine.approve("bob", trade_id, None, None).expect("Approve failed");
+
+        // Refused before anything else, even the workflow
+        let err = engine.book("alice", trade_id, None, confirmation()).unwrap_err();
+        assert_eq!(err.code(), "TUA04");
+        assert!(err.tags().contains(&"book".into()));
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::Approved);
+
+        engine.send_to_execute("olga", trade_id, None, None).expect("Send failed");
+        engine.book("olga", trade_id, None, confirmation()).expect("Book failed");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::Executed);
+    }
+
+    #[test]
+    fn test_quorum_needs_distinct_approvers() {
+        use crate::approval::{ApprovalRule, ApprovalRules};
+
+        let big_tickets = ApprovalRule {
+            trading_entity: None,
+            notional_currency: None,
+            notional_min: Some(dec!(500_000)),
+            notional_max: None,
+            approvers: 2,
+        };
+        let engine = TradeEngine::builder().approval_rules(ApprovalRules::new(vec![big_tickets]).unwrap()).build();
+        let events = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&events);
+        engine.events().subscribe(move |event: &TradeEvent| seen.lock().push(event.clone()));
+
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        assert_eq!(engine.approve("alice", trade_id, None, None).unwrap_err().code(), "TOR14");
+
+        // First of two: recorded, but still pending
+        engine.approve("bob", trade_id, None, None).expect("First approval failed");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::PendingApproval);
+        let history = engine.trade_history(trade_id).unwrap();
+        assert_eq!((history.len(), history[2].user_id.as_str()), (3, "bob"));
+        assert!(matches!(events.lock().last(), Some(TradeEvent::ApprovalRecorded(id, _)) if *id == trade_id));
+
+        let err = engine.approve("bob", trade_id, None, None).unwrap_err();
+        assert_eq!(err.code(), "TAA19");
+
+        engine.approve("carol", trade_id, None, None).expect("Second approval failed");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::Approved);
+        assert!(matches!(events.lock().last(), Some(TradeEvent::Approved(id, _)) if *id == trade_id));
+    }
+
+    #[test]
+    fn test_quorum_applies_to_reapproval() {
+        use crate::approval::{ApprovalRule, ApprovalRules};
+
+        let big_tickets = ApprovalRule {
+            trading_entity: None,
+            notional_currency: None,
+            notional_min: Some(dec!(5_000_000)),
+            notional_max: None,
+            approvers: 2,
+        };
+        let engine = TradeEngine::builder().approval_rules(ApprovalRules::new(vec![big_tickets]).unwrap()).build();
+
+        // A small trade only needs the one approver
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        engine.approve("bob", trade_id, None, None).expect("Approve failed");
+
+        // Raised above the threshold, the requester can't re-approve it alone
+        let bigger = TradeDetails { notional_amount: dec!(10_000_000), ..sample_trade_details() };
+        engine.update("alice", trade_id, bigger, None, None).expect("Update failed");
+        let err = engine.approve("alice", trade_id, None, None).unwrap_err();
+        assert_eq!(err.code(), "TOR14");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::NeedsReapproval);
+
+        // Two distinct approvers other than the requester, counted afresh after another update
+        engine.approve("bob", trade_id, None, None).expect("First re-approval failed");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::NeedsReapproval);
+        assert_eq!(engine.approve("bob", trade_id, None, None).unwrap_err().code(), "TAA19");
+
+        let again = TradeDetails { notional_amount: dec!(12_000_000), ..sample_trade_details() };
+        engine.update("alice", trade_id, again, None, None).expect("Update failed");
+        engine.approve("bob", trade_id, None, None).expect("Re-approval after update failed");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::NeedsReapproval);
+        engine.approve("carol", trade_id, None, None).expect("Second re-approval failed");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::Approved);
+    }
+
+    #[test]
+    fn test_quorum_only_for_matching_trades() {
+        use crate::approval::{ApprovalRule, ApprovalRules};
+
+        let gbp_only = ApprovalRule {
+            trading_entity: None,
+            notional_currency: Some(Currency::GBP),
+            notional_min: None,
+            notional_max: None,
+            approvers: 3,
+        };
+        let engine = TradeEngine::builder().approval_rules(ApprovalRules::new(vec![gbp_only]).unwrap()).build();
+
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed"); // USD
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        engine.approve("bob", trade_id, None, None).expect("Approve failed");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::Approved);
+    }
+
+    #[test]
+    fn test_approval_over_limit_is_refused_and_recorded() {
+        use crate::approval::{ApprovalLimit, ApprovalLimits, ApprovalRules};
+        use crate::policy::{Role, RolePolicy};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let policy = RolePolicy::default()
+            .with_user("alice", [Role::Trader])
+            .with_user("bob", [Role::Approver])
+            .with_user("carol", [Role::Approver]);
+        let limits = ApprovalLimits::default()
+            .with_role(Role::Approver, ApprovalLimit::base(dec!(500_000)))
+            .with_user("carol", ApprovalLimit::base(dec!(5_000_000)))
+            .with_rate(Currency::USD, Currency::EUR, dec!(1.08));
+        let rules = ApprovalRules::default().with_limits(limits).unwrap();
+        let engine = TradeEngine::builder().policy(policy).approval_rules(rules).build();
+
+        let approvals = Arc::new(AtomicUsize::new(0));
+        let seen = approvals.clone();
+        engine.events().subscribe(move |event: &TradeEvent| {
+            if matches!(event, TradeEvent::Approved(..)) {
+                seen.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed"); // 1m USD
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+
+        let err = engine.approve("bob", trade_id, None, None).unwrap_err();
+        assert_eq!(err.code(), "TAL21");
+        assert_eq!(err.message(), "bob may approve up to 500000 USD equivalent, this trade is 1000000.00 USD");
+        assert!(err.tags().contains(&"approve".into()));
+
+        // Nothing changed, but the refusal is on record
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::PendingApproval);
+        assert_eq!(engine.trade_history(trade_id).unwrap().len(), 2);
+        let rejections = engine.trade_rejections(trade_id).unwrap();
+        assert_eq!(rejections.len(), 1);
+        assert_eq!((rejections[0].user_id.as_str(), rejections[0].action), ("bob", TradeAction::Approve));
+        assert_eq!((rejections[0].version, rejections[0].code.as_str()), (1, "TAL21"));
+        assert_eq!(rejections[0].reason, err.message());
+        assert_eq!(approvals.load(Ordering::SeqCst), 0);
+
+        // Carol's own limit is higher than her role's
+        engine.approve("carol", trade_id, None, None).expect("Approve failed");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::Approved);
+        assert_eq!(approvals.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_comments_are_kept_and_can_be_mandatory() {
+        let rules = CommentRules { cancel: true, update_after_approval: true };
+        let engine = TradeEngine::builder().comment_rules(rules).build();
+
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        engine.approve("bob", trade_id, None, Some("Within limits")).expect("Approve failed");
+
+        // Approved, so amending it needs a reason
+        let mut details = sample_trade_details();
+        details.notional_amount = dec!(2_000_000.00);
+        let err = engine.update("alice", trade_id, details.clone(), None, Some("  ")).unwrap_err();
+        assert_eq!(err.code(), "TCR22");
+        assert_eq!(err.message(), "A comment is required to update this trade");
+        assert!(err.tags().contains(&"update".into()));
+        engine.update("alice", trade_id, details, None, Some("Client doubled the notional")).expect("Update failed");
+
+        assert_eq!(engine.cancel("alice", trade_id, None, None).unwrap_err().code(), "TCR22");
+        engine.cancel("alice", trade_id, None, Some("Client walked away")).expect("Cancel failed");
+
+        let comments: Vec<_> = engine.trade_history(trade_id).unwrap().into_iter().map(|s| s.comment).collect();
+        assert_eq!(
+            comments,
+            vec![
+                None,
+                None,
+                Some("Within limits".into()),
+                Some("Client doubled the notional".into()),
+                Some("Client walked away".into())
+            ]
+        );
+
+        let diff = engine.diff(trade_id, 2, 3).unwrap();
+        assert!(diff.to_string().contains("Comment: Within limits → Client doubled the notional"));
+    }
+
+    #[test]
+    fn test_execute_with_idempotency_key_runs_once() {
+        let at = Utc.with_ymd_and_hms(2025, 4, 1, 9, 0, 0).unwrap();
+        let engine = TradeEngine::builder()
+            .clock(HourlyClock(parking_lot::Mutex::new(at)))
+            .idempotency_window(chrono::Duration::hours(4))
+            .build();
+        let create = TradeCommand::Create { user_id: "alice".into(), details: sample_trade_details() };
+
+        // A retried create gets the same trade back, not a duplicate
+        let created = engine.execute(create.clone(), Some("create-1")).expect("Create failed");
+        assert_eq!(created.state, TradeState::Draft);
+        assert_eq!(engine.execute(create.clone(), Some("create-1")).unwrap(), created);
+        assert_eq!(engine.trade_ids(false).unwrap().len(), 1);
+
+        // A retried submit succeeds again, rather than failing as the trade is no longer a draft
+        let trade_id = created.trade_id;
+        let submit = TradeCommand::Submit { user_id: "alice".into(), trade_id, expected_version: None };
+        let submitted = engine.execute(submit.clone(), Some("submit-1")).expect("Submit failed");
+        assert_eq!(submitted, CommandOutcome { trade_id, state: TradeState::PendingApproval });
+        assert_eq!(engine.execute(submit.clone(), Some("submit-1")).unwrap(), submitted);
+        assert_eq!(engine.trade_history(trade_id).unwrap().len(), 2);
+        assert_eq!(engine.execute(submit.clone(), None).unwrap_err().code(), "TST02");
+
+        // The key is for that command only
+        let cancel = TradeCommand::Cancel { user_id: "alice".into(), trade_id, expected_version: None, comment: None };
+        let err = engine.execute(cancel.clone(), Some("submit-1")).unwrap_err();
+        assert_eq!(err.code(), "TIK23");
+        assert!(err.tags().contains(&"idempotency".into()));
+
+        // Failures are not remembered, the command runs again on retry
+        let approve =
+            TradeCommand::Approve { user_id: "alice".into(), trade_id, expected_version: None, comment: None };
+        assert_eq!(engine.execute(approve.clone(), Some("approve-1")).unwrap_err().code(), "TOR14");
+        let approve = TradeCommand::Approve { user_id: "bob".into(), trade_id, expected_version: None, comment: None };
+        assert_eq!(engine.execute(approve, Some("approve-1")).unwrap().state, TradeState::Approved);
+
+        // Past the window the key is forgotten, and the create runs again
+        let recreated = engine.execute(create, Some("create-1")).expect("Create failed");
+        assert_ne!(recreated.trade_id, created.trade_id);
+    }
+
+    #[test]
+    fn test_batch_all_or_nothing_applies_every_command_or_none() {
+        let engine = new_engine();
+        let first = engine.create("alice", sample_trade_details()).expect("Create failed");
+        let second = engine.create("alice", sample_trade_details()).expect("Create failed");
+        let mut receiver = engine.events().subscribe_async();
+
+        let submit = |trade_id| TradeCommand::Submit { user_id: "alice".into(), trade_id, expected_version: None };
+        let approve = |user_id: &str, trade_id| TradeCommand::Approve {
+            user_id: user_id.into(),
+            trade_id,
+            expected_version: None,
+            comment: None,
+        };
+
+        // The requester can't approve their own trade, so nothing of the batch is applied
+        let results = engine
+            .execute_batch(vec![submit(first), submit(second), approve("alice", second)], BatchMode::AllOrNothing);
+        let codes: Vec<_> = results.iter().map(|result| result.as_ref().unwrap_err().code()).collect();
+        assert_eq!(codes, vec!["TBA25", "TBA25", "TOR14"]);
+        assert_eq!(engine.trade_history(first).unwrap().len(), 1, "No partial snapshot should be stored");
+        assert_eq!(engine.trade_history(second).unwrap().len(), 1, "No partial snapshot should be stored");
+        assert!(engine.trade_rejections(second).unwrap().is_empty(), "Nothing of the batch should be recorded");
+        assert!(receiver.try_recv().is_err(), "Nothing should have been published");
+
+        // Later commands see the earlier ones, the events come once the batch is stored
+        let results =
+            engine.execute_batch(vec![submit(first), submit(second), approve("bob", second)], BatchMode::AllOrNothing);
+        let states: Vec<_> = results.into_iter().map(|result| result.expect("Batch item failed").state).collect();
+        assert_eq!(states, vec![TradeState::PendingApproval, TradeState::PendingApproval, TradeState::Approved]);
+        assert_eq!(engine.trade_get_status(second).unwrap(), TradeState::Approved);
+        for _ in 0..3 {
+            assert!(receiver.try_recv().is_ok(), "Each change should be published");
+        }
+    }
+
+    #[test]
+    fn test_batch_best_effort_keeps_what_succeeded() {
+        let engine = new_engine();
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+
+        let results = engine.execute_batch(
+            vec![
+                TradeCommand::Submit { user_id: "alice".into(), trade_id, expected_version: None },
+                TradeCommand::Book {
+                    user_id: "bob".into(),
+                    trade_id,
+                    expected_version: None,
+                    confirmation: confirmation(),
+                },
+                TradeCommand::Create { user_id: "carol".into(), details: sample_trade_details() },
+            ],
+            BatchMode::BestEffort,
+        );
+
+        assert_eq!(results[0].as_ref().unwrap().state, TradeState::PendingApproval);
+        assert!(results[1].is_err(), "Booking a trade pending approval should fail");
+        assert_eq!(results[2].as_ref().unwrap().state, TradeState::Draft);
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::PendingApproval);
+        assert_eq!(engine.trade_ids(false).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_product_rules_and_diff() {
+        let engine = new_engine();
+        let details = sample_trade_details();
+        let trade_id = engine.create("alice", details.clone()).expect("Create failed");
+
+        // An NDF fixing after its value date is refused, with the product named
+        let mut ndf = details.clone();
+        let fixing_date = Utc.with_ymd_and_hms(2025, 4, 14, 0, 0, 0).unwrap();
+        ndf.product = Product::Ndf { fixing_date, settlement_currency: Currency::USD };
+        let err = engine.update("alice", trade_id, ndf.clone(), None, None).unwrap_err();
+        assert_eq!(err.code(), "TPR29");
+        assert!(err.message().contains("Invalid NDF"), "{}", err.message());
+
+        ndf.product = Product::Ndf { fixing_date: details.trade_date, settlement_currency: Currency::USD };
+        engine.update("alice", trade_id, ndf, None, None).expect("Update failed");
+
+        // The diff has the product and the fields only the new product has
+        let diff = engine.diff(trade_id, 0, 1).expect("Diff failed");
+        assert_eq!(diff.differences["product"], ("\"Forward\"".to_string(), "\"NDF\"".to_string()));
+        assert_eq!(diff.differences["settlement_currency"], ("-".to_string(), "USD".to_string()));
+        assert!(diff.differences.contains_key("fixing_date"));
+        assert_eq!(diff.differences.len(), 3);
+    }
+
+    #[test]
+    fn test_book_with_execution_confirmation() {
+        let engine = new_engine();
+
+        // No strike until the trade is executed
+        let priced = TradeDetails { strike: Some(dec!(1.2345)), ..sample_trade_details() };
+        assert_eq!(engine.create("alice", priced).unwrap_err().code(), "TSK31");
+
+        let trade_id = engine.create("alice", sample_trade_details()).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        engine.approve("bob", trade_id, None, None).expect("Approve failed");
+        engine.send_to_execute("bob", trade_id, None, None).expect("Send failed");
+
+        // A confirmation for more than the trade is refused, the trade stays sent
+        let overfilled = ExecutionConfirmation { executed_notional: dec!(2_000_000), ..confirmation() };
+        let err = engine.book("bob", trade_id, None, overfilled).unwrap_err();
+        assert_eq!(err.code(), "TEC30");
+        assert_eq!(engine.trade_get_status(trade_id).unwrap(), TradeState::SentToCounterparty);
+
+        engine.book("bob", trade_id, None, confirmation()).expect("Book failed");
+        let history = engine.trade_history(trade_id).unwrap();
+        let booked = history.last().unwrap();
+        assert_eq!(booked.to_state, TradeState::Executed);
+        assert_eq!(booked.execution, Some(confirmation()));
+        assert_eq!(booked.details.strike, Some(dec!(1.2345)));
+        assert!(history[..history.len() - 1].iter().all(|s| s.execution.is_none()));
+    }
+
+    #[test]
+    fn test_book_swap_with_far_rate() {
+        let engine = new_engine();
+        let far = SwapLeg {
+            value_date: Utc.with_ymd_and_hms(2025, 7, 14, 0, 0, 0).unwrap(),
+            notional_amount: dec!(1_000_000.00),
+            rate: None,
+        };
+        let swap = TradeDetails { product: Product::Swap(far.clone()), ..sample_trade_details() };
+
+        // Like the strike, the far rate comes with the execution confirmation
+        let priced = SwapLeg { rate: Some(dec!(1.2400)), ..far.clone() };
+        let priced = TradeDetails { product: Product::Swap(priced), ..swap.clone() };
+        assert_eq!(engine.create("alice", priced).unwrap_err().code(), "TSK31");
+
+        let trade_id = engine.create("alice", swap).expect("Create failed");
+        engine.submit("alice", trade_id, None).expect("Submit failed");
+        engine.approve("bob", trade_id, None, None).expect("Approve failed");
+        engine.send_to_execute("bob", trade_id, None, None).expect("Send failed");
+
+        assert_eq!(engine.book("bob", trade_id, None, confirmation()).unwrap_err().code(), "TEC30");
+        let confirmation = ExecutionConfirmation { far_rate: Some(dec!(1.2400)), ..confirmation() };
+        engine.book("bob", trade_id, None, confirmation).expect("Book failed");
+
+        let booked = engine.trade_details(trade_id).unwrap();
+        assert_eq!(booked.strike, Some(dec!(1.2345)));
+        assert_eq!(booked.product, Product::Swap(SwapLeg { rate: Some(dec!(1.2400)), ..far }));
+    }
+
+    #[test]
+    fn test_amounts_fit_the_currency_minor_units() {
+        let engine = new_engine();
+
+        // Half a cent is refused, trailing zeros are not extra decimals
+        let fractional = TradeDetails { notional_amount: dec!(1_000_000.005), ..sample_trade_details() };
+        let err = engine.create("alice", fractional).unwrap_err();
+        assert_eq!(err.code(), "TMU32");
+        assert!(err.message().contains("(2)"), "{}", err.message());
+        let padded = TradeDetails { notional_amount: dec!(1_000_000.500), ..sample_trade_details() };
+        engine.create("alice", padded).expect("Create failed");
+
+        // Yen have no minor units
+        let yen = TradeDetails {
+            notional_currency: Currency::JPY,
+            notional_amount: dec!(150_000_000.5),
+            underlying: vec![Currency::USD, Currency::JPY],
+            ..sample_trade_details()
+        };
+        assert_eq!(engine.create("alice", yen).unwrap_err().code(), "TMU32");
+
+        // Withdrawn currencies can't be traded
+        let lira = "ITL".parse::<Currency>().unwrap();
+        let historic = TradeDetails { underlying: vec![Currency::USD, lira], ..sample_trade_details() };
+        assert_eq!(engine.create("alice", historic).unwrap_err().code(), "TIC08");
+    }
 }
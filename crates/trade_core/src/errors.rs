@@ -3,7 +3,7 @@ use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde_json::json;
 
-use crate::model::{Currency, TradeAction, TradeId, TradeState};
+use crate::model::{Currency, SnapshotId, TradeAction, TradeId, TradeState, UserId};
 
 #[derive(Debug)]
 pub enum ErrCodes {
@@ -23,6 +23,24 @@ pub enum ErrCodes {
     TVD12, // Invalid value date
     TDI13, // New details identical to existing
     TOR14, // Original requester cannot first-approve
+    TVC15, // Trade changed since the caller read it (version conflict)
+    TWF16, // Workflow table is invalid
+    TIQ17, // Invalid trade query (filters, limit or cursor)
+    TNY18, // Trade did not exist yet at the requested time
+    TAA19, // Approver already approved this trade (quorum needs distinct approvers)
+    TAR20, // Approval quorum rules are invalid
+    TAL21, // Approver's delegated authority limit is below the trade's notional
+    TCR22, // Action needs a comment (reason) and none was given
+    TIK23, // Idempotency key already used for a different command
+    TIP24, // Command with this idempotency key is still running
+    TBA25, // Batch item not applied because another item of the all-or-nothing batch failed
+    TSN26, // Trade has no snapshot (version) with this id
+    TCL27, // Holiday calendar file is invalid
+    TTN28, // Tenor can't be parsed, or has no value date
+    TPR29, // Details break a rule of the trade's product (e.g. a swap's far leg before its near leg)
+    TEC30, // Execution confirmation doesn't fit the trade it books
+    TSK31, // Strike or swap far rate given before the trade is executed
+    TMU32, // Amount has more decimals than its currency's minor units
 }
 
 impl ErrorCode for ErrCodes {
@@ -44,6 +62,24 @@ impl ErrorCode for ErrCodes {
             ErrCodes::TVD12 => "TVD12",
             ErrCodes::TDI13 => "TDI13",
             ErrCodes::TOR14 => "TOR14",
+            ErrCodes::TVC15 => "TVC15",
+            ErrCodes::TWF16 => "TWF16",
+            ErrCodes::TIQ17 => "TIQ17",
+            ErrCodes::TNY18 => "TNY18",
+            ErrCodes::TAA19 => "TAA19",
+            ErrCodes::TAR20 => "TAR20",
+            ErrCodes::TAL21 => "TAL21",
+            ErrCodes::TCR22 => "TCR22",
+            ErrCodes::TIK23 => "TIK23",
+            ErrCodes::TIP24 => "TIP24",
+            ErrCodes::TBA25 => "TBA25",
+            ErrCodes::TSN26 => "TSN26",
+            ErrCodes::TCL27 => "TCL27",
+            ErrCodes::TTN28 => "TTN28",
+            ErrCodes::TPR29 => "TPR29",
+            ErrCodes::TEC30 => "TEC30",
+            ErrCodes::TSK31 => "TSK31",
+            ErrCodes::TMU32 => "TMU32",
         }
     }
 
@@ -62,9 +98,27 @@ impl ErrorCode for ErrCodes {
             ErrCodes::TUE09 => "Underlying is empty",
             ErrCodes::TUC10 => "Underlying has no associated currency",
             ErrCodes::TTD11 => "Invalid trade date: {0}",
-            ErrCodes::TVD12 => "Invalid value date: {0}",
+            ErrCodes::TVD12 => "Invalid value date: {reason}",
             ErrCodes::TDI13 => "New trade details are identical to existing",
             ErrCodes::TOR14 => "Original requester cannot perform first-approval",
+            ErrCodes::TVC15 => "Trade has changed: expected version {expected}, found {actual}",
+            ErrCodes::TWF16 => "Invalid workflow: {reason}",
+            ErrCodes::TIQ17 => "Invalid trade query: {reason}",
+            ErrCodes::TNY18 => "Trade {trade_id} did not exist yet at {as_of}",
+            ErrCodes::TAA19 => "{user_id} has already approved this trade",
+            ErrCodes::TAR20 => "Invalid approval rules: {reason}",
+            ErrCodes::TAL21 => "{user_id} may approve up to {limit}, this trade is {attempted}",
+            ErrCodes::TCR22 => "A comment is required to {action} this trade",
+            ErrCodes::TIK23 => "Idempotency key {key} was already used for a different command",
+            ErrCodes::TIP24 => "A command with idempotency key {key} is still running",
+            ErrCodes::TBA25 => "Not applied, item {index} of the batch failed",
+            ErrCodes::TSN26 => "Trade {trade_id} has no version {version}",
+            ErrCodes::TCL27 => "Invalid holiday calendar: {reason}",
+            ErrCodes::TTN28 => "Invalid tenor: {reason}",
+            ErrCodes::TPR29 => "Invalid {product}: {reason}",
+            ErrCodes::TEC30 => "Invalid execution confirmation: {reason}",
+            ErrCodes::TSK31 => "Strike and swap far rate are set on booking, from the execution confirmation",
+            ErrCodes::TMU32 => "{amount} has more decimals than {currency} allows ({minor_units})",
         }
     }
 
@@ -88,6 +142,24 @@ pub enum ValidationError {
     NoUnderlyingCcy(Currency),
     InvalidTradeDate(DateTime<Utc>, String),
     InvalidValueDate(DateTime<Utc>, String),
+    VersionConflict(SnapshotId, SnapshotId), // expected, actual
+    InvalidWorkflow(String),
+    InvalidQuery(String),
+    NotYetCreated(TradeId, DateTime<Utc>), // trade, as-of time asked for
+    AlreadyApproved(UserId),
+    InvalidApprovalRules(String),
+    ApprovalLimitExceeded(UserId, String, String), // approver, their limit, the trade's notional
+    CommentRequired(TradeAction),
+    IdempotencyKeyReused(String),
+    IdempotencyKeyInFlight(String),
+    BatchAborted(usize), // index of the item that failed
+    SnapshotNotFound(TradeId, SnapshotId),
+    InvalidCalendar(String),
+    InvalidTenor(String),
+    InvalidProduct(String, String), // product, reason
+    InvalidExecution(String),
+    StrikeBeforeExecution,
+    TooManyDecimals(Decimal, Currency), // amount, its currency
 }
 
 impl From<String> for ValidationError {
@@ -143,6 +215,68 @@ impl From<ValidationError> for AppError {
                 let payload = json!({"date": date, "reason": reason});
                 AppError::from_code(ErrCodes::TVD12, payload).with_tags(&["validation", "dates"])
             }
+            ValidationError::VersionConflict(expected, actual) => {
+                let payload = json!({"expected": expected, "actual": actual});
+                AppError::from_code(ErrCodes::TVC15, payload).with_tags(&["validation", "version"])
+            }
+            ValidationError::InvalidWorkflow(reason) => {
+                AppError::from_code(ErrCodes::TWF16, json!({ "reason": reason })).with_tags(&["workflow"])
+            }
+            ValidationError::InvalidQuery(reason) => {
+                AppError::from_code(ErrCodes::TIQ17, json!({ "reason": reason })).with_tags(&["validation", "query"])
+            }
+            ValidationError::NotYetCreated(id, as_of) => {
+                let payload = json!({"trade_id": id, "as_of": as_of});
+                AppError::from_code(ErrCodes::TNY18, payload).with_tags(&["validation", "as_of"])
+            }
+            ValidationError::AlreadyApproved(user_id) => {
+                AppError::from_code(ErrCodes::TAA19, json!({ "user_id": user_id })).with_tags(&["validation", "quorum"])
+            }
+            ValidationError::InvalidApprovalRules(reason) => {
+                AppError::from_code(ErrCodes::TAR20, json!({ "reason": reason })).with_tags(&["quorum"])
+            }
+            ValidationError::ApprovalLimitExceeded(user_id, limit, attempted) => {
+                let payload = json!({"user_id": user_id, "limit": limit, "attempted": attempted});
+                AppError::from_code(ErrCodes::TAL21, payload).with_tags(&["validation", "limit"])
+            }
+            ValidationError::CommentRequired(action) => {
+                let payload = json!({ "action": action.to_string().to_lowercase() });
+                AppError::from_code(ErrCodes::TCR22, payload).with_tags(&["validation", "comment"])
+            }
+            ValidationError::IdempotencyKeyReused(key) => {
+                AppError::from_code(ErrCodes::TIK23, json!({ "key": key })).with_tags(&["validation", "idempotency"])
+            }
+            ValidationError::IdempotencyKeyInFlight(key) => {
+                AppError::from_code(ErrCodes::TIP24, json!({ "key": key })).with_tags(&["idempotency"])
+            }
+            ValidationError::BatchAborted(index) => {
+                AppError::from_code(ErrCodes::TBA25, json!({ "index": index })).with_tags(&["batch"])
+            }
+            ValidationError::SnapshotNotFound(trade_id, version) => {
+                let payload = json!({"trade_id": trade_id, "version": version});
+                AppError::from_code(ErrCodes::TSN26, payload).with_tags(&["validation", "version"])
+            }
+            ValidationError::InvalidCalendar(reason) => {
+                AppError::from_code(ErrCodes::TCL27, json!({ "reason": reason })).with_tags(&["calendar"])
+            }
+            ValidationError::InvalidTenor(reason) => {
+                AppError::from_code(ErrCodes::TTN28, json!({ "reason": reason })).with_tags(&["validation", "tenor"])
+            }
+            ValidationError::InvalidProduct(product, reason) => {
+                let payload = json!({"product": product, "reason": reason});
+                AppError::from_code(ErrCodes::TPR29, payload).with_tags(&["validation", "product"])
+            }
+            ValidationError::InvalidExecution(reason) => {
+                AppError::from_code(ErrCodes::TEC30, json!({ "reason": reason }))
+                    .with_tags(&["validation", "execution"])
+            }
+            ValidationError::StrikeBeforeExecution => {
+                AppError::from_code(ErrCodes::TSK31, json!({})).with_tags(&["validation", "strike"])
+            }
+            ValidationError::TooManyDecimals(amount, ccy) => {
+                let payload = json!({"amount": amount, "currency": ccy, "minor_units": ccy.minor_units()});
+                AppError::from_code(ErrCodes::TMU32, payload).with_tags(&["validation", "amount"])
+            }
         }
     }
 }
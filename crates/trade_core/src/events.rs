@@ -0,0 +1,212 @@
+use crate::model::{TradeAction, TradeEventSnapshot, TradeId};
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// How many events an async subscriber can fall behind before it starts missing them
+pub const DEFAULT_EVENT_CAPACITY: usize = 1_024;
+
+/// Something that happened to a trade, published by the engine once the store has it.
+/// Carries the snapshot the action added, so subscribers never need to read the trade back.
+#[derive(Debug, Clone, Serialize)]
+pub enum TradeEvent {
+    Created(TradeId, TradeEventSnapshot),
+    Submitted(TradeId, TradeEventSnapshot),
+    Approved(TradeId, TradeEventSnapshot),
+    /// An approval short of the quorum, the trade is still waiting for more
+    ApprovalRecorded(TradeId, TradeEventSnapshot),
+    Updated(TradeId, TradeEventSnapshot),
+    Cancelled(TradeId, TradeEventSnapshot),
+    Sent(TradeId, TradeEventSnapshot),
+    Booked(TradeId, TradeEventSnapshot),
+    Expired(TradeId, TradeEventSnapshot),
+}
+
+impl TradeEvent {
+    /// The event for a workflow action (creation has no action, see `TradeEvent::Created`)
+    pub fn for_action(action: TradeAction, trade_id: TradeId, snapshot: TradeEventSnapshot) -> Self {
+        match action {
+            TradeAction::Submit => TradeEvent::Submitted(trade_id, snapshot),
+            // Approvals short of a quorum leave the trade where it was
+            TradeAction::Approve if snapshot.from_state == snapshot.to_state => {
+                TradeEvent::ApprovalRecorded(trade_id, snapshot)
+            }
+            TradeAction::Approve => TradeEvent::Approved(trade_id, snapshot),
+            TradeAction::Update => TradeEvent::Updated(trade_id, snapshot),
+            TradeAction::Cancel => TradeEvent::Cancelled(trade_id, snapshot),
+            TradeAction::SendToExecute => TradeEvent::Sent(trade_id, snapshot),
+            TradeAction::Book => TradeEvent::Booked(trade_id, snapshot),
+            TradeAction::Expire => TradeEvent::Expired(trade_id, snapshot),
+        }
+    }
+
+    pub fn trade_id(&self) -> TradeId {
+        self.parts().0
+    }
+
+    pub fn snapshot(&self) -> &TradeEventSnapshot {
+        self.parts().1
+    }
+
+    fn parts(&self) -> (TradeId, &TradeEventSnapshot) {
+        match self {
+            TradeEvent::Created(id, snapshot)
+            | TradeEvent::Submitted(id, snapshot)
+            | TradeEvent::Approved(id, snapshot)
+            | TradeEvent::ApprovalRecorded(id, snapshot)
+            | TradeEvent::Updated(id, snapshot)
+            | TradeEvent::Cancelled(id, snapshot)
+            | TradeEvent::Sent(id, snapshot)
+            | TradeEvent::Booked(id, snapshot)
+            | TradeEvent::Expired(id, snapshot) => (*id, snapshot),
+        }
+    }
+}
+
+/// Synchronous subscriber, called on the thread that ran the engine action.
+/// The trade's lock is already released by then, but the caller is still waiting,
+/// so keep it quick - hand anything slow over to an async subscriber instead.
+///
+/// Because of that, two actions on the same trade from different threads can be seen in the
+/// reverse of the order they were committed in. Only `snapshot.snapshot_id` gives a trade's order:
+/// projections have to apply events by it (e.g. skip any older than what they already hold).
+pub trait TradeEventSubscriber: Send + Sync {
+    fn on_event(&self, event: &TradeEvent);
+}
+
+/// Plain closures make perfectly good subscribers
+impl<F> TradeEventSubscriber for F
+where
+    F: Fn(&TradeEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &TradeEvent) {
+        self(event)
+    }
+}
+
+/// Fans trade events out to subscribers:
+/// - sync: every `subscribe`d subscriber, in the order they were added
+/// - async: every `subscribe_async` receiver, over a tokio broadcast channel
+///
+/// Events are published after the trade's lock is released, so per-trade order is not the
+/// publishing order when actions race: order by `snapshot.snapshot_id` (see `TradeEventSubscriber`).
+pub struct EventBus {
+    subscribers: RwLock<Vec<Arc<dyn TradeEventSubscriber>>>,
+    sender: broadcast::Sender<TradeEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_CAPACITY)
+    }
+}
+
+impl EventBus {
+    /// `capacity` is how far behind an async receiver may lag before it sees `RecvError::Lagged`
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { subscribers: RwLock::new(Vec::new()), sender }
+    }
+
+    /// Adds a synchronous subscriber, it sees every event published from now on
+    pub fn subscribe(&self, subscriber: impl TradeEventSubscriber + 'static) {
+        self.subscribers.write().push(Arc::new(subscriber));
+    }
+
+    /// A receiver for async delivery, it sees every event published from now on
+    pub fn subscribe_async(&self) -> broadcast::Receiver<TradeEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Delivers the event to the sync subscribers, then queues it for the async ones
+    pub fn publish(&self, event: TradeEvent) {
+        // Clone the list so a subscriber may itself subscribe without deadlocking
+        let subscribers = self.subscribers.read().clone();
+        for subscriber in &subscribers {
+            subscriber.on_event(&event);
+        }
+
+        // Only fails when there are no async receivers, which is fine
+        let _ = self.sender.send(event);
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for the event bus
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Currency, Direction, Product, TradeDetails, TradeState};
+    use chrono::Utc;
+    use parking_lot::Mutex;
+    use rust_decimal_macros::dec;
+
+    fn snapshot(snapshot_id: usize, to_state: TradeState) -> TradeEventSnapshot {
+        let now = Utc::now();
+        TradeEventSnapshot {
+            snapshot_id,
+            user_id: "alice".into(),
+            timestamp: now,
+            from_state: TradeState::Draft,
+            to_state,
+            details: TradeDetails {
+                trading_entity: "Entity A".into(),
+                counterparty: "Counterparty B".into(),
+                direction: Direction::Buy,
+                notional_currency: Currency::USD,
+                notional_amount: dec!(1_000_000),
+                underlying: vec![Currency::EUR, Currency::USD],
+                trade_date: now,
+                value_date: now,
+                delivery_date: now,
+                strike: None,
+                product: Product::Forward,
+            }
+            .into(),
+            comment: None,
+            reverted_from: None,
+            execution: None,
+        }
+    }
+
+    #[test]
+    fn test_event_for_action() {
+        let event = TradeEvent::for_action(TradeAction::SendToExecute, 42, snapshot(3, TradeState::SentToCounterparty));
+        assert!(matches!(event, TradeEvent::Sent(42, _)));
+        assert_eq!(event.trade_id(), 42);
+        assert_eq!(event.snapshot().snapshot_id, 3);
+    }
+
+    #[test]
+    fn test_sync_subscribers_get_every_event_in_order() {
+        let bus = EventBus::default();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let log = seen.clone();
+        bus.subscribe(move |event: &TradeEvent| log.lock().push(event.snapshot().snapshot_id));
+
+        bus.publish(TradeEvent::Created(1, snapshot(0, TradeState::Draft)));
+        bus.publish(TradeEvent::Submitted(1, snapshot(1, TradeState::PendingApproval)));
+
+        assert_eq!(*seen.lock(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_async_receiver_gets_events() {
+        let bus = EventBus::new(8);
+        let mut receiver = bus.subscribe_async();
+
+        bus.publish(TradeEvent::Approved(7, snapshot(2, TradeState::Approved)));
+
+        let event = receiver.try_recv().expect("Event should be queued");
+        assert!(matches!(event, TradeEvent::Approved(7, _)));
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_is_fine() {
+        let bus = EventBus::default();
+        bus.publish(TradeEvent::Booked(1, snapshot(5, TradeState::Executed)));
+    }
+}
@@ -0,0 +1,176 @@
+//! Remembered command outcomes, so a retried command is not run twice
+//!
+//! A caller that is not sure whether a command went through (e.g. a REST call that timed out)
+//! sends it again with the same idempotency key. Keys belong to the user sending them.
+//! For a while (the window) after the first run, the same command under the same key gets
+//! the first run's outcome back instead of running again:
+//! - a different command under the key is refused (TIK23), the key is the caller's mistake
+//! - the same command while the first run is still going is refused (TIP24), try again shortly
+//! - failures are not remembered, the command changed nothing so it simply runs again
+//!
+//! Outcomes are kept in memory only, a restart forgets them.
+
+use crate::command::{CommandOutcome, TradeCommand};
+use crate::errors::ValidationError;
+use crate::model::UserId;
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+
+/// How long outcomes are remembered unless configured otherwise
+pub const DEFAULT_IDEMPOTENCY_WINDOW: Duration = Duration::hours(24);
+
+/// A key is only ever the sending user's
+type Key = (UserId, String);
+
+struct Entry {
+    command: TradeCommand,
+    outcome: Option<CommandOutcome>, // None while the command is running
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct Entries {
+    by_key: HashMap<Key, Entry>,
+    /// Keys in the order they expire, as the window is the same for all
+    expiries: VecDeque<(DateTime<Utc>, Key)>,
+}
+
+pub(crate) struct IdempotencyCache {
+    window: Duration,
+    entries: Mutex<Entries>,
+}
+
+impl IdempotencyCache {
+    pub(crate) fn new(window: Duration) -> Self {
+        Self { window, entries: Mutex::new(Entries::default()) }
+    }
+
+    /// Claims the key for the command. The first run's outcome if there was one, in which case
+    /// the command must not run again. Otherwise None: run it, then `finish`.
+    pub(crate) fn begin(
+        &self,
+        key: &str,
+        command: &TradeCommand,
+        now: DateTime<Utc>,
+    ) -> Result<Option<CommandOutcome>, ValidationError> {
+        let mut entries = self.entries.lock();
+        entries.forget_expired(now);
+
+        let key = (command.user_id().to_string(), key.to_string());
+        if let Some(entry) = entries.by_key.get(&key) {
+            if &entry.command != command {
+                return Err(ValidationError::IdempotencyKeyReused(key.1));
+            }
+            return entry.outcome.map(Some).ok_or(ValidationError::IdempotencyKeyInFlight(key.1));
+        }
+
+        // Held while running, so a run that never finishes doesn't block the key forever
+        let expires_at = now + self.window;
+        entries.expiries.push_back((expires_at, key.clone()));
+        entries.by_key.insert(key, Entry { command: command.clone(), outcome: None, expires_at });
+        Ok(None)
+    }
+
+    /// Records how the command claimed by `begin` went: the outcome is remembered for the window,
+    /// a failure frees the key up again
+    pub(crate) fn finish(
+        &self,
+        key: &str,
+        command: &TradeCommand,
+        outcome: Option<CommandOutcome>,
+        now: DateTime<Utc>,
+    ) {
+        let mut entries = self.entries.lock();
+        let key = (command.user_id().to_string(), key.to_string());
+        let Some(outcome) = outcome else {
+            entries.by_key.remove(&key);
+            return;
+        };
+
+        let expires_at = now + self.window;
+        if let Some(entry) = entries.by_key.get_mut(&key) {
+            entry.outcome = Some(outcome);
+            entry.expires_at = expires_at;
+            entries.expiries.push_back((expires_at, key));
+        }
+    }
+}
+
+impl Entries {
+    fn forget_expired(&mut self, now: DateTime<Utc>) {
+        while let Some((expires_at, _)) = self.expiries.front() {
+            if *expires_at > now {
+                break;
+            }
+            let (_, key) = self.expiries.pop_front().expect("Front was just seen");
+            // The key may have been taken again since, or finished later than it was claimed
+            if self.by_key.get(&key).is_some_and(|entry| entry.expires_at <= now) {
+                self.by_key.remove(&key);
+            }
+        }
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for the idempotency cache
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TradeState;
+    use chrono::TimeZone;
+
+    fn submit(user_id: &str, trade_id: u64) -> TradeCommand {
+        TradeCommand::Submit { user_id: user_id.into(), trade_id, expected_version: None }
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 4, 1, hour, 0, 0).unwrap()
+    }
+
+    const OUTCOME: CommandOutcome = CommandOutcome { trade_id: 1, state: TradeState::PendingApproval };
+
+    #[test]
+    fn test_replay_returns_the_first_outcome() {
+        let cache = IdempotencyCache::new(Duration::hours(1));
+        assert_eq!(cache.begin("k1", &submit("alice", 1), at(9)), Ok(None));
+        cache.finish("k1", &submit("alice", 1), Some(OUTCOME), at(9));
+
+        assert_eq!(cache.begin("k1", &submit("alice", 1), at(9)), Ok(Some(OUTCOME)));
+
+        // Someone else's key of the same name is theirs
+        assert_eq!(cache.begin("k1", &submit("bob", 1), at(9)), Ok(None));
+    }
+
+    #[test]
+    fn test_key_reused_or_still_running() {
+        let cache = IdempotencyCache::new(Duration::hours(1));
+        assert_eq!(cache.begin("k1", &submit("alice", 1), at(9)), Ok(None));
+        assert_eq!(
+            cache.begin("k1", &submit("alice", 1), at(9)),
+            Err(ValidationError::IdempotencyKeyInFlight("k1".into()))
+        );
+        assert_eq!(
+            cache.begin("k1", &submit("alice", 2), at(9)),
+            Err(ValidationError::IdempotencyKeyReused("k1".into()))
+        );
+
+        // A failed run frees the key up
+        cache.finish("k1", &submit("alice", 1), None, at(9));
+        assert_eq!(cache.begin("k1", &submit("alice", 2), at(9)), Ok(None));
+    }
+
+    #[test]
+    fn test_outcomes_are_forgotten_after_the_window() {
+        let cache = IdempotencyCache::new(Duration::hours(1));
+        cache.begin("k1", &submit("alice", 1), at(9)).unwrap();
+        cache.finish("k1", &submit("alice", 1), Some(OUTCOME), at(9));
+        cache.begin("k2", &submit("alice", 2), at(9)).unwrap(); // never finishes
+
+        assert_eq!(cache.begin("k1", &submit("alice", 1), at(9) + Duration::minutes(59)), Ok(Some(OUTCOME)));
+        assert_eq!(cache.begin("k1", &submit("alice", 1), at(10)), Ok(None));
+        assert_eq!(cache.begin("k2", &submit("alice", 3), at(10)), Ok(None));
+        assert_eq!(cache.entries.lock().by_key.len(), 2);
+    }
+}
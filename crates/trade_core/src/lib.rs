@@ -1,15 +1,30 @@
 //! Core trading engine components
 
 // Private modules
+mod idempotency;
 mod snowflake;
 mod state;
 mod util;
 
 // Public modules
+pub mod approval;
+pub mod batch;
+pub mod calendar;
+pub mod clock;
+pub mod command;
+pub mod comments;
 pub mod engine;
 pub mod errors;
+pub mod events;
 pub mod model;
+pub mod policy;
 pub mod prelude;
+pub mod query;
 pub mod store;
+pub mod tenor;
 
-pub use engine::TradeEngine;
+pub use engine::{TradeEngine, TradeEngineBuilder};
+pub use idempotency::DEFAULT_IDEMPOTENCY_WINDOW;
+pub use snowflake::{IdGenerator, SnowflakeIdGenerator};
+pub use state::{Guard, StateMachine, Transition, TransitionRule, WorkflowConfig};
+pub use util::TradeDiff;
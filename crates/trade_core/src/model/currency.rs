@@ -1,93 +1,269 @@
-// Supporting G20 Currency Codes only
-// We could move this to a config later
-
-use serde::{Deserialize, Serialize};
-/// Allow for conversion between currency codes and their string representations
-use strum_macros::{Display, EnumString};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display, Serialize, Deserialize)]
-#[strum(ascii_case_insensitive)]
-pub enum Currency {
-    #[strum(serialize = "ARS")]
-    ARS, // Argentine Peso
-    #[strum(serialize = "AUD")]
-    AUD, // Australian Dollar
-    #[strum(serialize = "BRL")]
-    BRL, // Brazilian Real
-    #[strum(serialize = "CAD")]
-    CAD, // Canadian Dollar
-    #[strum(serialize = "CNY")]
-    CNY, // Chinese Yuan
-    #[strum(serialize = "EUR")]
-    EUR, // Euro
-    #[strum(serialize = "INR")]
-    INR, // Indian Rupee
-    #[strum(serialize = "IDR")]
-    IDR, // Indonesian Rupiah
-    #[strum(serialize = "JPY")]
-    JPY, // Japanese Yen
-    #[strum(serialize = "KRW")]
-    KRW, // South Korean Won
-    #[strum(serialize = "MXN")]
-    MXN, // Mexican Peso
-    #[strum(serialize = "RUB")]
-    RUB, // Russian Ruble
-    #[strum(serialize = "SAR")]
-    SAR, // Saudi Riyal
-    #[strum(serialize = "ZAR")]
-    ZAR, // South African Rand
-    #[strum(serialize = "TRY")]
-    TRY, // Turkish Lira
-    #[strum(serialize = "GBP")]
-    GBP, // British Pound Sterling
-    #[strum(serialize = "USD")]
-    USD, // US Dollar
+// ISO 4217 currencies, active and historic, from the table embedded below
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// The ISO 4217 table: code, numeric code, minor units, name, status
+const ISO_4217: &str = include_str!("iso4217.csv");
+
+/// A currency by its ISO 4217 alphabetic code. Only codes in the table can be made,
+/// so every currency has a name, numeric code and minor units.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency([u8; 3]);
+
+/// One row of the ISO 4217 table
+struct CurrencyData {
+    code: &'static str,
+    numeric_code: u16,
+    minor_units: Option<u8>, // None where ISO says N.A., e.g. gold or the SDR
+    name: &'static str,
+    active: bool,
+}
+
+struct Iso4217 {
+    rows: Vec<CurrencyData>,
+    by_code: HashMap<[u8; 3], usize>,
+}
+
+impl Iso4217 {
+    fn parse(table: &'static str) -> Self {
+        let rows: Vec<CurrencyData> = table
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .skip(1) // Header
+            .map(|line| {
+                let row: Vec<&str> = line.split(',').collect();
+                let [code, numeric_code, minor_units, name, status] = row[..] else {
+                    panic!("ISO 4217 table: bad row {line:?}");
+                };
+                CurrencyData {
+                    code,
+                    numeric_code: numeric_code.parse().expect("ISO 4217 table: bad numeric code"),
+                    minor_units: (!minor_units.is_empty())
+                        .then(|| minor_units.parse().expect("ISO 4217 table: bad minor units")),
+                    name,
+                    active: status == "active",
+                }
+            })
+            .collect();
+        let by_code = rows.iter().enumerate().map(|(i, row)| (Currency::bytes(row.code), i)).collect();
+        Iso4217 { rows, by_code }
+    }
+}
+
+fn table() -> &'static Iso4217 {
+    static TABLE: OnceLock<Iso4217> = OnceLock::new();
+    TABLE.get_or_init(|| Iso4217::parse(ISO_4217))
 }
 
 impl Currency {
-    #[allow(dead_code)]
-    fn name(&self) -> &'static str {
-        match self {
-            Currency::ARS => "Argentine Peso",
-            Currency::AUD => "Australian Dollar",
-            Currency::BRL => "Brazilian Real",
-            Currency::CAD => "Canadian Dollar",
-            Currency::CNY => "Chinese Yuan",
-            Currency::EUR => "Euro",
-            Currency::INR => "Indian Rupee",
-            Currency::IDR => "Indonesian Rupiah",
-            Currency::JPY => "Japanese Yen",
-            Currency::KRW => "South Korean Won",
-            Currency::MXN => "Mexican Peso",
-            Currency::RUB => "Russian Ruble",
-            Currency::SAR => "Saudi Riyal",
-            Currency::ZAR => "South African Rand",
-            Currency::TRY => "Turkish Lira",
-            Currency::GBP => "British Pound Sterling",
-            Currency::USD => "US Dollar",
+    pub const ARS: Currency = Currency(*b"ARS"); // Argentine Peso
+    pub const AUD: Currency = Currency(*b"AUD"); // Australian Dollar
+    pub const BRL: Currency = Currency(*b"BRL"); // Brazilian Real
+    pub const CAD: Currency = Currency(*b"CAD"); // Canadian Dollar
+    pub const CHF: Currency = Currency(*b"CHF"); // Swiss Franc
+    pub const CNY: Currency = Currency(*b"CNY"); // Chinese Yuan
+    pub const EUR: Currency = Currency(*b"EUR"); // Euro
+    pub const INR: Currency = Currency(*b"INR"); // Indian Rupee
+    pub const IDR: Currency = Currency(*b"IDR"); // Indonesian Rupiah
+    pub const JPY: Currency = Currency(*b"JPY"); // Japanese Yen
+    pub const KRW: Currency = Currency(*b"KRW"); // South Korean Won
+    pub const MXN: Currency = Currency(*b"MXN"); // Mexican Peso
+    pub const RUB: Currency = Currency(*b"RUB"); // Russian Ruble
+    pub const SAR: Currency = Currency(*b"SAR"); // Saudi Riyal
+    pub const ZAR: Currency = Currency(*b"ZAR"); // South African Rand
+    pub const TRY: Currency = Currency(*b"TRY"); // Turkish Lira
+    pub const GBP: Currency = Currency(*b"GBP"); // Pound Sterling
+    pub const USD: Currency = Currency(*b"USD"); // US Dollar
+
+    fn bytes(code: &str) -> [u8; 3] {
+        let mut bytes = [0; 3];
+        if code.len() == 3 {
+            bytes.copy_from_slice(code.as_bytes());
+        }
+        bytes.map(|b| b.to_ascii_uppercase())
+    }
+
+    fn data(&self) -> &'static CurrencyData {
+        let table = table();
+        // Currencies are only made from codes in the table
+        &table.rows[table.by_code[&self.0]]
+    }
+
+    /// Every currency in the table, active ones first
+    pub fn all() -> impl Iterator<Item = Currency> {
+        table().rows.iter().map(|row| Currency(Currency::bytes(row.code)))
+    }
+
+    /// The currency with this ISO 4217 numeric code. Withdrawn codes can be reused,
+    /// in which case the active currency is the one returned.
+    pub fn from_numeric(numeric_code: u16) -> Option<Currency> {
+        Currency::all().find(|ccy| ccy.numeric_code() == numeric_code)
+    }
+
+    /// Alphabetic code, e.g. "USD"
+    pub fn code(&self) -> &'static str {
+        self.data().code
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.data().name
+    }
+
+    pub fn numeric_code(&self) -> u16 {
+        self.data().numeric_code
+    }
+
+    /// Decimal places amounts in this currency have, e.g. 2 for USD and 0 for JPY.
+    /// None for currencies ISO gives none for, like precious metals.
+    pub fn minor_units(&self) -> Option<u8> {
+        self.data().minor_units
+    }
+
+    /// Active currencies can be traded, historic (withdrawn) ones are only kept for reading old data
+    pub fn is_active(&self) -> bool {
+        self.data().active
+    }
+
+    /// Whether the amount has no more decimals than the currency's minor units allow
+    pub fn allows_amount(&self, amount: Decimal) -> bool {
+        self.minor_units().is_none_or(|units| amount.normalize().scale() <= units as u32)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UnknownCurrency(pub String);
+
+impl fmt::Display for UnknownCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown ISO 4217 currency code: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownCurrency {}
+
+impl FromStr for Currency {
+    type Err = UnknownCurrency;
+
+    /// Case-insensitive, e.g. "usd" is USD
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = Currency::bytes(s);
+        match table().by_code.contains_key(&code) {
+            true => Ok(Currency(code)),
+            false => Err(UnknownCurrency(s.to_string())),
         }
     }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Just the code, the way the enum this replaced printed
+impl fmt::Debug for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for currency.rs
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
 
-    #[allow(dead_code)]
-    fn numeric_code(&self) -> u16 {
-        match self {
-            Currency::ARS => 32,
-            Currency::AUD => 36,
-            Currency::BRL => 986,
-            Currency::CAD => 124,
-            Currency::CNY => 156,
-            Currency::EUR => 978,
-            Currency::INR => 356,
-            Currency::IDR => 360,
-            Currency::JPY => 392,
-            Currency::KRW => 410,
-            Currency::MXN => 484,
-            Currency::RUB => 643,
-            Currency::SAR => 682,
-            Currency::ZAR => 710,
-            Currency::TRY => 949,
-            Currency::GBP => 826,
-            Currency::USD => 840,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_table_has_every_named_currency() {
+        assert!(Currency::all().count() > 250);
+        let named = [
+            Currency::ARS,
+            Currency::AUD,
+            Currency::BRL,
+            Currency::CAD,
+            Currency::CHF,
+            Currency::CNY,
+            Currency::EUR,
+            Currency::INR,
+            Currency::IDR,
+            Currency::JPY,
+            Currency::KRW,
+            Currency::MXN,
+            Currency::RUB,
+            Currency::SAR,
+            Currency::ZAR,
+            Currency::TRY,
+            Currency::GBP,
+            Currency::USD,
+        ];
+        for ccy in named {
+            assert!(ccy.is_active(), "{ccy}");
         }
+        assert_eq!(Currency::GBP.name(), "Pound Sterling");
+        assert_eq!(Currency::USD.numeric_code(), 840);
+        assert_eq!(
+            Currency::all().filter(|ccy| ccy.is_active()).count(),
+            Currency::all().take_while(|ccy| ccy.is_active()).count()
+        );
+    }
+
+    #[test]
+    fn test_parse_and_serialize() {
+        assert_eq!("usd".parse::<Currency>().unwrap(), Currency::USD);
+        assert_eq!("KWD".parse::<Currency>().unwrap().minor_units(), Some(3));
+        assert!("XYZ".parse::<Currency>().is_err());
+        assert!("US".parse::<Currency>().is_err());
+
+        assert_eq!(serde_json::to_string(&Currency::EUR).unwrap(), "\"EUR\"");
+        assert_eq!(serde_json::from_str::<Currency>("\"eur\"").unwrap(), Currency::EUR);
+        let limits: HashMap<Currency, u32> = serde_json::from_str(r#"{"GBP": 1, "JPY": 2}"#).unwrap();
+        assert_eq!(limits[&Currency::JPY], 2);
+        assert_eq!(format!("{:?}", vec![Currency::GBP, Currency::USD]), "[GBP, USD]");
+    }
+
+    #[test]
+    fn test_numeric_codes_prefer_active_currencies() {
+        assert_eq!(Currency::from_numeric(392), Some(Currency::JPY));
+        assert_eq!(Currency::from_numeric(0), None);
+
+        // ANG's 532 was given to the Caribbean guilder that replaced it
+        let ang = "ANG".parse::<Currency>().unwrap();
+        assert!(!ang.is_active());
+        assert_eq!(Currency::from_numeric(532).unwrap().code(), "XCG");
+        assert_eq!(Currency::from_numeric(380).unwrap().code(), "ITL");
+    }
+
+    #[test]
+    fn test_amounts_fit_minor_units() {
+        assert!(Currency::USD.allows_amount(dec!(100.25)));
+        assert!(Currency::USD.allows_amount(dec!(100.2500)));
+        assert!(!Currency::USD.allows_amount(dec!(100.255)));
+        assert!(Currency::JPY.allows_amount(dec!(1_000_000.00)));
+        assert!(!Currency::JPY.allows_amount(dec!(1_000_000.5)));
+
+        let gold = "XAU".parse::<Currency>().unwrap();
+        assert_eq!(gold.minor_units(), None);
+        assert!(gold.allows_amount(dec!(12.3456789)));
     }
 }
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::ValidationError;
+use crate::model::{Product, TradeDetails};
+
+/// What the counterparty (or venue) confirmed when the trade was executed.
+/// Booking a trade takes one, and it stays on the snapshot that booked it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionConfirmation {
+    pub rate: Decimal, // Executed rate, becomes the trade's strike
+    pub executed_at: DateTime<Utc>,
+    pub counterparty_ref: String, // The counterparty's own reference for the trade
+    pub executed_notional: Decimal,
+    #[serde(default)]
+    pub far_rate: Option<Decimal>, // Swaps only, becomes the far leg's rate
+}
+
+impl ExecutionConfirmation {
+    /// Checks the confirmation against the details of the trade it books, at `now`
+    pub fn validate(&self, details: &TradeDetails, now: DateTime<Utc>) -> Result<(), ValidationError> {
+        let invalid = |reason: &str| Err(ValidationError::InvalidExecution(reason.to_string()));
+
+        if self.rate <= Decimal::ZERO {
+            return invalid("Executed rate must be positive");
+        }
+        if self.executed_notional <= Decimal::ZERO {
+            return invalid("Executed notional must be positive");
+        }
+        // Partial fills are fine, more than was agreed is not
+        if self.executed_notional > details.notional_amount {
+            return invalid("Executed notional is more than the trade's notional");
+        }
+        if !details.notional_currency.allows_amount(self.executed_notional) {
+            return Err(ValidationError::TooManyDecimals(self.executed_notional, details.notional_currency));
+        }
+        if self.counterparty_ref.trim().is_empty() {
+            return invalid("Counterparty reference is required");
+        }
+        if self.executed_at < details.trade_date || self.executed_at > now {
+            return invalid("Execution time must be between the trade date and now");
+        }
+        match (&details.product, self.far_rate) {
+            (Product::Swap(_), None) => return invalid("Far rate is required to book a swap"),
+            (Product::Swap(_), Some(rate)) if rate <= Decimal::ZERO => return invalid("Far rate must be positive"),
+            (Product::Swap(_), Some(_)) | (_, None) => {}
+            (_, Some(_)) => return invalid("Far rate is only for swaps"),
+        }
+        Ok(())
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for execution.rs
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Currency, Direction, SwapLeg};
+    use chrono::{Duration, TimeZone};
+    use rust_decimal_macros::dec;
+
+    fn details() -> TradeDetails {
+        let trade_date = Utc.with_ymd_and_hms(2025, 7, 1, 0, 0, 0).unwrap();
+        TradeDetails {
+            trading_entity: "BankA".into(),
+            counterparty: "ClientX".into(),
+            direction: Direction::Sell,
+            notional_currency: Currency::GBP,
+            notional_amount: dec!(500_000),
+            underlying: vec![Currency::GBP, Currency::USD],
+            trade_date,
+            value_date: trade_date + Duration::days(2),
+            delivery_date: trade_date + Duration::days(2),
+            strike: None,
+            product: Product::Spot,
+        }
+    }
+
+    fn confirmation() -> ExecutionConfirmation {
+        ExecutionConfirmation {
+            rate: dec!(1.2712),
+            executed_at: Utc.with_ymd_and_hms(2025, 7, 1, 14, 30, 0).unwrap(),
+            counterparty_ref: "CPX-88412".into(),
+            executed_notional: dec!(500_000),
+            far_rate: None,
+        }
+    }
+
+    fn reason(result: Result<(), ValidationError>) -> String {
+        match result {
+            Err(ValidationError::InvalidExecution(reason)) => reason,
+            other => panic!("expected an invalid execution, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_confirmation_matches_the_trade() {
+        let (details, now) = (details(), Utc.with_ymd_and_hms(2025, 7, 2, 0, 0, 0).unwrap());
+        assert!(confirmation().validate(&details, now).is_ok());
+
+        let partial = ExecutionConfirmation { executed_notional: dec!(250_000), ..confirmation() };
+        assert!(partial.validate(&details, now).is_ok());
+
+        let overfilled = ExecutionConfirmation { executed_notional: dec!(500_001), ..confirmation() };
+        assert!(reason(overfilled.validate(&details, now)).contains("more than"));
+
+        let no_rate = ExecutionConfirmation { rate: Decimal::ZERO, ..confirmation() };
+        assert!(reason(no_rate.validate(&details, now)).contains("rate"));
+
+        let no_ref = ExecutionConfirmation { counterparty_ref: " ".into(), ..confirmation() };
+        assert!(reason(no_ref.validate(&details, now)).contains("reference"));
+    }
+
+    #[test]
+    fn test_far_rate_only_for_swaps() {
+        let now = Utc.with_ymd_and_hms(2025, 7, 2, 0, 0, 0).unwrap();
+        let with_far_rate = ExecutionConfirmation { far_rate: Some(dec!(1.2750)), ..confirmation() };
+        assert!(reason(with_far_rate.validate(&details(), now)).contains("only for swaps"));
+
+        let far = SwapLeg {
+            value_date: Utc.with_ymd_and_hms(2025, 10, 3, 0, 0, 0).unwrap(),
+            notional_amount: dec!(500_000),
+            rate: None,
+        };
+        let swap = TradeDetails { product: Product::Swap(far), ..details() };
+        assert!(with_far_rate.validate(&swap, now).is_ok());
+        assert!(reason(confirmation().validate(&swap, now)).contains("required"));
+    }
+
+    #[test]
+    fn test_execution_time_is_not_in_the_future() {
+        let details = details();
+        let before = Utc.with_ymd_and_hms(2025, 7, 1, 12, 0, 0).unwrap();
+        assert!(reason(confirmation().validate(&details, before)).contains("Execution time"));
+
+        let early = ExecutionConfirmation { executed_at: details.trade_date - Duration::hours(1), ..confirmation() };
+        assert!(reason(early.validate(&details, Utc::now())).contains("Execution time"));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod currency;
 pub mod direction;
+pub mod execution;
+pub mod product;
 pub mod trade;
 pub mod trade_action;
 pub mod trade_details;
@@ -7,6 +9,8 @@ pub mod trade_state;
 
 pub use currency::*;
 pub use direction::*;
+pub use execution::*;
+pub use product::*;
 pub use trade::*;
 pub use trade_action::*;
 pub use trade_details::*;
@@ -0,0 +1,255 @@
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum_macros::{Display, EnumString};
+
+use crate::errors::ValidationError;
+use crate::model::{Currency, TradeDetails};
+
+/// A spot trade settles within this many calendar days of its trade date,
+/// T+2 plus room for a weekend and holidays. Anything later is a forward.
+pub const SPOT_MAX_DAYS: i64 = 7;
+
+/// What kind of FX trade it is, and the details only that kind of trade has.
+/// The trade's own dates, notional and strike are the near leg of a swap.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Product {
+    Spot,
+    /// Trades booked before products existed were forwards
+    #[default]
+    Forward,
+    Swap(SwapLeg),
+    #[serde(rename = "NDF")]
+    Ndf {
+        fixing_date: DateTime<Utc>,
+        settlement_currency: Currency, // Non-deliverable forwards settle the difference in this currency
+    },
+    VanillaOption {
+        expiry_date: DateTime<Utc>,
+        cut: OptionCut,
+        option_type: OptionType,
+    },
+}
+
+/// Far leg of a swap, exchanging back what the near leg exchanged
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapLeg {
+    pub value_date: DateTime<Utc>,
+    pub notional_amount: Decimal,
+    pub rate: Option<Decimal>, // Like the strike, set on booking from the execution confirmation
+}
+
+/// Time of day, and place, an option expires
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, EnumString, Serialize, Deserialize)]
+#[strum(ascii_case_insensitive)]
+pub enum OptionCut {
+    #[strum(serialize = "NY")]
+    NewYork, // 10:00 New York
+    #[strum(serialize = "TOK")]
+    Tokyo, // 15:00 Tokyo
+    #[strum(serialize = "ECB")]
+    Ecb, // 14:15 Frankfurt
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, EnumString, Serialize, Deserialize)]
+#[strum(ascii_case_insensitive)]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+impl Product {
+    /// Names as used over the API, same as the serialized variant
+    pub const NAMES: [&'static str; 5] = ["Spot", "Forward", "Swap", "NDF", "VanillaOption"];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Product::Spot => "Spot",
+            Product::Forward => "Forward",
+            Product::Swap(_) => "Swap",
+            Product::Ndf { .. } => "NDF",
+            Product::VanillaOption { .. } => "VanillaOption",
+        }
+    }
+
+    /// The product and its own fields by name, for diffs. Values are formatted like the other fields.
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![("product", format!("{:?}", self.name()))];
+        match self {
+            Product::Spot | Product::Forward => {}
+            Product::Swap(far) => {
+                fields.push(("far_value_date", format!("{:?}", far.value_date)));
+                fields.push(("far_notional_amount", format!("{:?}", far.notional_amount)));
+                fields.push(("far_rate", format!("{:?}", far.rate)));
+            }
+            Product::Ndf { fixing_date, settlement_currency } => {
+                fields.push(("fixing_date", format!("{:?}", fixing_date)));
+                fields.push(("settlement_currency", format!("{:?}", settlement_currency)));
+            }
+            Product::VanillaOption { expiry_date, cut, option_type } => {
+                fields.push(("expiry_date", format!("{:?}", expiry_date)));
+                fields.push(("cut", format!("{:?}", cut)));
+                fields.push(("option_type", format!("{:?}", option_type)));
+            }
+        }
+        fields
+    }
+
+    /// Rules on top of the ones every trade follows (see `TradeDetails::validate`)
+    pub fn validate(&self, details: &TradeDetails) -> Result<(), ValidationError> {
+        let invalid = |reason: String| Err(ValidationError::InvalidProduct(self.name().to_string(), reason));
+
+        match self {
+            Product::Spot => {
+                if details.value_date > details.trade_date + Duration::days(SPOT_MAX_DAYS) {
+                    return invalid(format!(
+                        "Value date is more than {SPOT_MAX_DAYS} days after the trade date, book a forward"
+                    ));
+                }
+            }
+            Product::Forward => {}
+            Product::Swap(far) => {
+                if far.value_date <= details.delivery_date {
+                    return invalid("Far leg must settle after the near leg".into());
+                }
+                if far.notional_amount <= Decimal::ZERO {
+                    return invalid("Far leg notional amount must be positive".into());
+                }
+                if !details.notional_currency.allows_amount(far.notional_amount) {
+                    return Err(ValidationError::TooManyDecimals(far.notional_amount, details.notional_currency));
+                }
+                if far.rate.is_some_and(|rate| rate <= Decimal::ZERO) {
+                    return invalid("Far leg rate must be positive".into());
+                }
+            }
+            Product::Ndf { fixing_date, settlement_currency } => {
+                if *fixing_date < details.trade_date || *fixing_date > details.value_date {
+                    return invalid("Fixing date must be between the trade date and the value date".into());
+                }
+                if !details.underlying.contains(settlement_currency) {
+                    return invalid(format!("Settlement currency {settlement_currency} is not in the underlying"));
+                }
+            }
+            Product::VanillaOption { expiry_date, .. } => {
+                if *expiry_date < details.trade_date || *expiry_date > details.value_date {
+                    return invalid("Expiry date must be between the trade date and the value date".into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Short form for tables, e.g. "NDF (fix 2025-07-10, USD)"
+impl fmt::Display for Product {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Product::Spot | Product::Forward => write!(f, "{}", self.name()),
+            Product::Swap(far) => write!(f, "Swap (far {})", far.value_date.date_naive()),
+            Product::Ndf { fixing_date, settlement_currency } => {
+                write!(f, "NDF (fix {}, {})", fixing_date.date_naive(), settlement_currency)
+            }
+            Product::VanillaOption { expiry_date, cut, option_type } => {
+                write!(f, "{} (exp {} {})", option_type, expiry_date.date_naive(), cut)
+            }
+        }
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for product.rs
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Direction;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+    use std::str::FromStr;
+
+    fn date(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+
+    fn details(product: Product) -> TradeDetails {
+        TradeDetails {
+            trading_entity: "BankA".into(),
+            counterparty: "ClientX".into(),
+            direction: Direction::Buy,
+            notional_currency: Currency::EUR,
+            notional_amount: dec!(1_000_000),
+            underlying: vec![Currency::EUR, Currency::USD],
+            trade_date: date(2025, 7, 1),
+            value_date: date(2025, 7, 3),
+            delivery_date: date(2025, 7, 3),
+            strike: None,
+            product,
+        }
+    }
+
+    fn reason(result: Result<(), ValidationError>) -> String {
+        match result {
+            Err(ValidationError::InvalidProduct(_, reason)) => reason,
+            other => panic!("expected an invalid product, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_spot_settles_within_a_week() {
+        assert!(details(Product::Spot).validate().is_ok());
+
+        let mut late = details(Product::Spot);
+        late.value_date = date(2025, 8, 1);
+        late.delivery_date = late.value_date;
+        assert!(reason(late.validate()).contains("book a forward"));
+        late.product = Product::Forward;
+        assert!(late.validate().is_ok());
+    }
+
+    #[test]
+    fn test_swap_far_leg_follows_the_near_leg() {
+        let far = SwapLeg { value_date: date(2025, 10, 3), notional_amount: dec!(1_000_000), rate: None };
+        assert!(details(Product::Swap(far.clone())).validate().is_ok());
+
+        let early = SwapLeg { value_date: date(2025, 7, 3), ..far.clone() };
+        assert!(reason(details(Product::Swap(early)).validate()).contains("after the near leg"));
+
+        let empty = SwapLeg { notional_amount: Decimal::ZERO, ..far };
+        assert!(reason(details(Product::Swap(empty)).validate()).contains("notional"));
+    }
+
+    #[test]
+    fn test_ndf_fixes_before_value_date_in_an_underlying_currency() {
+        let ndf = |fixing_date, settlement_currency| Product::Ndf { fixing_date, settlement_currency };
+        assert!(details(ndf(date(2025, 7, 2), Currency::USD)).validate().is_ok());
+        assert!(reason(details(ndf(date(2025, 7, 4), Currency::USD)).validate()).contains("Fixing date"));
+        assert!(reason(details(ndf(date(2025, 7, 2), Currency::GBP)).validate()).contains("GBP"));
+    }
+
+    #[test]
+    fn test_option_expires_before_value_date() {
+        let option = |expiry_date| Product::VanillaOption {
+            expiry_date,
+            cut: OptionCut::NewYork,
+            option_type: OptionType::Call,
+        };
+        assert!(details(option(date(2025, 7, 1))).validate().is_ok());
+        assert!(reason(details(option(date(2025, 6, 30))).validate()).contains("Expiry date"));
+    }
+
+    #[test]
+    fn test_names_parse_and_serialize() {
+        assert_eq!(OptionCut::from_str("tok").unwrap(), OptionCut::Tokyo);
+        assert_eq!(OptionCut::NewYork.to_string(), "NY");
+        assert_eq!(OptionType::from_str("PUT").unwrap(), OptionType::Put);
+
+        let ndf = Product::Ndf { fixing_date: date(2025, 7, 2), settlement_currency: Currency::USD };
+        let json = serde_json::to_string(&ndf).unwrap();
+        assert!(json.starts_with("{\"NDF\""));
+        assert_eq!(serde_json::from_str::<Product>(&json).unwrap(), ndf);
+        assert_eq!(ndf.to_string(), "NDF (fix 2025-07-02, USD)");
+        assert!(Product::NAMES.contains(&ndf.name()));
+    }
+}
@@ -1,27 +1,58 @@
 use crate::model::*;
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 pub type TradeId = u64;
 pub type SnapshotId = usize;
 pub type UserId = String;
 pub type HistoryTable = Vec<(SnapshotId, UserId, TradeState, TradeState, DateTime<Utc>)>;
 
-#[derive(Debug, Clone, Serialize)]
+/// One version of a trade. The details are shared with the previous snapshot when they didn't change,
+/// so a state change costs a pointer rather than a copy of the details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeEventSnapshot {
     pub snapshot_id: SnapshotId,
     pub user_id: UserId,
     pub timestamp: DateTime<Utc>,
     pub from_state: TradeState,
     pub to_state: TradeState,
-    pub details: TradeDetails,
+    pub details: Arc<TradeDetails>,
+
+    /// Why the action was taken, if the user said
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// The earlier snapshot whose details this one restores, when made by a revert
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reverted_from: Option<SnapshotId>,
+
+    /// What was confirmed when the trade was executed, on the snapshot that booked it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution: Option<ExecutionConfirmation>,
+}
+
+/// An action the engine refused but kept on record, e.g. an approval over the approver's limit.
+/// The trade itself is unchanged, so this sits alongside the history rather than in it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RejectedAction {
+    pub user_id: UserId,
+    pub timestamp: DateTime<Utc>,
+    pub action: TradeAction,
+    pub version: SnapshotId, // The version the action was refused on
+    pub code: String,        // Error code, e.g. "TAL21"
+    pub reason: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
     pub id: TradeId,
     pub created_at: DateTime<Utc>,        // When the trade was first created
     pub history: Vec<TradeEventSnapshot>, // Current state is the last entry
+
+    /// Refused actions worth auditing, oldest first
+    #[serde(default)]
+    pub rejections: Vec<RejectedAction>,
 }
 
 impl Trade {
@@ -30,17 +61,24 @@ impl Trade {
     /// Trade is an envelope for the details (detail history)
     /// The top level contains id, created_at, and history vector
     pub fn new(id: TradeId, initial_details: TradeDetails, user_id: UserId) -> Self {
-        let now = Utc::now(); // TODO - discuss, time can be taken from request entry in our network
+        Self::new_at(id, initial_details, user_id, Utc::now())
+    }
+
+    /// Same as `new`, but the creation time is given by the caller (e.g. the engine clock)
+    pub fn new_at(id: TradeId, initial_details: TradeDetails, user_id: UserId, now: DateTime<Utc>) -> Self {
         let initial_snapshot = TradeEventSnapshot {
             snapshot_id: 0,
             user_id,
             timestamp: now,
             from_state: TradeState::Draft, // Debatable whether we need this, it can be inferred
             to_state: TradeState::Draft,
-            details: initial_details,
+            details: Arc::new(initial_details),
+            comment: None,
+            reverted_from: None,
+            execution: None,
         };
 
-        Trade { id, created_at: now, history: vec![initial_snapshot] }
+        Trade { id, created_at: now, history: vec![initial_snapshot], rejections: Vec::new() }
     }
 
     /// Returns the current state of the trade
@@ -52,7 +90,13 @@ impl Trade {
     /// Returns the current details of the trade
     /// This will be taken from the very last entry in the history
     pub fn latest_details(&self) -> Option<&TradeDetails> {
-        self.history.last().map(|s| &s.details)
+        self.history.last().map(|s| s.details.as_ref())
+    }
+
+    /// Same as `latest_details`, but hands out the shared details themselves,
+    /// for a new snapshot that leaves them unchanged
+    pub fn latest_details_shared(&self) -> Option<Arc<TradeDetails>> {
+        self.history.last().map(|s| s.details.clone())
     }
 
     /// Add a new versioned snapshot to the trade
@@ -60,25 +104,129 @@ impl Trade {
         &mut self,
         user_id: impl Into<UserId>,
         to_state: TradeState,
-        details: TradeDetails,
+        details: impl Into<Arc<TradeDetails>>,
+    ) -> &TradeEventSnapshot {
+        self.add_snapshot_at(user_id, to_state, details, Utc::now())
+    }
+
+    /// Same as `add_snapshot`, but stamped with the given time
+    pub fn add_snapshot_at(
+        &mut self,
+        user_id: impl Into<UserId>,
+        to_state: TradeState,
+        details: impl Into<Arc<TradeDetails>>,
+        timestamp: DateTime<Utc>,
+    ) -> &TradeEventSnapshot {
+        self.add_commented_snapshot_at(user_id, to_state, details, timestamp, None)
+    }
+
+    /// Same as `add_snapshot_at`, with the user's reason for the change
+    pub fn add_commented_snapshot_at(
+        &mut self,
+        user_id: impl Into<UserId>,
+        to_state: TradeState,
+        details: impl Into<Arc<TradeDetails>>,
+        timestamp: DateTime<Utc>,
+        comment: Option<String>,
     ) -> &TradeEventSnapshot {
         self.history.push(TradeEventSnapshot {
             snapshot_id: self.history.len(),
             user_id: user_id.into(),
-            timestamp: Utc::now(),
+            timestamp,
             from_state: self.current_state(),
             to_state,
-            details,
+            details: details.into(),
+            comment,
+            reverted_from: None,
+            execution: None,
         });
 
         self.history.last().unwrap()
     }
 
+    /// Same as `add_commented_snapshot_at`, bringing back the details of an earlier snapshot (a revert).
+    /// None if the trade has no such snapshot.
+    pub fn add_reverted_snapshot_at(
+        &mut self,
+        user_id: impl Into<UserId>,
+        to_state: TradeState,
+        source: SnapshotId,
+        timestamp: DateTime<Utc>,
+        comment: Option<String>,
+    ) -> Option<&TradeEventSnapshot> {
+        let details = self.history.get(source)?.details.clone();
+        self.add_commented_snapshot_at(user_id, to_state, details, timestamp, comment);
+
+        let snapshot = self.history.last_mut()?;
+        snapshot.reverted_from = Some(source);
+        Some(&*snapshot)
+    }
+
+    /// Same as `add_snapshot_at`, for the booking of the trade with its execution confirmation
+    pub fn add_executed_snapshot_at(
+        &mut self,
+        user_id: impl Into<UserId>,
+        to_state: TradeState,
+        details: impl Into<Arc<TradeDetails>>,
+        timestamp: DateTime<Utc>,
+        execution: ExecutionConfirmation,
+    ) -> &TradeEventSnapshot {
+        self.add_snapshot_at(user_id, to_state, details, timestamp);
+
+        let snapshot = self.history.last_mut().unwrap();
+        snapshot.execution = Some(execution);
+        &*snapshot
+    }
+
+    /// Records an action refused on the trade as it stands now, for the audit trail
+    pub fn add_rejection(
+        &mut self,
+        user_id: impl Into<UserId>,
+        action: TradeAction,
+        code: impl Into<String>,
+        reason: impl Into<String>,
+        timestamp: DateTime<Utc>,
+    ) -> &RejectedAction {
+        self.rejections.push(RejectedAction {
+            user_id: user_id.into(),
+            timestamp,
+            action,
+            version: self.version(),
+            code: code.into(),
+            reason: reason.into(),
+        });
+
+        self.rejections.last().unwrap()
+    }
+
+    /// The current version of the trade, i.e. the ID of its latest snapshot
+    pub fn version(&self) -> SnapshotId {
+        self.history.last().map(|s| s.snapshot_id).unwrap_or_default()
+    }
+
+    /// Shares the details between consecutive snapshots wherever they are equal.
+    /// For trades read back from storage, where every snapshot comes with its own copy.
+    pub fn share_details(&mut self) {
+        for i in 1..self.history.len() {
+            let (before, after) = self.history.split_at_mut(i);
+            let (previous, current) = (&before[i - 1], &mut after[0]);
+            if !Arc::ptr_eq(&previous.details, &current.details) && previous.details == current.details {
+                current.details = previous.details.clone();
+            }
+        }
+    }
+
     /// Get a specific snapshot by version ID
     pub fn get_snapshot(&self, version: SnapshotId) -> Option<&TradeEventSnapshot> {
         self.history.get(version)
     }
 
+    /// The snapshot in force at the given instant, i.e. the last one made at or before it.
+    /// None if the trade did not exist yet.
+    pub fn snapshot_as_of(&self, at: DateTime<Utc>) -> Option<&TradeEventSnapshot> {
+        self.history.iter().rev().find(|s| s.timestamp <= at)
+    }
+
     /// Get the very latest snapshot
     pub fn get_snapshot_last(&self) -> Option<&TradeEventSnapshot> {
         self.history.last()
@@ -104,6 +252,29 @@ impl Trade {
             .map(|snapshot| snapshot.user_id.clone())
     }
 
+    /// Users who approved the trade without it reaching `Approved` yet, i.e. the partial
+    /// approvals towards a quorum, since the trade last went into `PendingApproval` or
+    /// `NeedsReapproval`. A partial approval keeps both the state and the details, unlike an
+    /// update in `NeedsReapproval`, which starts the count again.
+    pub fn pending_approvers(&self) -> Vec<UserId> {
+        self.history
+            .windows(2)
+            .rev()
+            .take_while(|pair| {
+                let (before, s) = (&pair[0], &pair[1]);
+                s.from_state == s.to_state
+                    && matches!(s.to_state, TradeState::PendingApproval | TradeState::NeedsReapproval)
+                    && s.details == before.details
+            })
+            .map(|pair| pair[1].user_id.clone())
+            .collect()
+    }
+
+    /// Whether the trade has been approved at some point, e.g. before being updated again
+    pub fn was_approved(&self) -> bool {
+        self.history.iter().any(|s| s.to_state == TradeState::Approved)
+    }
+
     /// Check if the most recent state is "NeedsReapproval"
     /// This is abstracted away into a function in case it needs special logic later
     /// or the rule changes, or it's used in multiple places. Just best practice
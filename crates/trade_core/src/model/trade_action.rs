@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Display, EnumString, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TradeAction {
     Submit,
     Approve,
@@ -8,6 +9,7 @@ pub enum TradeAction {
     Update,
     SendToExecute,
     Book,
+    Expire,
 }
 
 impl TradeAction {
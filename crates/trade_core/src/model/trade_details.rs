@@ -3,7 +3,7 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use crate::errors::ValidationError;
-use crate::model::{Currency, Direction};
+use crate::model::{Currency, Direction, Product};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TradeDetails {
@@ -17,6 +17,8 @@ pub struct TradeDetails {
     pub value_date: DateTime<Utc>,
     pub delivery_date: DateTime<Utc>,
     pub strike: Option<Decimal>, // Decimal for guaranteed precision
+    #[serde(default)] // Journals written before products existed hold forwards
+    pub product: Product,
 }
 
 impl TradeDetails {
@@ -30,6 +32,11 @@ impl TradeDetails {
             return Err(ValidationError::DetailsInvalid("Notional amount is required".into()));
         }
 
+        // Amounts can't be finer than the currency's minor units, e.g. cents for USD, whole yen for JPY
+        if !self.notional_currency.allows_amount(self.notional_amount) {
+            return Err(ValidationError::TooManyDecimals(self.notional_amount, self.notional_currency));
+        }
+
         // Check that underlying currency has at least one entry
         if self.underlying.is_empty() {
             return Err(ValidationError::EmptyUnderlying("Underlying currency must be present".into()));
@@ -40,6 +47,11 @@ impl TradeDetails {
             return Err(ValidationError::NoUnderlyingCcy(self.notional_currency));
         }
 
+        // Historic currencies are only there to read old data, not to trade
+        if let Some(ccy) = self.underlying.iter().find(|ccy| !ccy.is_active()) {
+            return Err(ValidationError::InvalidCurrency(*ccy));
+        }
+
         // Check that trade date is on or before value date
         if self.trade_date > self.value_date {
             return Err(ValidationError::InvalidTradeDate(
@@ -55,6 +67,17 @@ impl TradeDetails {
             ));
         }
 
+        self.product.validate(self)
+    }
+
+    /// `validate`, for details of a trade not executed yet: the strike, and a swap's far rate,
+    /// come with the execution confirmation
+    pub fn validate_unexecuted(&self) -> Result<(), ValidationError> {
+        self.validate()?;
+        let far_rate = matches!(&self.product, Product::Swap(far) if far.rate.is_some());
+        if self.strike.is_some() || far_rate {
+            return Err(ValidationError::StrikeBeforeExecution);
+        }
         Ok(())
     }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
-use strum_macros::Display;
+use strum_macros::{Display, EnumString};
 
-#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Display, EnumString, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TradeState {
     Draft,
     PendingApproval,
@@ -10,10 +10,11 @@ pub enum TradeState {
     SentToCounterparty,
     Executed,
     Cancelled,
+    Expired,
 }
 
 impl TradeState {
     pub fn is_final(self) -> bool {
-        matches!(self, TradeState::Executed | TradeState::Cancelled)
+        matches!(self, TradeState::Executed | TradeState::Cancelled | TradeState::Expired)
     }
 }
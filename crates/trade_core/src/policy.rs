@@ -0,0 +1,330 @@
+//! Who may do what to a trade
+//!
+//! Every engine action (creating a trade included) goes through the engine's `Policy` first,
+//! with the `Principal` behind the acting user ID. A refusal is `ValidationError::Unauthorized` (TUA04).
+//!
+//! The default policy, `AllowAll`, leaves everything open as it always was. `RolePolicy` is the
+//! real thing: a role-to-operation matrix and the roles of each user, loadable from config, e.g.
+//!
+//! ```toml
+//! [policy.permissions]
+//! Create = ["Trader", "Admin"]
+//! Book = ["Operations", "Admin"]
+//!
+//! [policy.users]
+//! alice = ["Trader"]
+//! ```
+//!
+//! Separation of duties on a single trade (e.g. requesters not approving their own trade) is
+//! a matter for the workflow guards, see `Guard`. The policy only answers "may this role do that".
+
+use crate::errors::ValidationError;
+use crate::model::{Trade, TradeAction, UserId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// What a user is there to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Role {
+    Trader,
+    Approver,
+    Operations,
+    Admin,
+}
+
+/// The acting user, as far as authorization goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub user_id: UserId,
+    pub roles: HashSet<Role>,
+}
+
+impl Principal {
+    pub fn new(user_id: impl Into<UserId>, roles: impl IntoIterator<Item = Role>) -> Self {
+        Self { user_id: user_id.into(), roles: roles.into_iter().collect() }
+    }
+
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.contains(&role)
+    }
+}
+
+/// Everything a principal can be authorized for: creating a trade, or a workflow action on one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Operation {
+    Create,
+    Submit,
+    Approve,
+    Update,
+    Cancel,
+    SendToExecute,
+    Book,
+    Expire,
+}
+
+impl From<TradeAction> for Operation {
+    fn from(action: TradeAction) -> Self {
+        match action {
+            TradeAction::Submit => Operation::Submit,
+            TradeAction::Approve => Operation::Approve,
+            TradeAction::Update => Operation::Update,
+            TradeAction::Cancel => Operation::Cancel,
+            TradeAction::SendToExecute => Operation::SendToExecute,
+            TradeAction::Book => Operation::Book,
+            TradeAction::Expire => Operation::Expire,
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Decides whether a principal may perform an operation
+pub trait Policy: Send + Sync {
+    /// The principal behind a user ID, i.e. the user with their roles
+    fn principal(&self, user_id: &str) -> Principal;
+
+    /// Ok if the principal may perform the operation. `trade` is the trade acted on,
+    /// as it stands under its lock, and None when creating one.
+    fn authorize(
+        &self,
+        principal: &Principal,
+        operation: Operation,
+        trade: Option<&Trade>,
+    ) -> Result<(), ValidationError>;
+}
+
+/// Anyone may do anything, the engine's default
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AllowAll;
+
+impl Policy for AllowAll {
+    fn principal(&self, user_id: &str) -> Principal {
+        Principal::new(user_id, [])
+    }
+
+    fn authorize(&self, _: &Principal, _: Operation, _: Option<&Trade>) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+/// Role-based policy: an operation is allowed if the principal holds any of the roles it is
+/// granted to. Users missing from `users` have no roles, so they may do nothing.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RolePolicy {
+    /// Operation -> roles allowed to perform it. Configured operations replace their own entry
+    /// in the standard matrix, the others keep theirs.
+    #[serde(default = "default_permissions", deserialize_with = "over_default_permissions")]
+    pub permissions: HashMap<Operation, HashSet<Role>>,
+
+    /// User ID -> their roles
+    #[serde(default)]
+    pub users: HashMap<UserId, HashSet<Role>>,
+}
+
+impl Default for RolePolicy {
+    /// The standard matrix, and no users yet
+    fn default() -> Self {
+        Self { permissions: default_permissions(), users: HashMap::new() }
+    }
+}
+
+/// The standard matrix:
+/// - traders draft, submit and amend trades
+/// - approvers approve them
+/// - operations send them to the counterparty and book them
+/// - traders and approvers may cancel, admins may do everything
+/// - only admins may expire trades, that is for the service's expiry job to do
+fn default_permissions() -> HashMap<Operation, HashSet<Role>> {
+    use Operation::*;
+    use Role::*;
+
+    let grant = |operation, roles: &[Role]| (operation, roles.iter().copied().chain([Admin]).collect());
+    HashMap::from([
+        grant(Create, &[Trader]),
+        grant(Submit, &[Trader]),
+        grant(Update, &[Trader]),
+        grant(Approve, &[Approver]),
+        grant(Cancel, &[Trader, Approver]),
+        grant(SendToExecute, &[Operations]),
+        grant(Book, &[Operations]),
+        grant(Expire, &[]),
+    ])
+}
+
+/// The configured permissions laid over the standard matrix
+fn over_default_permissions<'de, D>(deserializer: D) -> Result<HashMap<Operation, HashSet<Role>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let configured = HashMap::<Operation, HashSet<Role>>::deserialize(deserializer)?;
+    let mut permissions = default_permissions();
+    permissions.extend(configured);
+    Ok(permissions)
+}
+
+impl RolePolicy {
+    /// Gives a user their roles, replacing any they had
+    pub fn with_user(mut self, user_id: impl Into<UserId>, roles: impl IntoIterator<Item = Role>) -> Self {
+        self.users.insert(user_id.into(), roles.into_iter().collect());
+        self
+    }
+
+    /// Lets the given roles (only) perform the operation
+    pub fn with_permission(mut self, operation: Operation, roles: impl IntoIterator<Item = Role>) -> Self {
+        self.permissions.insert(operation, roles.into_iter().collect());
+        self
+    }
+}
+
+impl Policy for RolePolicy {
+    fn principal(&self, user_id: &str) -> Principal {
+        Principal::new(user_id, self.users.get(user_id).into_iter().flatten().copied())
+    }
+
+    fn authorize(&self, principal: &Principal, operation: Operation, _: Option<&Trade>) -> Result<(), ValidationError> {
+        let allowed = self.permissions.get(&operation);
+        if allowed.is_some_and(|roles| roles.iter().any(|role| principal.has_role(*role))) {
+            return Ok(());
+        }
+
+        // Sorted, so the message reads the same every time
+        let mut roles: Vec<String> = allowed.into_iter().flatten().map(|role| format!("{role:?}")).collect();
+        roles.sort();
+        Err(ValidationError::Unauthorized(format!(
+            "{} may not {operation}, that takes one of [{}]",
+            principal.user_id,
+            roles.join(", ")
+        )))
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for the authorization policies
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RolePolicy {
+        RolePolicy::default()
+            .with_user("trader", [Role::Trader])
+            .with_user("approver", [Role::Approver])
+            .with_user("ops", [Role::Operations])
+            .with_user("root", [Role::Admin])
+    }
+
+    fn allowed(policy: &RolePolicy, user_id: &str, operation: Operation) -> bool {
+        policy.authorize(&policy.principal(user_id), operation, None).is_ok()
+    }
+
+    #[test]
+    fn test_allow_all_allows_anything() {
+        let principal = AllowAll.principal("anyone");
+        assert!(principal.roles.is_empty());
+        assert!(AllowAll.authorize(&principal, Operation::Book, None).is_ok());
+    }
+
+    #[test]
+    fn test_default_matrix() {
+        let policy = policy();
+        assert!(allowed(&policy, "trader", Operation::Create));
+        assert!(allowed(&policy, "trader", Operation::Cancel));
+        assert!(!allowed(&policy, "trader", Operation::Approve));
+        assert!(!allowed(&policy, "trader", Operation::Book));
+
+        assert!(allowed(&policy, "approver", Operation::Approve));
+        assert!(!allowed(&policy, "approver", Operation::Update));
+
+        assert!(allowed(&policy, "ops", Operation::SendToExecute));
+        assert!(allowed(&policy, "ops", Operation::Book));
+        assert!(!allowed(&policy, "ops", Operation::Create));
+    }
+
+    #[test]
+    fn test_admin_may_do_everything() {
+        let policy = policy();
+        for operation in [
+            Operation::Create,
+            Operation::Submit,
+            Operation::Approve,
+            Operation::Update,
+            Operation::Cancel,
+            Operation::SendToExecute,
+            Operation::Book,
+        ] {
+            assert!(allowed(&policy, "root", operation), "Admin should be allowed to {operation}");
+        }
+    }
+
+    #[test]
+    fn test_unknown_user_may_do_nothing() {
+        let policy = policy();
+        let err = policy.authorize(&policy.principal("mallory"), Operation::Create, None).unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::Unauthorized("mallory may not Create, that takes one of [Admin, Trader]".into())
+        );
+    }
+
+    #[test]
+    fn test_operation_without_permission_is_refused() {
+        let policy = policy().with_permission(Operation::Cancel, []);
+        assert!(!allowed(&policy, "root", Operation::Cancel));
+    }
+
+    #[test]
+    fn test_policy_from_toml() {
+        let policy: RolePolicy = toml::from_str(
+            r#"
+            [permissions]
+            Book = ["Trader"]
+
+            [users]
+            alice = ["Trader"]
+            "#,
+        )
+        .expect("Policy should parse");
+
+        // Book is replaced, the rest of the standard matrix stays
+        assert!(allowed(&policy, "alice", Operation::Book));
+        assert!(allowed(&policy, "alice", Operation::Create));
+        assert_eq!(policy.permissions[&Operation::Book], HashSet::from([Role::Trader]));
+
+        let policy: RolePolicy = toml::from_str(r#"users = { bob = ["Approver"] }"#).unwrap();
+        assert_eq!(policy.permissions, default_permissions());
+        assert!(allowed(&policy, "bob", Operation::Approve));
+
+        assert!(toml::from_str::<RolePolicy>(r#"users = { bob = ["Janitor"] }"#).is_err());
+    }
+
+    #[test]
+    fn test_partial_permissions_keep_the_standard_matrix() {
+        // As in the commented example in config/app.toml
+        let policy: RolePolicy = toml::from_str(
+            r#"
+            [permissions]
+            Create = ["Trader", "Admin"]
+            Book = ["Operations", "Admin"]
+
+            [users]
+            root = ["Admin"]
+            bob = ["Approver"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.permissions.len(), default_permissions().len());
+        for operation in [Operation::Submit, Operation::Approve, Operation::Update, Operation::Cancel] {
+            assert!(allowed(&policy, "root", operation), "{operation}");
+        }
+        assert!(allowed(&policy, "root", Operation::SendToExecute));
+        assert!(allowed(&policy, "root", Operation::Expire));
+        assert!(allowed(&policy, "bob", Operation::Approve));
+        assert!(!allowed(&policy, "bob", Operation::Create));
+    }
+}
@@ -1,4 +1,14 @@
 pub use crate::engine::TradeEngine;
 pub use crate::errors::ErrCodes as TradeErrors;
-pub use crate::model::{Currency, Direction, Trade, TradeDetails, TradeId};
-pub use crate::store::{InMemoryStore, TradeStore};
+pub use crate::model::{Currency, Direction, RejectedAction, Trade, TradeDetails, TradeId};
+pub use crate::store::{InMemoryStore, JournalStore, SqliteStore, TradeStore};
+pub use crate::events::{EventBus, TradeEvent, TradeEventSubscriber};
+pub use crate::policy::{AllowAll, Operation, Policy, Principal, Role, RolePolicy};
+pub use crate::approval::{ApprovalLimit, ApprovalLimits, ApprovalRules};
+pub use crate::comments::CommentRules;
+pub use crate::command::{CommandOutcome, TradeCommand};
+pub use crate::batch::BatchMode;
+pub use crate::calendar::{Calendars, HolidayCalendar};
+pub use crate::tenor::{Tenor, TenorDates};
+pub use crate::model::{OptionCut, OptionType, Product, SwapLeg};
+pub use crate::model::ExecutionConfirmation;
@@ -0,0 +1,407 @@
+//! Trade queries for blotters and reports: filter, sort and page through trades
+//!
+//! Paging is cursor based. Each page hands back a `next_cursor` naming the sort value and
+//! trade ID of its last row, and the next page starts strictly after that row. Trades
+//! created or changed in the meantime don't shift the pages the way offsets would.
+
+use crate::errors::ValidationError;
+use crate::model::{Currency, Direction, SnapshotId, Trade, TradeDetails, TradeId, TradeState, UserId};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::cmp::Ordering;
+use strum_macros::{Display, EnumString};
+
+/// Page size when the query does not say
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Largest page a single query can ask for
+pub const MAX_PAGE_SIZE: usize = 1_000;
+
+/// What the results are ordered by, the trade ID breaks ties
+#[derive(Debug, Display, EnumString, Clone, Copy, Default, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+pub enum SortBy {
+    #[default]
+    TradeId,
+    CreatedAt,
+    NotionalAmount,
+    TradeDate,
+    ValueDate,
+}
+
+#[derive(Debug, Display, EnumString, Clone, Copy, Default, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Filters for `TradeEngine::query`, every filter that is set has to match.
+/// Text filters are exact matches, ranges are inclusive of `from`/`min` and `max`,
+/// exclusive of `created_to`.
+///
+/// ```ignore
+/// let page = engine.query(TradeQuery { state: Some(TradeState::Approved), limit: Some(50), ..Default::default() })?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TradeQuery {
+    pub state: Option<TradeState>,
+    pub counterparty: Option<String>,
+    pub trading_entity: Option<String>,
+    pub notional_currency: Option<Currency>,
+    pub direction: Option<Direction>,
+    pub requester: Option<UserId>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub notional_min: Option<Decimal>,
+    pub notional_max: Option<Decimal>,
+
+    pub sort_by: SortBy,
+    pub order: SortOrder,
+    pub limit: Option<usize>,   // DEFAULT_PAGE_SIZE if not set
+    pub cursor: Option<String>, // `next_cursor` of the previous page
+}
+
+/// One row of a query result: the trade as it stands now
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TradeSummary {
+    pub trade_id: TradeId,
+    pub state: TradeState,
+    pub requester: UserId,
+    pub created_at: DateTime<Utc>,
+    pub version: SnapshotId,
+    pub details: TradeDetails,
+}
+
+impl TradeSummary {
+    fn from_trade(trade: &Trade) -> Option<Self> {
+        Some(Self {
+            trade_id: trade.id,
+            state: trade.current_state(),
+            requester: trade.get_requester(),
+            created_at: trade.created_at,
+            version: trade.version(),
+            details: trade.latest_details()?.clone(),
+        })
+    }
+}
+
+/// A page of results, `next_cursor` is None on the last page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TradePage {
+    pub trades: Vec<TradeSummary>,
+    pub next_cursor: Option<String>,
+}
+
+/// Sortable value of a row, compared first, then the trade ID
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    Id,
+    Time(DateTime<Utc>),
+    Amount(Decimal),
+}
+
+impl TradeQuery {
+    /// Does the trade pass every filter that is set?
+    pub fn matches(&self, summary: &TradeSummary) -> bool {
+        let details = &summary.details;
+        let created = summary.created_at;
+
+        self.state.is_none_or(|state| summary.state == state)
+            && self.counterparty.as_ref().is_none_or(|cp| &details.counterparty == cp)
+            && self.trading_entity.as_ref().is_none_or(|entity| &details.trading_entity == entity)
+            && self.notional_currency.is_none_or(|ccy| details.notional_currency == ccy)
+            && self.direction.as_ref().is_none_or(|direction| &details.direction == direction)
+            && self.requester.as_ref().is_none_or(|user| &summary.requester == user)
+            && self.created_from.is_none_or(|from| created >= from)
+            && self.created_to.is_none_or(|to| created < to)
+            && self.notional_min.is_none_or(|min| details.notional_amount >= min)
+            && self.notional_max.is_none_or(|max| details.notional_amount <= max)
+    }
+
+    /// Filters, sorts and pages the given trades
+    pub fn page(&self, trades: impl IntoIterator<Item = Trade>) -> Result<TradePage, ValidationError> {
+        let limit = self.validate()?;
+        let after = self.cursor.as_deref().map(|cursor| self.parse_cursor(cursor)).transpose()?;
+
+        let mut rows: Vec<(SortValue, TradeSummary)> = trades
+            .into_iter()
+            .filter_map(|trade| TradeSummary::from_trade(&trade))
+            .filter(|summary| self.matches(summary))
+            .map(|summary| (self.sort_value(&summary), summary))
+            .collect();
+        rows.sort_by(|a, b| self.compare(a, b));
+
+        // Keyset paging: skip everything up to and including the cursor row
+        let start = match &after {
+            Some(cursor) => rows.partition_point(|row| self.compare(row, cursor) != Ordering::Greater),
+            None => 0,
+        };
+        let mut rows: Vec<_> = rows.into_iter().skip(start).take(limit + 1).collect();
+
+        let next_cursor = if rows.len() > limit {
+            rows.truncate(limit);
+            rows.last().map(|(value, summary)| self.cursor_for(value, summary.trade_id))
+        } else {
+            None
+        };
+
+        Ok(TradePage { trades: rows.into_iter().map(|(_, summary)| summary).collect(), next_cursor })
+    }
+
+    /// Checks the query holds together, answering with the page size
+    fn validate(&self) -> Result<usize, ValidationError> {
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if limit == 0 || limit > MAX_PAGE_SIZE {
+            return Err(query_error(format!("limit must be between 1 and {MAX_PAGE_SIZE}")));
+        }
+        if let (Some(min), Some(max)) = (self.notional_min, self.notional_max) {
+            if min > max {
+                return Err(query_error(format!("notional_min {min} is above notional_max {max}")));
+            }
+        }
+        if let (Some(from), Some(to)) = (self.created_from, self.created_to) {
+            if from > to {
+                return Err(query_error("created_from is after created_to".into()));
+            }
+        }
+        Ok(limit)
+    }
+
+    fn sort_value(&self, summary: &TradeSummary) -> SortValue {
+        match self.sort_by {
+            SortBy::TradeId => SortValue::Id,
+            SortBy::CreatedAt => SortValue::Time(summary.created_at),
+            SortBy::NotionalAmount => SortValue::Amount(summary.details.notional_amount),
+            SortBy::TradeDate => SortValue::Time(summary.details.trade_date),
+            SortBy::ValueDate => SortValue::Time(summary.details.value_date),
+        }
+    }
+
+    /// Compares rows by sort value then trade ID, in the requested order.
+    /// Works for the cursor too, it is a row without the trade attached.
+    fn compare<R: SortRow, C: SortRow>(&self, a: &R, b: &C) -> Ordering {
+        let ordering = (a.value(), a.trade_id()).cmp(&(b.value(), b.trade_id()));
+        match self.order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+
+    /// Cursors look like `<sort_by>/<value>/<trade_id>`, e.g. `created_at/2025-04-10T00:00:00Z/4242`
+    fn cursor_for(&self, value: &SortValue, trade_id: TradeId) -> String {
+        let value = match value {
+            SortValue::Id => String::new(),
+            // `Z` rather than `+00:00`, a `+` in a query string reads back as a space
+            SortValue::Time(time) => time.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            SortValue::Amount(amount) => amount.to_string(),
+        };
+        format!("{}/{value}/{trade_id}", self.sort_by)
+    }
+
+    fn parse_cursor(&self, cursor: &str) -> Result<(SortValue, TradeId), ValidationError> {
+        let invalid = || query_error(format!("cursor '{cursor}' does not belong to this query"));
+
+        let mut parts = cursor.splitn(3, '/');
+        let (Some(sort_by), Some(value), Some(trade_id)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid());
+        };
+        if sort_by != self.sort_by.to_string() {
+            return Err(invalid());
+        }
+
+        let trade_id = trade_id.parse::<TradeId>().map_err(|_| invalid())?;
+        let value = match self.sort_by {
+            SortBy::TradeId => SortValue::Id,
+            SortBy::NotionalAmount => SortValue::Amount(value.parse().map_err(|_| invalid())?),
+            SortBy::CreatedAt | SortBy::TradeDate | SortBy::ValueDate => {
+                let time = DateTime::parse_from_rfc3339(value).map_err(|_| invalid())?;
+                SortValue::Time(time.with_timezone(&Utc))
+            }
+        };
+        Ok((value, trade_id))
+    }
+}
+
+/// Anything that can be placed in the sort order: result rows, and the cursor
+trait SortRow {
+    fn value(&self) -> &SortValue;
+    fn trade_id(&self) -> TradeId;
+}
+
+impl SortRow for (SortValue, TradeSummary) {
+    fn value(&self) -> &SortValue {
+        &self.0
+    }
+    fn trade_id(&self) -> TradeId {
+        self.1.trade_id
+    }
+}
+
+impl SortRow for (SortValue, TradeId) {
+    fn value(&self) -> &SortValue {
+        &self.0
+    }
+    fn trade_id(&self) -> TradeId {
+        self.1
+    }
+}
+
+fn query_error(reason: String) -> ValidationError {
+    ValidationError::InvalidQuery(reason)
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for trade queries
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::model::Product;
+    use rust_decimal_macros::dec;
+
+    fn details(counterparty: &str, amount: Decimal, direction: Direction) -> TradeDetails {
+        let day = Utc.with_ymd_and_hms(2025, 4, 10, 0, 0, 0).unwrap();
+        TradeDetails {
+            trading_entity: "EntityA".into(),
+            counterparty: counterparty.into(),
+            direction,
+            notional_currency: Currency::USD,
+            notional_amount: amount,
+            underlying: vec![Currency::EUR, Currency::USD],
+            trade_date: day,
+            value_date: day + Duration::days(2),
+            delivery_date: day + Duration::days(3),
+            strike: None,
+            product: Product::Forward,
+        }
+    }
+
+    /// Ten trades, IDs 1..=10, an hour apart, alternating counterparty and direction,
+    /// notional 1000 * id, trades 1-3 requested by bob, the rest by alice
+    fn blotter() -> Vec<Trade> {
+        let start = Utc.with_ymd_and_hms(2025, 4, 1, 9, 0, 0).unwrap();
+        (1..=10u64)
+            .map(|id| {
+                let (cp, direction) =
+                    if id % 2 == 0 { ("CP-Even", Direction::Buy) } else { ("CP-Odd", Direction::Sell) };
+                let user = if id <= 3 { "bob" } else { "alice" };
+                let created = start + Duration::hours(id as i64);
+                let mut trade =
+                    Trade::new_at(id, details(cp, Decimal::from(id * 1_000), direction), user.into(), created);
+                if id % 5 == 0 {
+                    let d = trade.latest_details().cloned().unwrap();
+                    trade.add_snapshot_at("carol", TradeState::Cancelled, d, created);
+                }
+                trade
+            })
+            .collect()
+    }
+
+    fn ids(page: &TradePage) -> Vec<TradeId> {
+        page.trades.iter().map(|t| t.trade_id).collect()
+    }
+
+    #[test]
+    fn test_no_filters_returns_everything_by_id() {
+        let page = TradeQuery::default().page(blotter()).unwrap();
+        assert_eq!(ids(&page), (1..=10).collect::<Vec<_>>());
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[test]
+    fn test_filters_combine() {
+        let query = TradeQuery {
+            counterparty: Some("CP-Even".into()),
+            direction: Some(Direction::Buy),
+            notional_min: Some(dec!(4000)),
+            notional_max: Some(dec!(8000)),
+            ..Default::default()
+        };
+        assert_eq!(ids(&query.page(blotter()).unwrap()), vec![4, 6, 8]);
+
+        let query = TradeQuery { requester: Some("bob".into()), ..Default::default() };
+        assert_eq!(ids(&query.page(blotter()).unwrap()), vec![1, 2, 3]);
+
+        let query = TradeQuery { state: Some(TradeState::Cancelled), ..Default::default() };
+        let page = query.page(blotter()).unwrap();
+        assert_eq!(ids(&page), vec![5, 10]);
+        assert!(page.trades.iter().all(|t| t.version == 1 && t.state == TradeState::Cancelled));
+    }
+
+    #[test]
+    fn test_created_range_is_half_open() {
+        let start = Utc.with_ymd_and_hms(2025, 4, 1, 9, 0, 0).unwrap();
+        let query = TradeQuery {
+            created_from: Some(start + Duration::hours(2)),
+            created_to: Some(start + Duration::hours(5)),
+            ..Default::default()
+        };
+        assert_eq!(ids(&query.page(blotter()).unwrap()), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_sort_descending_by_notional() {
+        let query = TradeQuery {
+            sort_by: SortBy::NotionalAmount,
+            order: SortOrder::Desc,
+            limit: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(ids(&query.page(blotter()).unwrap()), vec![10, 9, 8]);
+    }
+
+    #[test]
+    fn test_cursor_walks_every_page_once() {
+        let mut query =
+            TradeQuery { sort_by: SortBy::CreatedAt, order: SortOrder::Desc, limit: Some(4), ..Default::default() };
+        let mut seen = Vec::new();
+        let mut pages = 0;
+
+        loop {
+            let page = query.page(blotter()).unwrap();
+            seen.extend(ids(&page));
+            pages += 1;
+            match page.next_cursor {
+                Some(cursor) => query.cursor = Some(cursor),
+                None => break,
+            }
+        }
+
+        assert_eq!(pages, 3);
+        assert_eq!(seen, (1..=10).rev().collect::<Vec<_>>());
+
+        let first = TradeQuery { cursor: None, ..query }.page(blotter()).unwrap();
+        assert_eq!(first.next_cursor.as_deref(), Some("created_at/2025-04-01T16:00:00Z/7"));
+    }
+
+    #[test]
+    fn test_cursor_survives_new_trades() {
+        // A trade created between pages doesn't shift the next page
+        let query = TradeQuery { limit: Some(3), ..Default::default() };
+        let first = query.page(blotter()).unwrap();
+
+        let mut grown = blotter();
+        grown.insert(0, Trade::new(0, details("CP-Odd", dec!(1), Direction::Buy), "dave".into()));
+
+        let next = TradeQuery { cursor: first.next_cursor, ..query }.page(grown).unwrap();
+        assert_eq!(ids(&next), vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn test_invalid_queries_are_rejected() {
+        let bad = |query: TradeQuery| matches!(query.page(blotter()), Err(ValidationError::InvalidQuery(_)));
+
+        assert!(bad(TradeQuery { limit: Some(0), ..Default::default() }));
+        assert!(bad(TradeQuery { limit: Some(MAX_PAGE_SIZE + 1), ..Default::default() }));
+        assert!(bad(TradeQuery { notional_min: Some(dec!(10)), notional_max: Some(dec!(1)), ..Default::default() }));
+        assert!(bad(TradeQuery { cursor: Some("nonsense".into()), ..Default::default() }));
+
+        // A cursor from a query sorted another way
+        let cursor = "notional_amount/3000/3".to_string();
+        assert!(bad(TradeQuery { cursor: Some(cursor), sort_by: SortBy::CreatedAt, ..Default::default() }));
+    }
+}
@@ -19,6 +19,11 @@ const MAX_SEQUENCE: u16 = (1 << SEQUENCE_BITS) - 1;
 /// Alias for clarity
 pub type SnowflakeId = u64;
 
+/// Source of new trade IDs, the engine does not care how they are made as long as they are unique
+pub trait IdGenerator: Send + Sync {
+    fn generate(&self) -> SnowflakeId;
+}
+
 /// Internal generator state, protected by a mutex for thread safety
 #[derive(Debug)]
 struct State {
@@ -86,6 +91,12 @@ impl SnowflakeIdGenerator {
     }
 }
 
+impl IdGenerator for SnowflakeIdGenerator {
+    fn generate(&self) -> SnowflakeId {
+        SnowflakeIdGenerator::generate(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
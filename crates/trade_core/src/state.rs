@@ -1,103 +1,213 @@
+//! Trade workflow, as a table of `(action, from) -> to` transitions
+//!
+//! The built-in table holds the standard rules (see `WorkflowConfig::default`). A different
+//! table can be loaded from the `[workflow]` config section, or from a file of its own
+//! (`StateMachine::load`), e.g.
+//!
+//! ```toml
+//! final = ["Executed", "Cancelled"]
+//! transitions = [
+//!     { action = "Submit", from = ["Draft"], to = "PendingApproval" },
+//!     { action = "Approve", from = ["PendingApproval"], to = "Approved", guard = "not_requester" },
+//! ]
+//! ```
+//!
+//! Tables are validated when loaded, so a bad workflow is caught at startup rather than
+//! half-way through a trade's life:
+//! - each `(action, from)` pair appears at most once
+//! - final states are terminal, nothing leads out of them
+//! - every state used by the table can be reached from `Draft`, where all trades start
+
 use crate::errors::ValidationError;
 use crate::model::{TradeAction, TradeState};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 use TradeAction::*;
 use TradeState::*;
 
-/// Simple state machine for trade transitions
-/// TODO - Could potentially move the allowed transitions to a config file if it's likely to change
-///  Probably best practice so it's not hard coded. Anyway it's here for now
-#[derive(Default)]
-pub struct StateMachine;
-
-impl StateMachine {
-    /// Checks if a transition is valid, returning a bool
-    /// Do not confuse with next_state which actually returns the next state for a given action
-    pub fn can_transition(&self, from: TradeState, to: TradeState) -> bool {
-        match (from, to) {
-            // New trade creation or "draft" = no state change
-            (Draft, Draft) => true,
-
-            // Submitting a draft goes to pending approval, cancelling it goes to cancelled
-            (Draft, PendingApproval | Cancelled) => true,
-
-            // Updating a draft can go to needs re-approval
-            (Draft, NeedsReapproval) => true,
-
-            // UPDATING a pending-approval trade = no stage change
-            (PendingApproval, PendingApproval) => true,
-
-            // Pending approval state can go to Approved, Cancelled or NeedsApproval
-            (PendingApproval, Approved | Cancelled | NeedsReapproval) => true,
-
-            // After update, original requester can re-approve or cancel it
-            (NeedsReapproval, Approved | Cancelled) => true,
+/// Named check attached to a transition, the engine runs it before the trade moves.
+/// On `Approve`, a quorum rule (see `approval`) replaces the guard while it applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Guard {
+    /// The original requester may not take this action (e.g. first approval)
+    NotRequester,
+    /// Only the original requester may take this action (e.g. re-approval)
+    RequesterOnly,
+}
 
-            // Approved trade can be sent to counterparty, cancelled, or re-approved if updated
-            (Approved, SentToCounterparty | Cancelled | NeedsReapproval) => true,
+/// One row of the workflow table, as written in config.
+/// `from` is a list so that rules sharing an action and target stay on one line.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TransitionRule {
+    pub action: TradeAction,
+    pub from: Vec<TradeState>,
+    pub to: TradeState,
+    #[serde(default)]
+    pub guard: Option<Guard>,
+}
 
-            // After sending, trade can be executed or cancelled
-            (SentToCounterparty, Executed | Cancelled) => true,
+/// The `[workflow]` config section, or the contents of a standalone workflow file
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkflowConfig {
+    #[serde(rename = "final")]
+    pub finals: Vec<TradeState>,
+    pub transitions: Vec<TransitionRule>,
+}
 
-            // Anything else is not supported
-            _ => false,
+impl Default for WorkflowConfig {
+    /// The standard trade workflow
+    fn default() -> Self {
+        let rule = |action, from: &[TradeState], to, guard| TransitionRule { action, from: from.to_vec(), to, guard };
+
+        Self {
+            finals: vec![Executed, Cancelled, Expired],
+            transitions: vec![
+                // User submits draft -> moves to "pending approval"
+                rule(Submit, &[Draft], PendingApproval, None),
+                // Trade approved by someone other than the requester -> "Approved"
+                rule(Approve, &[PendingApproval], Approved, Some(Guard::NotRequester)),
+                // Only the original REQUESTER re-approves an updated trade -> "Approved"
+                rule(Approve, &[NeedsReapproval], Approved, Some(Guard::RequesterOnly)),
+                // Trade gets updated -> needs re-approval
+                // We allow updates from Draft, PendingApproval, Approved and NeedsReapproval
+                // Debatable whether update is allowed from "cancelled"
+                rule(Update, &[Draft, PendingApproval, Approved, NeedsReapproval], NeedsReapproval, None),
+                // Approved trade sent to counterparty -> "SentToCounterparty"
+                rule(SendToExecute, &[Approved], SentToCounterparty, None),
+                // Trade executed (confirmation) -> book it
+                rule(Book, &[SentToCounterparty], Executed, None),
+                // Cancel allowed from any active state,
+                // including SentToCounterparty (on a best-effort basis) TODO - To be discussed
+                rule(Cancel, &[Draft, PendingApproval, NeedsReapproval, Approved, SentToCounterparty], Cancelled, None),
+                // Trade left too long before it went out (see the service's expiry job) -> "Expired"
+                rule(Expire, &[Draft, PendingApproval, NeedsReapproval, Approved], Expired, None),
+            ],
         }
     }
+}
 
-    /// Provides the next state for a given action and current state
-    /// Responds with an error if the requested action is not valid for the current state
-    pub fn next_state(&self, action: TradeAction, from_state: TradeState) -> Result<TradeState, ValidationError> {
-        match (action, from_state) {
-            // ----------------- Happy paths -------------------------------------------------------
+/// No state change, allowed whatever the table says:
+/// new trade creation (a "draft"), and a pending-approval trade that stays pending (e.g. a partial approval)
+const STAY_PUT: [(TradeState, TradeState); 2] = [(Draft, Draft), (PendingApproval, PendingApproval)];
 
-            // User submits draft -> moves to "pending approval"
-            (Submit, Draft) => Ok(PendingApproval),
+/// Where a `(action, from)` pair leads, and what has to hold for it to be taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub to: TradeState,
+    pub guard: Option<Guard>,
+}
 
-            // Trade approved -> Moves to "Approved"
-            (Approve, PendingApproval) => Ok(Approved),
-            // Original REQUESTER re-approves trade -> moves to "approved"
-            (Approve, NeedsReapproval) => Ok(Approved),
+/// State machine for trade transitions, driven by a validated workflow table
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateMachine {
+    finals: HashSet<TradeState>,
+    table: HashMap<(TradeAction, TradeState), Transition>,
+}
 
-            // Trade gets updated -> needs re-approval
-            // We allow updates from Draft, PendingApproval, and POSSIBLY also NeedsReapproval
-            // Debatable whether update is allowed from "cancelled"
-            (Update, Draft | PendingApproval | Approved | NeedsReapproval) => Ok(NeedsReapproval),
+impl Default for StateMachine {
+    fn default() -> Self {
+        Self::from_config(WorkflowConfig::default()).expect("Built-in workflow is valid")
+    }
+}
 
-            // Approved trade sent to counterparty -> "SentToCounterparty"
-            (SendToExecute, Approved) => Ok(SentToCounterparty),
+impl StateMachine {
+    /// Builds the state machine from a workflow table, rejecting tables that don't hold together
+    pub fn from_config(config: WorkflowConfig) -> Result<Self, ValidationError> {
+        let finals: HashSet<TradeState> = config.finals.iter().copied().collect();
+        let mut table = HashMap::new();
+
+        for rule in &config.transitions {
+            for &from in &rule.from {
+                if finals.contains(&from) {
+                    return Err(workflow_error(format!("{from} is final but has a {:?} transition", rule.action)));
+                }
+                let transition = Transition { to: rule.to, guard: rule.guard };
+                if table.insert((rule.action, from), transition).is_some() {
+                    return Err(workflow_error(format!("{:?} from {from} is defined more than once", rule.action)));
+                }
+            }
+        }
 
-            // Trade executed (confirmation) -> book it
-            (Book, SentToCounterparty) => Ok(Executed),
+        let machine = Self { finals, table };
+        machine.check_reachable(&config)?;
+        Ok(machine)
+    }
 
-            // Cancel allowed from active state,
-            // - possibly including SentToCounterparty (on a best-effort basis)
-            //   but definitely not including Executed or Cancelled
-            (Cancel, Draft | PendingApproval | NeedsReapproval | Approved) => Ok(Cancelled),
-            (Cancel, SentToCounterparty) => Ok(Cancelled), // TODO - To be discussed
+    /// Parses and validates a workflow table written in TOML
+    pub fn from_toml(text: &str) -> Result<Self, ValidationError> {
+        let config: WorkflowConfig = toml::from_str(text).map_err(|e| workflow_error(e.message().to_string()))?;
+        Self::from_config(config)
+    }
 
-            // --------------- Unhappy paths / No-Ops ----------------------------------------------
+    /// Loads a workflow table from its own TOML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ValidationError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| workflow_error(format!("{}: {e}", path.display())))?;
+        Self::from_toml(&text)
+    }
 
-            // Cannot update when sent to counterparty, executed - possibly or cancelled
-            // Could be covered by fallback anyway, but just in case we need a different error
-            (Update, SentToCounterparty | Executed | Cancelled) => {
-                Err(ValidationError::InvalidTransition(from_state, from_state))
+    /// Every state the table mentions must be reachable from Draft, where trades are created
+    fn check_reachable(&self, config: &WorkflowConfig) -> Result<(), ValidationError> {
+        let mut reached = HashSet::from([Draft]);
+        let mut queue = vec![Draft];
+        while let Some(state) = queue.pop() {
+            for (&(_, from), transition) in &self.table {
+                if from == state && reached.insert(transition.to) {
+                    queue.push(transition.to);
+                }
             }
+        }
 
-            // Can't submit (again) from PendingApproval or NeedsReapproval state
-            (Submit, PendingApproval | NeedsReapproval) => Err(ValidationError::InvalidAction(Submit, from_state)),
+        let used = config.transitions.iter().flat_map(|r| r.from.iter().chain(std::iter::once(&r.to)));
+        match used.chain(&config.finals).find(|state| !reached.contains(state)) {
+            Some(state) => Err(workflow_error(format!("{state} can never be reached from {Draft}"))),
+            None => Ok(()),
+        }
+    }
 
-            // Can't cancel a cancelled trade
-            (Cancel, Cancelled) => Err(ValidationError::AlreadyFinal(from_state)),
+    /// True if no action leads out of the state
+    pub fn is_final(&self, state: TradeState) -> bool {
+        self.finals.contains(&state)
+    }
 
-            // No action allowed from "final" state
-            (_, Executed | Cancelled) => Err(ValidationError::AlreadyFinal(from_state)),
+    /// Checks if a transition is valid, returning a bool
+    /// Do not confuse with next_state which actually returns the next state for a given action
+    pub fn can_transition(&self, from: TradeState, to: TradeState) -> bool {
+        STAY_PUT.contains(&(from, to))
+            || self.table.iter().any(|(&(_, state), transition)| state == from && transition.to == to)
+    }
 
-            // Catch-all for anything not explicitly supported above
-            _ => Err(ValidationError::InvalidAction(action, from_state)),
+    /// Provides the next state for a given action and current state
+    /// Responds with an error if the requested action is not valid for the current state
+    pub fn next_state(&self, action: TradeAction, from_state: TradeState) -> Result<TradeState, ValidationError> {
+        self.transition(action, from_state).map(|transition| transition.to)
+    }
+
+    /// Same as `next_state`, along with the guard the engine has to check before moving the trade
+    pub fn transition(&self, action: TradeAction, from_state: TradeState) -> Result<Transition, ValidationError> {
+        if let Some(transition) = self.table.get(&(action, from_state)) {
+            return Ok(*transition);
         }
+
+        // Not in the table, report it as precisely as we can
+        Err(match action {
+            // Updating leaves the trade where it is, so a refused update is a refused transition
+            Update => ValidationError::InvalidTransition(from_state, from_state),
+            // No action allowed from "final" state
+            _ if self.is_final(from_state) => ValidationError::AlreadyFinal(from_state),
+            // Anything else is not supported
+            _ => ValidationError::InvalidAction(action, from_state),
+        })
     }
 }
 
+fn workflow_error(reason: String) -> ValidationError {
+    ValidationError::InvalidWorkflow(reason)
+}
+
 // = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
 // Unit tests for state machine
 // = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
@@ -137,6 +247,15 @@ mod tests {
         assert!(!sm().can_transition(Executed, Executed)); // Pending to final
     }
 
+    #[test]
+    fn test_can_transition_follows_the_table() {
+        // Unlike the old hard-coded rules: updating a trade that already needs re-approval is a table
+        // row (Update from NeedsReapproval), so the trade may stay where it is
+        assert!(sm().can_transition(NeedsReapproval, NeedsReapproval));
+        assert!(!sm().can_transition(Approved, Approved));
+        assert!(!sm().can_transition(NeedsReapproval, PendingApproval));
+    }
+
     // - - - - - - - - - - - - - - - - - - - - - - - -  - - - - - - - - - - - -  - - - - - - - - - -
     // HAPPY PATH — Valid transitions
     // - - - - - - - - - - - - - - - - - - - - - - - -  - - - - - - - - - - - -  - - - - - - - - - -
@@ -197,6 +316,13 @@ mod tests {
         assert_eq!(result.unwrap(), NeedsReapproval);
     }
 
+    #[test]
+    fn test_update_from_needs_reapproval() {
+        // NeedsReapproval → Update → NeedsReapproval
+        let result = sm().next_state(Update, NeedsReapproval);
+        assert_eq!(result.unwrap(), NeedsReapproval);
+    }
+
     #[test]
     fn test_cancel_from_needs_reapproval() {
         // NeedsReapproval → Cancel → Cancelled
@@ -211,6 +337,19 @@ mod tests {
         assert_eq!(result.unwrap(), Cancelled);
     }
 
+    #[test]
+    fn test_expire_until_sent() {
+        // Anything not yet sent may expire, Expired is final
+        for state in [Draft, PendingApproval, NeedsReapproval, Approved] {
+            assert_eq!(sm().next_state(Expire, state).unwrap(), Expired);
+        }
+        assert_eq!(
+            sm().next_state(Expire, SentToCounterparty).unwrap_err(),
+            ValidationError::InvalidAction(Expire, SentToCounterparty)
+        );
+        assert_eq!(sm().next_state(Approve, Expired).unwrap_err(), ValidationError::AlreadyFinal(Expired));
+    }
+
     // - - - - - - - - - - - - - - - - - - - - - - - -  - - - - - - - - - - - -  - - - - - - - - - -
     // SAD PATH TESTS — Invalid / disallowed transitions
     // - - - - - - - - - - - - - - - - - - - - - - - -  - - - - - - - - - - - -  - - - - - - - - - -
@@ -249,4 +388,104 @@ mod tests {
         let result = sm().next_state(Submit, NeedsReapproval);
         assert!(matches!(result, Err(ValidationError::InvalidAction(Submit, NeedsReapproval))));
     }
+
+    // - - - - - - - - - - - - - - - - - - - - - - - -  - - - - - - - - - - - -  - - - - - - - - - -
+    // WORKFLOW TABLES — Guards, loading and validation
+    // - - - - - - - - - - - - - - - - - - - - - - - -  - - - - - - - - - - - -  - - - - - - - - - -
+
+    fn workflow_reason(result: Result<StateMachine, ValidationError>) -> String {
+        match result {
+            Err(ValidationError::InvalidWorkflow(reason)) => reason,
+            other => panic!("Expected an invalid workflow, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_default_approval_guards() {
+        let first = sm().transition(Approve, PendingApproval).unwrap();
+        assert_eq!(first, Transition { to: Approved, guard: Some(Guard::NotRequester) });
+
+        let again = sm().transition(Approve, NeedsReapproval).unwrap();
+        assert_eq!(again, Transition { to: Approved, guard: Some(Guard::RequesterOnly) });
+
+        assert_eq!(sm().transition(Submit, Draft).unwrap().guard, None);
+    }
+
+    #[test]
+    fn test_shipped_workflow_file_matches_default() {
+        // config/workflow.toml documents the built-in rules, keep the two in step
+        let shipped = StateMachine::from_toml(include_str!("../../../config/workflow.toml")).unwrap();
+        assert_eq!(shipped, sm());
+    }
+
+    #[test]
+    fn test_custom_workflow_from_toml() {
+        // No approval step: drafts go straight to the counterparty
+        let machine = StateMachine::from_toml(
+            r#"
+            final = ["Executed", "Cancelled"]
+            transitions = [
+                { action = "SendToExecute", from = ["Draft"], to = "SentToCounterparty", guard = "requester_only" },
+                { action = "Book", from = ["SentToCounterparty"], to = "Executed" },
+                { action = "Cancel", from = ["Draft"], to = "Cancelled" },
+            ]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(machine.next_state(SendToExecute, Draft).unwrap(), SentToCounterparty);
+        assert_eq!(machine.transition(SendToExecute, Draft).unwrap().guard, Some(Guard::RequesterOnly));
+        assert_eq!(machine.next_state(Submit, Draft).unwrap_err(), ValidationError::InvalidAction(Submit, Draft));
+        assert_eq!(machine.next_state(Cancel, Executed).unwrap_err(), ValidationError::AlreadyFinal(Executed));
+        assert!(machine.can_transition(SentToCounterparty, Executed));
+        assert!(!machine.can_transition(SentToCounterparty, Cancelled));
+    }
+
+    #[test]
+    fn test_final_state_must_be_terminal() {
+        let mut config = WorkflowConfig::default();
+        config.transitions.push(TransitionRule {
+            action: Update,
+            from: vec![Executed],
+            to: NeedsReapproval,
+            guard: None,
+        });
+
+        let reason = workflow_reason(StateMachine::from_config(config));
+        assert!(reason.contains("Executed is final"), "{reason}");
+    }
+
+    #[test]
+    fn test_unreachable_state_is_rejected() {
+        // Nothing leads to NeedsReapproval once updates are gone, but it can still be approved from
+        let mut config = WorkflowConfig::default();
+        config.transitions.retain(|rule| rule.action != Update);
+
+        let reason = workflow_reason(StateMachine::from_config(config));
+        assert!(reason.contains("NeedsReapproval can never be reached"), "{reason}");
+    }
+
+    #[test]
+    fn test_duplicate_transition_is_rejected() {
+        let mut config = WorkflowConfig::default();
+        config.transitions.push(TransitionRule { action: Submit, from: vec![Draft], to: Approved, guard: None });
+
+        let reason = workflow_reason(StateMachine::from_config(config));
+        assert!(reason.contains("Submit from Draft"), "{reason}");
+    }
+
+    #[test]
+    fn test_bad_toml_is_rejected() {
+        // Unknown guard names are caught when parsing
+        let result = StateMachine::from_toml(
+            r#"
+            final = []
+            transitions = [{ action = "Submit", from = ["Draft"], to = "PendingApproval", guard = "anyone" }]
+            "#,
+        );
+        assert!(workflow_reason(result).contains("anyone"));
+
+        let missing = StateMachine::load("does/not/exist.toml");
+        assert!(workflow_reason(missing).contains("does/not/exist.toml"));
+    }
 }
@@ -0,0 +1,273 @@
+//! The contract every `TradeStore` keeps, as checks against `&dyn TradeStore`.
+//!
+//! Each backend runs all of them with `store_contract_tests!`, every check on a fresh empty store,
+//! next to its own tests (indexes, persistence, recovery ...). The fixtures are shared too.
+
+use crate::errors::ValidationError;
+use crate::model::{Currency, Direction, Product, Trade, TradeAction, TradeDetails, TradeId, TradeState};
+use crate::query::TradeQuery;
+use crate::store::TradeStore;
+use chrono::{TimeZone, Utc};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+pub(crate) fn trade_details(quantity: f32) -> TradeDetails {
+    TradeDetails {
+        trading_entity: "BigBank".to_string(),
+        counterparty: "ClientCo".to_string(),
+        direction: Direction::Buy,
+        notional_currency: Currency::USD,
+        notional_amount: Decimal::from_f32(quantity).expect("invalid float"),
+        underlying: vec![Currency::EUR],
+        trade_date: Utc.with_ymd_and_hms(2025, 4, 10, 0, 0, 0).unwrap(),
+        value_date: Utc.with_ymd_and_hms(2025, 4, 12, 0, 0, 0).unwrap(),
+        delivery_date: Utc.with_ymd_and_hms(2025, 4, 15, 0, 0, 0).unwrap(),
+        strike: Some(dec!(1.25)),
+        product: Product::Forward,
+    }
+}
+
+pub(crate) fn create_trade(id: TradeId, user_id: &str) -> Trade {
+    Trade::new(id, trade_details(150.0), user_id.to_string())
+}
+
+/// One `#[test]` per contract check, each against the store `$new_store(<check name>)` returns.
+/// The store may come in a guard (e.g. one that removes its files), anything that derefs to it.
+macro_rules! store_contract_tests {
+    ($new_store:expr) => {
+        $crate::store::contract::store_contract_tests!(@checks $new_store;
+            test_push_and_has_trade,
+            test_push_duplicate_id_fails,
+            test_get_trade_success,
+            test_get_trade_not_found,
+            test_update_trade_success,
+            test_update_trade_not_found,
+            test_keys_list,
+            test_trade_details_persistence_on_update,
+            test_full_round_trip_is_lossless,
+            test_modify_in_place,
+            test_modify_failure_leaves_trade_untouched,
+            test_modify_trade_not_found,
+            test_commit_batch_stores_new_and_changed_trades,
+            test_readers_never_see_half_a_batch,
+            test_candidates_without_filters_are_every_trade,
+        );
+    };
+    (@checks $new_store:expr; $($check:ident),+ $(,)?) => {
+        $(
+            #[test]
+            fn $check() {
+                let store = ($new_store)(stringify!($check));
+                $crate::store::contract::$check(&*store);
+            }
+        )+
+    };
+}
+pub(crate) use store_contract_tests;
+
+pub(crate) fn test_push_and_has_trade(store: &dyn TradeStore) {
+    let trade = create_trade(1, "alice");
+
+    assert!(!store.has(trade.id).unwrap());
+    store.push(trade.clone()).unwrap();
+    assert!(store.has(trade.id).unwrap());
+}
+
+pub(crate) fn test_push_duplicate_id_fails(store: &dyn TradeStore) {
+    let mut trade = create_trade(5, "alice");
+    store.push(trade.clone()).unwrap();
+
+    // Refused, and the stored trade is left as it was
+    trade.add_snapshot("bob", TradeState::PendingApproval, trade_details(151.0));
+    assert!(store.push(trade).unwrap_err().contains("already exists"));
+    assert_eq!(store.get(5).unwrap().unwrap().history.len(), 1);
+}
+
+pub(crate) fn test_get_trade_success(store: &dyn TradeStore) {
+    let trade = create_trade(2, "bob");
+
+    store.push(trade.clone()).unwrap();
+    let fetched = store.get(trade.id).unwrap();
+    assert!(fetched.is_some());
+    assert_eq!(fetched.unwrap().id, trade.id);
+}
+
+pub(crate) fn test_get_trade_not_found(store: &dyn TradeStore) {
+    assert!(store.get(42).unwrap().is_none());
+}
+
+pub(crate) fn test_update_trade_success(store: &dyn TradeStore) {
+    let mut trade = create_trade(3, "charlie");
+
+    store.push(trade.clone()).unwrap();
+
+    trade.add_snapshot("charlie", TradeState::PendingApproval, trade_details(160.0));
+    let result = store.update(trade.clone());
+
+    assert!(result.is_ok());
+
+    let fetched = store.get(trade.id).unwrap().unwrap();
+    assert_eq!(fetched.latest_details().unwrap().notional_amount, dec!(160.0));
+    assert_eq!(fetched.current_state(), TradeState::PendingApproval);
+}
+
+pub(crate) fn test_update_trade_not_found(store: &dyn TradeStore) {
+    let trade = create_trade(999, "ghost");
+
+    let result = store.update(trade);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), "Trade with ID 999 not found");
+}
+
+pub(crate) fn test_keys_list(store: &dyn TradeStore) {
+    let trade1 = create_trade(100, "trader1");
+    let trade2 = create_trade(200, "trader2");
+
+    store.push(trade1.clone()).unwrap();
+    store.push(trade2.clone()).unwrap();
+
+    let keys = store.keys().unwrap();
+    assert_eq!(keys.len(), 2);
+    assert!(keys.contains(&trade1.id));
+    assert!(keys.contains(&trade2.id));
+}
+
+pub(crate) fn test_trade_details_persistence_on_update(store: &dyn TradeStore) {
+    let mut trade = create_trade(42, "alice");
+
+    store.push(trade.clone()).unwrap();
+
+    // Update with new details
+    let updated_details = TradeDetails {
+        trading_entity: "BigBank".to_string(),
+        counterparty: "AnotherCo".to_string(),
+        direction: Direction::Sell,
+        notional_currency: Currency::EUR,
+        notional_amount: dec!(2_000_000),
+        underlying: vec![Currency::USD, Currency::JPY],
+        trade_date: Utc.with_ymd_and_hms(2025, 5, 1, 0, 0, 0).unwrap(),
+        value_date: Utc.with_ymd_and_hms(2025, 5, 3, 0, 0, 0).unwrap(),
+        delivery_date: Utc.with_ymd_and_hms(2025, 5, 10, 0, 0, 0).unwrap(),
+        strike: None,
+        product: Product::Forward,
+    };
+
+    trade.add_snapshot("bob", TradeState::PendingApproval, updated_details.clone());
+    store.update(trade.clone()).unwrap();
+
+    let fetched = store.get(trade.id).unwrap().unwrap();
+    let current_details = fetched.latest_details().unwrap();
+
+    assert_eq!(current_details, &updated_details);
+}
+
+pub(crate) fn test_full_round_trip_is_lossless(store: &dyn TradeStore) {
+    let mut trade = create_trade(u64::MAX - 1, "alice"); // beyond i64::MAX on purpose
+    trade.add_snapshot("bob", TradeState::PendingApproval, trade_details(151.5));
+    trade.add_snapshot("carol", TradeState::Approved, trade_details(151.5));
+
+    store.push(trade.clone()).unwrap();
+    let fetched = store.get(trade.id).unwrap().unwrap();
+
+    assert_eq!(fetched.id, trade.id);
+    assert_eq!(fetched.created_at, trade.created_at);
+    assert_eq!(fetched.history.len(), 3);
+    for (a, b) in fetched.history.iter().zip(trade.history.iter()) {
+        assert_eq!(a.snapshot_id, b.snapshot_id);
+        assert_eq!(a.user_id, b.user_id);
+        assert_eq!(a.timestamp, b.timestamp);
+        assert_eq!(a.from_state, b.from_state);
+        assert_eq!(a.to_state, b.to_state);
+        assert_eq!(a.details, b.details);
+    }
+}
+
+pub(crate) fn test_modify_in_place(store: &dyn TradeStore) {
+    store.push(create_trade(5, "alice")).unwrap();
+
+    store
+        .modify(5, &mut |trade| {
+            trade.add_snapshot("alice", TradeState::PendingApproval, trade_details(150.0));
+            Ok(())
+        })
+        .unwrap();
+
+    assert_eq!(store.get(5).unwrap().unwrap().current_state(), TradeState::PendingApproval);
+}
+
+pub(crate) fn test_modify_failure_leaves_trade_untouched(store: &dyn TradeStore) {
+    store.push(create_trade(6, "alice")).unwrap();
+
+    let result = store.modify(6, &mut |trade| {
+        trade.add_snapshot("alice", TradeState::PendingApproval, trade_details(150.0));
+        Err(ValidationError::Internal("changed my mind".into()).into())
+    });
+
+    assert!(result.is_err());
+    assert_eq!(store.get(6).unwrap().unwrap().history.len(), 1);
+
+    let result = store.modify(6, &mut |trade| {
+        trade.add_rejection("bob", TradeAction::Approve, "TAL21", "over limit", Utc::now());
+        Err(ValidationError::Internal("changed my mind".into()).into())
+    });
+    assert!(result.is_err());
+    assert!(store.get(6).unwrap().unwrap().rejections.is_empty());
+}
+
+pub(crate) fn test_modify_trade_not_found(store: &dyn TradeStore) {
+    let result = store.modify(404, &mut |_| Ok(()));
+    assert_eq!(result.unwrap_err().code(), "TNF01");
+}
+
+pub(crate) fn test_commit_batch_stores_new_and_changed_trades(store: &dyn TradeStore) {
+    let mut known = create_trade(11, "alice");
+    store.push(known.clone()).unwrap();
+
+    known.add_snapshot("bob", TradeState::PendingApproval, trade_details(160.0));
+    store.commit_batch(vec![known.clone(), create_trade(12, "carol")]).unwrap();
+
+    let mut keys = store.keys().unwrap();
+    keys.sort();
+    assert_eq!(keys, vec![11, 12]);
+    assert_eq!(store.get(11).unwrap().unwrap().history.len(), 2);
+    assert_eq!(store.get(11).unwrap().unwrap().current_state(), TradeState::PendingApproval);
+    assert_eq!(store.get(12).unwrap().unwrap().history.len(), 1);
+}
+
+pub(crate) fn test_readers_never_see_half_a_batch(store: &dyn TradeStore) {
+    const BATCHES: usize = 200;
+    let mut pair = vec![create_trade(21, "alice"), create_trade(22, "alice")];
+    store.commit_batch(pair.clone()).unwrap();
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            for _ in 0..BATCHES {
+                for trade in pair.iter_mut() {
+                    trade.add_snapshot("bob", TradeState::Draft, trade_details(150.0));
+                }
+                store.commit_batch(pair.clone()).unwrap();
+            }
+        });
+
+        // Both trades move in every batch, so they always have as many snapshots as each other
+        loop {
+            let trades = store.candidates(&TradeQuery::default()).unwrap();
+            let lengths: Vec<usize> = trades.iter().map(|t| t.history.len()).collect();
+            assert_eq!(lengths[0], lengths[1], "half a batch was visible");
+            if lengths[0] == BATCHES + 1 {
+                break;
+            }
+        }
+    });
+}
+
+pub(crate) fn test_candidates_without_filters_are_every_trade(store: &dyn TradeStore) {
+    for (id, user) in [(1, "alice"), (2, "bob"), (3, "carol")] {
+        store.push(create_trade(id, user)).unwrap();
+    }
+
+    let mut ids: Vec<TradeId> = store.candidates(&TradeQuery::default()).unwrap().iter().map(|t| t.id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![1, 2, 3]);
+}
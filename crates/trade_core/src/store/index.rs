@@ -0,0 +1,121 @@
+use crate::model::{Trade, TradeId, TradeState, UserId};
+use crate::query::TradeQuery;
+use dashmap::DashMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// One secondary index: value -> IDs of the trades currently holding that value
+struct Index<K: Eq + Hash>(DashMap<K, HashSet<TradeId>>);
+
+impl<K: Eq + Hash> Default for Index<K> {
+    fn default() -> Self {
+        Self(DashMap::new())
+    }
+}
+
+impl<K: Eq + Hash> Index<K> {
+    fn insert(&self, key: K, trade_id: TradeId) {
+        self.0.entry(key).or_default().insert(trade_id);
+    }
+
+    fn remove(&self, key: &K, trade_id: TradeId) {
+        // Drop emptied buckets, so values nobody holds any more don't pile up
+        self.0.remove_if_mut(key, |_, ids| {
+            ids.remove(&trade_id);
+            ids.is_empty()
+        });
+    }
+
+    fn ids(&self, key: &K) -> HashSet<TradeId> {
+        self.0.get(key).map(|ids| ids.clone()).unwrap_or_default()
+    }
+}
+
+/// The indexed values of one trade, as it stands now
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct IndexKeys {
+    state: TradeState,
+    counterparty: String,
+    trading_entity: String,
+    requester: UserId,
+}
+
+impl IndexKeys {
+    pub(super) fn of(trade: &Trade) -> Option<Self> {
+        let details = trade.latest_details()?;
+        Some(Self {
+            state: trade.current_state(),
+            counterparty: details.counterparty.clone(),
+            trading_entity: details.trading_entity.clone(),
+            requester: trade.get_requester(),
+        })
+    }
+}
+
+/// Secondary indexes for `InMemoryStore`, over the values `TradeQuery` filters on most.
+///
+/// Callers keep them in step with the trades, under the trade's lock: `add` a new trade,
+/// `replace` the keys of a changed one. Lookups only narrow the candidates down,
+/// the query still checks every filter against the trade itself.
+#[derive(Default)]
+pub(super) struct TradeIndexes {
+    state: Index<TradeState>,
+    counterparty: Index<String>,
+    trading_entity: Index<String>,
+    requester: Index<UserId>,
+}
+
+impl TradeIndexes {
+    pub(super) fn add(&self, trade_id: TradeId, keys: IndexKeys) {
+        self.state.insert(keys.state, trade_id);
+        self.counterparty.insert(keys.counterparty, trade_id);
+        self.trading_entity.insert(keys.trading_entity, trade_id);
+        self.requester.insert(keys.requester, trade_id);
+    }
+
+    pub(super) fn remove(&self, trade_id: TradeId, keys: &IndexKeys) {
+        self.state.remove(&keys.state, trade_id);
+        self.counterparty.remove(&keys.counterparty, trade_id);
+        self.trading_entity.remove(&keys.trading_entity, trade_id);
+        self.requester.remove(&keys.requester, trade_id);
+    }
+
+    /// Moves the trade from its old keys to its new ones, a no-op if nothing indexed changed
+    pub(super) fn replace(&self, trade_id: TradeId, old: Option<IndexKeys>, new: Option<IndexKeys>) {
+        if old == new {
+            return;
+        }
+        if let Some(old) = &old {
+            self.remove(trade_id, old);
+        }
+        if let Some(new) = new {
+            self.add(trade_id, new);
+        }
+    }
+
+    /// IDs of the trades that can match the query's indexed filters, smallest set first and
+    /// narrowed down by the rest. None if the query has no indexed filter, meaning scan everything.
+    pub(super) fn candidates(&self, query: &TradeQuery) -> Option<HashSet<TradeId>> {
+        let mut sets = Vec::new();
+        if let Some(state) = &query.state {
+            sets.push(self.state.ids(state));
+        }
+        if let Some(counterparty) = &query.counterparty {
+            sets.push(self.counterparty.ids(counterparty));
+        }
+        if let Some(trading_entity) = &query.trading_entity {
+            sets.push(self.trading_entity.ids(trading_entity));
+        }
+        if let Some(requester) = &query.requester {
+            sets.push(self.requester.ids(requester));
+        }
+
+        sets.sort_by_key(|ids| ids.len());
+        let mut sets = sets.into_iter();
+        let smallest = sets.next()?;
+        Some(sets.fold(smallest, |mut acc, ids| {
+            acc.retain(|trade_id| ids.contains(trade_id));
+            acc
+        }))
+    }
+}
@@ -0,0 +1,760 @@
+use crate::errors::ValidationError;
+use crate::model::{RejectedAction, Trade, TradeEventSnapshot, TradeId};
+use crate::query::TradeQuery;
+use crate::store::{TradeMutation, TradeStore};
+use app_core::{eout, AppError};
+use chrono::{DateTime, Utc};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use parking_lot::{Mutex, RwLock};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Record header: payload length (u32 LE) followed by the CRC32 of the payload (u32 LE)
+const HEADER_LEN: usize = 8;
+
+/// By default the journal is folded into the checkpoint after this many records
+pub const DEFAULT_COMPACT_EVERY: usize = 1_000;
+
+/// One entry of the journal. Every snapshot produced by `Trade::add_snapshot` becomes a record,
+/// the very first one travels with the trade envelope. So does every `Trade::add_rejection`.
+#[derive(Debug, Serialize, Deserialize)]
+enum JournalRecord {
+    Created {
+        trade_id: TradeId,
+        created_at: DateTime<Utc>,
+        snapshot: TradeEventSnapshot,
+    },
+    Snapshot {
+        trade_id: TradeId,
+        snapshot: TradeEventSnapshot,
+    },
+    Rejection {
+        trade_id: TradeId,
+        position: usize,
+        rejection: RejectedAction,
+    },
+    /// What an all-or-nothing batch did, in one frame so it is replayed whole or not at all
+    Batch(Vec<JournalRecord>),
+}
+
+/// Append-only, event-sourced store.
+///
+/// Layout on disk, next to each other:
+/// - `<path>`            - the journal, length-prefixed + checksummed records, only ever appended to
+/// - `<path>.checkpoint` - every trade as of the last compaction, same framing
+///
+/// On open the checkpoint is loaded and the journal replayed on top of it into the `DashMap`.
+/// A torn record at the end of the journal (crash mid-write) is detected by its length or
+/// checksum and truncated away. Replay skips what the checkpoint already holds, so a crash
+/// half way through compaction is harmless.
+///
+/// Writes to different trades only meet on the journal file itself. Compaction has to see
+/// a quiet store though, so writers hold `compaction` shared and compaction takes it exclusively.
+/// Batches are the same for readers: they hold `batch` shared, a batch commit takes it exclusively.
+pub struct JournalStore {
+    trades: DashMap<TradeId, Trade>,
+    journal_path: PathBuf,
+    checkpoint_path: PathBuf,
+    journal: Mutex<File>,
+    appended: AtomicUsize, // records written since the last compaction
+    compact_every: usize,  // 0 = never compact automatically
+    compaction: RwLock<()>,
+    batch: RwLock<()>,
+}
+
+impl JournalStore {
+    /// Opens (or creates) the journal at the given path and replays it
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let journal_path = path.as_ref().to_path_buf();
+        let checkpoint_path = checkpoint_path_for(&journal_path);
+        let trades = DashMap::new();
+
+        // 1. Everything folded in by the last compaction
+        if checkpoint_path.exists() {
+            let bytes = fs::read(&checkpoint_path).map_err(|e| format!("Failed to read checkpoint: {e}"))?;
+            let (payloads, valid_len) = decode_frames(&bytes);
+            if valid_len != bytes.len() {
+                // Checkpoints are written aside and renamed in, so this is real damage
+                return Err(format!("Checkpoint {} is corrupted at byte {}", checkpoint_path.display(), valid_len));
+            }
+            for payload in payloads {
+                let mut trade: Trade = decode_payload(payload)?;
+                trade.share_details();
+                trades.insert(trade.id, trade);
+            }
+        }
+
+        // 2. Replay the journal on top, cutting off a torn tail
+        let mut appended = 0;
+        if journal_path.exists() {
+            let bytes = fs::read(&journal_path).map_err(|e| format!("Failed to read journal: {e}"))?;
+            let (payloads, valid_len) = decode_frames(&bytes);
+            for payload in payloads {
+                apply_record(&trades, decode_payload(payload)?)?;
+                appended += 1;
+            }
+
+            // Replayed snapshots each brought their own copy of the details
+            for mut trade in trades.iter_mut() {
+                trade.share_details();
+            }
+
+            if valid_len < bytes.len() {
+                let file = OpenOptions::new().write(true).open(&journal_path).map_err(|e| e.to_string())?;
+                file.set_len(valid_len as u64).map_err(|e| format!("Failed to truncate torn journal tail: {e}"))?;
+                file.sync_all().map_err(|e| e.to_string())?;
+            }
+        }
+
+        let journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&journal_path)
+            .map_err(|e| format!("Failed to open journal {}: {e}", journal_path.display()))?;
+
+        Ok(Self {
+            trades,
+            journal_path,
+            checkpoint_path,
+            journal: Mutex::new(journal),
+            appended: AtomicUsize::new(appended),
+            compact_every: DEFAULT_COMPACT_EVERY,
+            compaction: RwLock::new(()),
+            batch: RwLock::new(()),
+        })
+    }
+
+    /// How many records to append before compacting automatically, 0 switches it off
+    pub fn with_compact_every(mut self, records: usize) -> Self {
+        self.compact_every = records;
+        self
+    }
+
+    /// Where the checkpoint for this journal lives
+    pub fn checkpoint_path(&self) -> &Path {
+        &self.checkpoint_path
+    }
+
+    /// Fold the journal into a fresh checkpoint and start the journal again from empty.
+    ///
+    /// The checkpoint is written to a temp file and renamed over the old one, so at every
+    /// point there is either the old or the new checkpoint on disk, never half of one.
+    pub fn compact(&self) -> Result<(), String> {
+        let _writers = self.compaction.write();
+        self.compact_quiet()
+    }
+
+    /// The actual compaction, caller holds `compaction` exclusively
+    fn compact_quiet(&self) -> Result<(), String> {
+        let journal = self.journal.lock();
+
+        let mut buffer = Vec::new();
+        for entry in self.trades.iter() {
+            encode_frame(&mut buffer, entry.value())?;
+        }
+
+        let tmp_path = self.checkpoint_path.with_extension("checkpoint.tmp");
+        let mut tmp = File::create(&tmp_path).map_err(|e| format!("Failed to create checkpoint: {e}"))?;
+        tmp.write_all(&buffer).map_err(|e| format!("Failed to write checkpoint: {e}"))?;
+        tmp.sync_all().map_err(|e| e.to_string())?;
+        fs::rename(&tmp_path, &self.checkpoint_path).map_err(|e| format!("Failed to install checkpoint: {e}"))?;
+        sync_parent_dir(&self.checkpoint_path);
+
+        // Only now is it safe to let go of the journal
+        journal.set_len(0).map_err(|e| format!("Failed to reset journal: {e}"))?;
+        journal.sync_all().map_err(|e| e.to_string())?;
+        self.appended.store(0, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Write-ahead: the records hit the disk before the in-memory map changes
+    fn append(&self, records: &[JournalRecord]) -> Result<(), String> {
+        let mut buffer = Vec::new();
+        for record in records {
+            encode_frame(&mut buffer, record)?;
+        }
+
+        let mut journal = self.journal.lock();
+        journal.write_all(&buffer).map_err(|e| format!("Failed to append to journal: {e}"))?;
+        journal.sync_data().map_err(|e| format!("Failed to sync journal: {e}"))?;
+        self.appended.fetch_add(records.len(), Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Periodic compaction, checked after each successful write (with no locks held).
+    /// The write is durable by then, so a failure is only logged, the next write tries again.
+    fn maybe_compact(&self) {
+        if !self.compaction_due() {
+            return;
+        }
+
+        // Somebody else may have compacted while we waited
+        let _writers = self.compaction.write();
+        if self.compaction_due() {
+            if let Err(e) = self.compact_quiet() {
+                eout!("Journal compaction failed, will retry on the next write: {e}");
+            }
+        }
+    }
+
+    fn compaction_due(&self) -> bool {
+        self.compact_every > 0 && self.appended.load(Ordering::SeqCst) >= self.compact_every
+    }
+
+    /// Path of the journal file
+    pub fn journal_path(&self) -> &Path {
+        &self.journal_path
+    }
+}
+
+impl TradeStore for JournalStore {
+    /// Journal the creation (plus any extra snapshots the trade already carries)
+    fn push(&self, trade: Trade) -> Result<TradeId, String> {
+        let writing = self.compaction.read();
+        let Entry::Vacant(slot) = self.trades.entry(trade.id) else {
+            return Err(format!("Trade with ID {:?} already exists", trade.id));
+        };
+
+        self.append(&created_records(&trade)?)?;
+        let trade_id = trade.id;
+        slot.insert(trade);
+        drop(writing);
+        self.maybe_compact();
+
+        Ok(trade_id)
+    }
+
+    /// Get a trade by ID
+    fn get(&self, trade_id: TradeId) -> Result<Option<Trade>, String> {
+        let _reading = self.batch.read();
+        Ok(self.trades.get(&trade_id).map(|entry| entry.clone()))
+    }
+
+    /// Check if the trade exists in the store
+    fn has(&self, trade_id: TradeId) -> Result<bool, String> {
+        let _reading = self.batch.read();
+        Ok(self.trades.contains_key(&trade_id))
+    }
+
+    /// Only the snapshots (and rejections) added since the stored version are journaled
+    fn update(&self, trade: Trade) -> Result<(), String> {
+        let writing = self.compaction.read();
+        let Some(mut existing) = self.trades.get_mut(&trade.id) else {
+            return Err(format!("Trade with ID {:?} not found", trade.id));
+        };
+
+        self.append(&new_records(&trade, existing.history.len(), existing.rejections.len()))?;
+        *existing = trade;
+        drop(existing);
+        drop(writing);
+        self.maybe_compact();
+        Ok(())
+    }
+
+    /// Get a list of all trade IDs in the store
+    fn keys(&self) -> Result<Vec<TradeId>, String> {
+        let _reading = self.batch.read();
+        Ok(self.trades.iter().map(|entry| *entry.key()).collect())
+    }
+
+    /// Acts on the trade in the map, under its entry lock, then journals what it added.
+    /// Should the journal write fail, the new snapshots are dropped from memory again.
+    fn modify(&self, trade_id: TradeId, action: &mut TradeMutation) -> Result<(), AppError> {
+        let writing = self.compaction.read();
+        let mut trade = self.trades.get_mut(&trade_id).ok_or(ValidationError::TradeNotFound(trade_id))?;
+
+        let (stored, rejected) = (trade.history.len(), trade.rejections.len());
+        let rollback = |trade: &mut Trade| {
+            trade.history.truncate(stored);
+            trade.rejections.truncate(rejected);
+        };
+        action(&mut trade).inspect_err(|_| rollback(&mut trade))?;
+
+        if let Err(e) = self.append(&new_records(&trade, stored, rejected)) {
+            rollback(&mut trade);
+            return Err(ValidationError::Internal(e).into());
+        }
+
+        drop(trade);
+        drop(writing);
+        self.maybe_compact();
+        Ok(())
+    }
+
+    /// Journals the whole batch as a single record before touching the map
+    fn commit_batch(&self, trades: Vec<Trade>) -> Result<(), String> {
+        let writing = self.compaction.read();
+
+        let mut records = Vec::new();
+        for trade in &trades {
+            match self.trades.get(&trade.id) {
+                Some(existing) => records.extend(new_records(trade, existing.history.len(), existing.rejections.len())),
+                None => records.extend(created_records(trade)?),
+            }
+        }
+        self.append(&[JournalRecord::Batch(records)])?;
+
+        let batch = self.batch.write();
+        for trade in trades {
+            self.trades.insert(trade.id, trade);
+        }
+        drop(batch);
+        drop(writing);
+        self.maybe_compact();
+        Ok(())
+    }
+
+    /// Every trade, read in one go so no batch lands half way through
+    fn candidates(&self, _query: &TradeQuery) -> Result<Vec<Trade>, String> {
+        let _reading = self.batch.read();
+        Ok(self.trades.iter().map(|entry| entry.value().clone()).collect())
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Framing and replay helpers
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// The records of a brand new trade: its creation, then anything it already carries
+fn created_records(trade: &Trade) -> Result<Vec<JournalRecord>, String> {
+    let first = trade.history.first().ok_or_else(|| format!("Trade with ID {:?} has no history", trade.id))?;
+
+    let mut records =
+        vec![JournalRecord::Created { trade_id: trade.id, created_at: trade.created_at, snapshot: first.clone() }];
+    records.extend(new_records(trade, 1, 0));
+    Ok(records)
+}
+
+/// A record for every snapshot past the first `stored` ones, and every rejection past the first `rejected`
+fn new_records(trade: &Trade, stored: usize, rejected: usize) -> Vec<JournalRecord> {
+    let snapshots =
+        trade.history.iter().skip(stored).map(|s| JournalRecord::Snapshot { trade_id: trade.id, snapshot: s.clone() });
+    let rejections = trade.rejections.iter().enumerate().skip(rejected).map(|(position, r)| JournalRecord::Rejection {
+        trade_id: trade.id,
+        position,
+        rejection: r.clone(),
+    });
+    snapshots.chain(rejections).collect()
+}
+
+fn checkpoint_path_for(journal_path: &Path) -> PathBuf {
+    let mut name = journal_path.as_os_str().to_os_string();
+    name.push(".checkpoint");
+    PathBuf::from(name)
+}
+
+/// [len: u32 LE][crc32: u32 LE][payload: len bytes of JSON]
+fn encode_frame<T: Serialize>(buffer: &mut Vec<u8>, value: &T) -> Result<(), String> {
+    let payload = serde_json::to_vec(value).map_err(|e| format!("Failed to encode journal record: {e}"))?;
+    let len = u32::try_from(payload.len()).map_err(|_| "Journal record too large".to_string())?;
+
+    buffer.extend_from_slice(&len.to_le_bytes());
+    buffer.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+    buffer.extend_from_slice(&payload);
+    Ok(())
+}
+
+/// Splits a file into record payloads. Stops at the first record that is incomplete or fails
+/// its checksum, and reports how many bytes were good - anything after that is a torn tail.
+fn decode_frames(bytes: &[u8]) -> (Vec<&[u8]>, usize) {
+    let mut payloads = Vec::new();
+    let mut offset = 0;
+
+    while bytes.len() - offset >= HEADER_LEN {
+        let len = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+        let crc = u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap());
+
+        let start = offset + HEADER_LEN;
+        let Some(payload) = bytes.get(start..start + len) else {
+            break; // length runs past the end of the file
+        };
+        if crc32fast::hash(payload) != crc {
+            break;
+        }
+
+        payloads.push(payload);
+        offset = start + len;
+    }
+
+    (payloads, offset)
+}
+
+fn decode_payload<T: DeserializeOwned>(payload: &[u8]) -> Result<T, String> {
+    // The checksum matched, so a parse failure is a format problem rather than a torn write
+    serde_json::from_slice(payload).map_err(|e| format!("Unreadable journal record: {e}"))
+}
+
+/// Replays one record into the map. Anything the checkpoint already covers is skipped.
+fn apply_record(trades: &DashMap<TradeId, Trade>, record: JournalRecord) -> Result<(), String> {
+    match record {
+        JournalRecord::Created { trade_id, created_at, snapshot } => {
+            trades.entry(trade_id).or_insert_with(|| Trade {
+                id: trade_id,
+                created_at,
+                history: vec![snapshot],
+                rejections: Vec::new(),
+            });
+        }
+        JournalRecord::Snapshot { trade_id, snapshot } => {
+            let mut trade =
+                trades.get_mut(&trade_id).ok_or_else(|| format!("Journal snapshot for unknown trade {trade_id}"))?;
+
+            if snapshot.snapshot_id == trade.history.len() {
+                trade.history.push(snapshot);
+            } else if snapshot.snapshot_id > trade.history.len() {
+                return Err(format!("Journal is missing snapshots for trade {trade_id}"));
+            }
+        }
+        JournalRecord::Rejection { trade_id, position, rejection } => {
+            let mut trade =
+                trades.get_mut(&trade_id).ok_or_else(|| format!("Journal rejection for unknown trade {trade_id}"))?;
+
+            if position == trade.rejections.len() {
+                trade.rejections.push(rejection);
+            } else if position > trade.rejections.len() {
+                return Err(format!("Journal is missing rejections for trade {trade_id}"));
+            }
+        }
+        JournalRecord::Batch(records) => {
+            for record in records {
+                apply_record(trades, record)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Make the rename itself durable. Best effort, not every platform lets us open a directory.
+fn sync_parent_dir(path: &Path) {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for journal.rs - the InMemoryStore suite, plus replay and recovery
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{TradeAction, TradeState};
+    use crate::store::contract::{create_trade, store_contract_tests, trade_details};
+    use std::ops::Deref;
+    use std::sync::Arc;
+
+    /// Unique journal per test, removed (with its checkpoint) when dropped
+    struct TempJournal(PathBuf);
+
+    impl TempJournal {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("validus_{}_{}.journal", name, std::process::id()));
+            let journal = Self(path);
+            journal.clean();
+            journal
+        }
+
+        fn open(&self) -> JournalStore {
+            JournalStore::open(&self.0).expect("open journal")
+        }
+
+        fn clean(&self) {
+            let _ = fs::remove_file(&self.0);
+            let _ = fs::remove_file(checkpoint_path_for(&self.0));
+        }
+    }
+
+    impl Drop for TempJournal {
+        fn drop(&mut self) {
+            self.clean();
+        }
+    }
+
+    /// A store on its own journal, closed before the files go
+    struct OpenJournal {
+        store: JournalStore,
+        _tmp: TempJournal,
+    }
+
+    impl Deref for OpenJournal {
+        type Target = JournalStore;
+
+        fn deref(&self) -> &JournalStore {
+            &self.store
+        }
+    }
+
+    store_contract_tests!(|name: &str| {
+        let tmp = TempJournal::new(&format!("contract_{name}"));
+        OpenJournal { store: tmp.open(), _tmp: tmp }
+    });
+
+    #[test]
+    fn test_modify_is_journaled() {
+        let tmp = TempJournal::new("modify");
+        {
+            let store = tmp.open();
+            store.push(create_trade(4, "alice")).unwrap();
+            store
+                .modify(4, &mut |trade| {
+                    trade.add_snapshot("bob", TradeState::PendingApproval, trade_details(150.0));
+                    Ok(())
+                })
+                .unwrap();
+
+            // A failing action leaves nothing behind, in memory or on disk
+            let failed = store.modify(4, &mut |trade| {
+                trade.add_snapshot("bob", TradeState::Approved, trade_details(150.0));
+                Err(ValidationError::Internal("nope".into()).into())
+            });
+            assert!(failed.is_err());
+            assert_eq!(store.get(4).unwrap().unwrap().history.len(), 2);
+        }
+
+        let fetched = tmp.open().get(4).unwrap().unwrap();
+        assert_eq!(fetched.history.len(), 2);
+        assert_eq!(fetched.current_state(), TradeState::PendingApproval);
+    }
+
+    #[test]
+    fn test_replay_rebuilds_trades() {
+        let tmp = TempJournal::new("replay");
+        let mut trade = create_trade(7, "alice");
+
+        {
+            let store = tmp.open();
+            store.push(trade.clone()).unwrap();
+            trade.add_snapshot("bob", TradeState::PendingApproval, trade_details(175.0));
+            store.update(trade.clone()).unwrap();
+            store.push(create_trade(8, "carol")).unwrap();
+        }
+
+        let store = tmp.open();
+        let mut keys = store.keys().unwrap();
+        keys.sort();
+        assert_eq!(keys, vec![7, 8]);
+
+        let fetched = store.get(7).unwrap().unwrap();
+        assert_eq!(fetched.created_at, trade.created_at);
+        assert_eq!(fetched.history.len(), 2);
+        assert_eq!(fetched.current_state(), TradeState::PendingApproval);
+        assert_eq!(fetched.latest_details().unwrap(), trade.latest_details().unwrap());
+    }
+
+    #[test]
+    fn test_commit_batch_is_replayed_whole() {
+        let tmp = TempJournal::new("batch");
+        let mut known = create_trade(9, "alice");
+        {
+            let store = tmp.open();
+            store.push(known.clone()).unwrap();
+
+            known.add_snapshot("bob", TradeState::PendingApproval, trade_details(160.0));
+            store.commit_batch(vec![known.clone(), create_trade(10, "carol")]).unwrap();
+        }
+
+        let store = tmp.open();
+        let mut keys = store.keys().unwrap();
+        keys.sort();
+        assert_eq!(keys, vec![9, 10]);
+        assert_eq!(store.get(9).unwrap().unwrap().current_state(), TradeState::PendingApproval);
+        assert_eq!(store.get(10).unwrap().unwrap().history.len(), 1);
+    }
+
+    #[test]
+    fn test_reload_shares_unchanged_details() {
+        let tmp = TempJournal::new("share");
+        {
+            let store = tmp.open();
+            store.push(create_trade(9, "alice")).unwrap();
+            store
+                .modify(9, &mut |trade| {
+                    trade.add_snapshot("alice", TradeState::PendingApproval, trade_details(150.0));
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        // From the journal, then from the checkpoint
+        let store = tmp.open();
+        let fetched = store.get(9).unwrap().unwrap();
+        assert!(Arc::ptr_eq(&fetched.history[0].details, &fetched.history[1].details));
+
+        store.compact().unwrap();
+        drop(store);
+        let fetched = tmp.open().get(9).unwrap().unwrap();
+        assert!(Arc::ptr_eq(&fetched.history[0].details, &fetched.history[1].details));
+    }
+
+    #[test]
+    fn test_rejections_are_journaled() {
+        let tmp = TempJournal::new("rejections");
+        {
+            let store = tmp.open();
+            store.push(create_trade(5, "alice")).unwrap();
+            store
+                .modify(5, &mut |trade| {
+                    trade.add_rejection("bob", TradeAction::Approve, "TAL21", "over limit", Utc::now());
+                    Ok(())
+                })
+                .unwrap();
+        }
+
+        // From the journal, then from the checkpoint
+        let store = tmp.open();
+        let fetched = store.get(5).unwrap().unwrap();
+        assert_eq!((fetched.history.len(), fetched.rejections.len()), (1, 1));
+        assert_eq!(fetched.rejections[0].user_id, "bob");
+
+        store.compact().unwrap();
+        drop(store);
+        let fetched = tmp.open().get(5).unwrap().unwrap();
+        assert_eq!(fetched.rejections.len(), 1);
+        assert_eq!(fetched.rejections[0].code, "TAL21");
+    }
+
+    #[test]
+    fn test_torn_tail_is_truncated() {
+        let tmp = TempJournal::new("torn");
+        {
+            let store = tmp.open();
+            store.push(create_trade(1, "alice")).unwrap();
+        }
+        let good_len = fs::metadata(&tmp.0).unwrap().len();
+
+        // Simulate a crash half way through the next record
+        let mut frame = Vec::new();
+        encode_frame(&mut frame, &create_trade(2, "bob")).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&tmp.0).unwrap();
+        file.write_all(&frame[..frame.len() / 2]).unwrap();
+        drop(file);
+
+        let store = tmp.open();
+        assert_eq!(store.keys().unwrap(), vec![1]);
+        assert_eq!(fs::metadata(&tmp.0).unwrap().len(), good_len);
+
+        // And the journal is usable again straight away
+        store.push(create_trade(3, "carol")).unwrap();
+        drop(store);
+        assert!(tmp.open().has(3).unwrap());
+    }
+
+    #[test]
+    fn test_bad_checksum_tail_is_truncated() {
+        let tmp = TempJournal::new("checksum");
+        let mut trade = create_trade(1, "alice");
+        {
+            let store = tmp.open();
+            store.push(trade.clone()).unwrap();
+            trade.add_snapshot("bob", TradeState::PendingApproval, trade_details(151.0));
+            store.update(trade.clone()).unwrap();
+        }
+
+        // Flip a byte inside the last record's payload
+        let mut bytes = fs::read(&tmp.0).unwrap();
+        let last = bytes.len() - 2;
+        bytes[last] ^= 0xFF;
+        fs::write(&tmp.0, &bytes).unwrap();
+
+        let store = tmp.open();
+        let fetched = store.get(1).unwrap().unwrap();
+        assert_eq!(fetched.history.len(), 1, "damaged snapshot dropped, creation kept");
+        assert_eq!(fetched.current_state(), TradeState::Draft);
+    }
+
+    #[test]
+    fn test_compaction_folds_journal_into_checkpoint() {
+        let tmp = TempJournal::new("compact");
+        let mut trade = create_trade(1, "alice");
+        {
+            let store = tmp.open();
+            store.push(trade.clone()).unwrap();
+            trade.add_snapshot("bob", TradeState::PendingApproval, trade_details(151.0));
+            store.update(trade.clone()).unwrap();
+
+            store.compact().unwrap();
+            assert_eq!(fs::metadata(store.journal_path()).unwrap().len(), 0);
+            assert!(store.checkpoint_path().exists());
+
+            // Activity after the checkpoint goes to the journal again
+            trade.add_snapshot("bob", TradeState::Approved, trade_details(151.0));
+            store.update(trade.clone()).unwrap();
+        }
+
+        let store = tmp.open();
+        let fetched = store.get(1).unwrap().unwrap();
+        assert_eq!(fetched.history.len(), 3);
+        assert_eq!(fetched.current_state(), TradeState::Approved);
+    }
+
+    #[test]
+    fn test_crash_between_checkpoint_and_journal_reset() {
+        let tmp = TempJournal::new("compact_crash");
+        let mut trade = create_trade(1, "alice");
+        {
+            let store = tmp.open();
+            store.push(trade.clone()).unwrap();
+            trade.add_snapshot("bob", TradeState::PendingApproval, trade_details(151.0));
+            store.update(trade.clone()).unwrap();
+        }
+
+        // Checkpoint written but the journal never got reset: every record is replayed twice
+        let journal = fs::read(&tmp.0).unwrap();
+        {
+            let store = tmp.open();
+            store.compact().unwrap();
+        }
+        fs::write(&tmp.0, journal).unwrap();
+
+        let store = tmp.open();
+        assert_eq!(store.keys().unwrap(), vec![1]);
+        assert_eq!(store.get(1).unwrap().unwrap().history.len(), 2);
+    }
+
+    #[test]
+    fn test_periodic_compaction() {
+        let tmp = TempJournal::new("periodic");
+        let store = tmp.open().with_compact_every(3);
+
+        store.push(create_trade(1, "alice")).unwrap();
+        store.push(create_trade(2, "alice")).unwrap();
+        assert!(!store.checkpoint_path().exists());
+
+        store.push(create_trade(3, "alice")).unwrap(); // third record triggers it
+        assert!(store.checkpoint_path().exists());
+        assert_eq!(fs::metadata(store.journal_path()).unwrap().len(), 0);
+
+        drop(store);
+        assert_eq!(tmp.open().keys().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_failed_compaction_keeps_the_write() {
+        let tmp = TempJournal::new("compact_fails");
+        let store = tmp.open().with_compact_every(1);
+
+        // A directory where the checkpoint is written aside makes compaction fail
+        let blocker = store.checkpoint_path().with_extension("checkpoint.tmp");
+        fs::create_dir_all(&blocker).unwrap();
+
+        store.push(create_trade(1, "alice")).expect("journaled push is a success");
+        let mut action = |trade: &mut Trade| {
+            trade.add_snapshot("alice", TradeState::PendingApproval, trade_details(150.0));
+            Ok(())
+        };
+        store.modify(1, &mut action).expect("journaled modify is a success");
+        assert!(!store.checkpoint_path().exists());
+
+        drop(store);
+        fs::remove_dir(&blocker).unwrap();
+        assert_eq!(tmp.open().get(1).unwrap().unwrap().history.len(), 2);
+    }
+}
@@ -0,0 +1,354 @@
+use crate::errors::ValidationError;
+use crate::model::{Trade, TradeId};
+use crate::query::TradeQuery;
+use app_core::AppError;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use parking_lot::RwLock;
+//use std::collections::HashMap;
+
+#[cfg(test)]
+mod contract;
+mod index;
+mod journal;
+mod sqlite;
+
+use index::{IndexKeys, TradeIndexes};
+
+pub use journal::{JournalStore, DEFAULT_COMPACT_EVERY};
+pub use sqlite::SqliteStore;
+
+/// Just going with a simple HashMap for now, nothing too fancy
+/// This is obviously not sustainable for a production system as we'd run out of memory!
+///
+/// Keeps secondary indexes (state, counterparty, trading entity, requester) in step with
+/// the trades, so queries on those don't have to scan every trade.
+///
+/// Readers hold `batch` shared and a batch commit takes it exclusively, so nobody reads half a batch.
+pub struct InMemoryStore {
+    trades: DashMap<TradeId, Trade>,
+    indexes: TradeIndexes,
+    batch: RwLock<()>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self { trades: DashMap::new(), indexes: TradeIndexes::default(), batch: RwLock::new(()) }
+    }
+}
+
+/// The action run by `TradeStore::modify` against the stored trade
+pub type TradeMutation<'a> = dyn FnMut(&mut Trade) -> Result<(), AppError> + 'a;
+
+/// TradeStore - the trait / interface for the trade store
+/// Can be an in-memory or DB store etc
+///
+/// Stores take care of their own locking, so they can be shared across threads as they are.
+///
+/// Reads can fail for stores backed by a database or file: a trade that is there but can't be
+/// read is an error, never "not found" (`None`) or left out of `keys`.
+pub trait TradeStore: Send + Sync {
+    /// Stores a new trade. A trade ID that is already stored is refused, the stored trade stays as it is.
+    fn push(&self, trade: Trade) -> Result<TradeId, String>;
+    fn get(&self, trade_id: TradeId) -> Result<Option<Trade>, String>;
+    fn has(&self, trade_id: TradeId) -> Result<bool, String>;
+    fn update(&self, trade: Trade) -> Result<(), String>;
+    fn keys(&self) -> Result<Vec<TradeId>, String>;
+
+    /// Run `action` against the stored trade, in place, while holding the lock for that trade.
+    /// Nobody else can change the trade in the meantime, so read-check-write in the action is safe.
+    /// Actions only ever append to the history (and rejections), if one fails what it added is dropped again.
+    fn modify(&self, trade_id: TradeId, action: &mut TradeMutation) -> Result<(), AppError>;
+
+    /// Stores what an all-or-nothing batch did: every trade, or none of them if anything fails.
+    /// New trades go in as `push` would, known ones get what was added to them as `update` would.
+    /// Readers see all of the batch or none of it. The caller makes sure nobody else changes
+    /// these trades in the meantime.
+    fn commit_batch(&self, trades: Vec<Trade>) -> Result<(), String>;
+
+    /// Trades that may match the query, the engine applies every filter itself afterwards.
+    /// Stores that can narrow the search down (e.g. with indexes) override this,
+    /// by default it is every trade in the store.
+    fn candidates(&self, _query: &TradeQuery) -> Result<Vec<Trade>, String> {
+        self.keys()?.into_iter().filter_map(|trade_id| self.get(trade_id).transpose()).collect()
+    }
+}
+
+impl TradeStore for InMemoryStore {
+    /// Push a trade to the store
+    fn push(&self, trade: Trade) -> Result<TradeId, String> {
+        let trade_id = trade.id;
+        let keys = IndexKeys::of(&trade);
+
+        // The indexes are updated while the entry (trade lock) is still held
+        let Entry::Vacant(entry) = self.trades.entry(trade_id) else {
+            return Err(format!("Trade with ID {:?} already exists", trade_id));
+        };
+        let _trade = entry.insert(trade);
+        self.indexes.replace(trade_id, None, keys);
+        Ok(trade_id)
+    }
+
+    /// Get a trade by ID
+    fn get(&self, trade_id: TradeId) -> Result<Option<Trade>, String> {
+        let _reading = self.batch.read();
+        // self.trades.get(&trade_id).cloned() // Hashmap version
+        Ok(self.trades.get(&trade_id).map(|entry| entry.clone())) // DashMap version
+    }
+
+    /// Check if the trade exists in the store
+    fn has(&self, trade_id: TradeId) -> Result<bool, String> {
+        let _reading = self.batch.read();
+        Ok(self.trades.contains_key(&trade_id))
+    }
+
+    /// Update a trade in the store (replace it with a new one)
+    ///
+    /// We would never really need update entries with better thread safety optimization.
+    /// But right now we are taking a COPY of the trade and then replacing it here.
+    /// The trade envelope is basically immutable.
+    /// With this design we are just appending state to the trade history
+    fn update(&self, trade: Trade) -> Result<(), String> {
+        // Just replace the trade found in the hashmap if found by id, with trade
+        match self.trades.get_mut(&trade.id) {
+            //Some(trade_found) => {
+            Some(mut trade_found) => {
+                let old = IndexKeys::of(&trade_found);
+                *trade_found = trade;
+                self.indexes.replace(trade_found.id, old, IndexKeys::of(&trade_found));
+                Ok(())
+            }
+            // Trade not found - could handle, or just fail silently?
+            None => Err(format!("Trade with ID {:?} not found", trade.id)),
+        }
+    }
+
+    /// Get a list of all trade IDs in the store
+    /// They will be in order of insertion
+    fn keys(&self) -> Result<Vec<TradeId>, String> {
+        let _reading = self.batch.read();
+        Ok(self.trades.iter().map(|entry| entry.key().clone()).collect())
+    }
+
+    /// The DashMap entry guard is the lock: it write-locks the shard holding this trade only,
+    /// so unrelated trades (in other shards) carry on in parallel.
+    /// History and rejections are append-only, so undoing a failed action is a truncate back to where they were.
+    fn modify(&self, trade_id: TradeId, action: &mut TradeMutation) -> Result<(), AppError> {
+        let mut trade = self.trades.get_mut(&trade_id).ok_or(ValidationError::TradeNotFound(trade_id))?;
+
+        let (stored, rejected) = (trade.history.len(), trade.rejections.len());
+        let before = IndexKeys::of(&trade);
+        action(&mut trade).inspect_err(|_| {
+            trade.history.truncate(stored);
+            trade.rejections.truncate(rejected);
+        })?;
+
+        self.indexes.replace(trade_id, before, IndexKeys::of(&trade));
+        Ok(())
+    }
+
+    /// Nothing can fail half way in memory: new trades go in, known ones are replaced
+    fn commit_batch(&self, trades: Vec<Trade>) -> Result<(), String> {
+        let _batch = self.batch.write();
+        for trade in trades {
+            let (trade_id, keys) = (trade.id, IndexKeys::of(&trade));
+            match self.trades.entry(trade_id) {
+                Entry::Occupied(mut entry) => {
+                    let old = IndexKeys::of(entry.get());
+                    entry.insert(trade);
+                    self.indexes.replace(trade_id, old, keys);
+                }
+                Entry::Vacant(entry) => {
+                    let _trade = entry.insert(trade);
+                    self.indexes.replace(trade_id, None, keys);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Narrowed down by the indexes when the query filters on an indexed value
+    fn candidates(&self, query: &TradeQuery) -> Result<Vec<Trade>, String> {
+        let _reading = self.batch.read();
+        Ok(match self.indexes.candidates(query) {
+            Some(trade_ids) => trade_ids
+                .into_iter()
+                .filter_map(|trade_id| self.trades.get(&trade_id).map(|entry| entry.clone()))
+                .collect(),
+            None => self.trades.iter().map(|entry| entry.value().clone()).collect(),
+        })
+    }
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for direction.rs
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TradeState;
+    use crate::store::contract::{create_trade, store_contract_tests, trade_details};
+    use rust_decimal_macros::dec;
+
+    store_contract_tests!(|_| Box::new(InMemoryStore::new()));
+
+    // - - - - - - - - - - - - - - - - - - - - - - - -  - - - - - - - - - - - -  - - - - - - - - - -
+    // SECONDARY INDEXES
+    // - - - - - - - - - - - - - - - - - - - - - - - -  - - - - - - - - - - - -  - - - - - - - - - -
+
+    fn candidate_ids(store: &InMemoryStore, query: &TradeQuery) -> Vec<TradeId> {
+        let mut ids: Vec<TradeId> = store.candidates(query).unwrap().iter().map(|t| t.id).collect();
+        ids.sort();
+        ids
+    }
+
+    fn by_state(state: TradeState) -> TradeQuery {
+        TradeQuery { state: Some(state), ..Default::default() }
+    }
+
+    #[test]
+    fn test_index_follows_modify() {
+        let store = InMemoryStore::new();
+        store.push(create_trade(1, "alice")).unwrap();
+        store.push(create_trade(2, "bob")).unwrap();
+        assert_eq!(candidate_ids(&store, &by_state(TradeState::Draft)), vec![1, 2]);
+
+        store
+            .modify(2, &mut |trade| {
+                trade.add_snapshot("bob", TradeState::PendingApproval, trade_details(150.0));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(candidate_ids(&store, &by_state(TradeState::Draft)), vec![1]);
+        assert_eq!(candidate_ids(&store, &by_state(TradeState::PendingApproval)), vec![2]);
+    }
+
+    #[test]
+    fn test_index_untouched_by_failed_modify() {
+        let store = InMemoryStore::new();
+        store.push(create_trade(3, "alice")).unwrap();
+
+        let _ = store.modify(3, &mut |trade| {
+            trade.add_snapshot("alice", TradeState::PendingApproval, trade_details(150.0));
+            Err(ValidationError::Internal("changed my mind".into()).into())
+        });
+
+        assert_eq!(candidate_ids(&store, &by_state(TradeState::Draft)), vec![3]);
+        assert!(candidate_ids(&store, &by_state(TradeState::PendingApproval)).is_empty());
+    }
+
+    #[test]
+    fn test_index_follows_update_and_batch() {
+        let store = InMemoryStore::new();
+        store.push(create_trade(4, "alice")).unwrap();
+
+        // Counterparty changes through update
+        let mut trade = store.get(4).unwrap().unwrap();
+        let mut details = trade_details(150.0);
+        details.counterparty = "OtherCo".into();
+        trade.add_snapshot("alice", TradeState::NeedsReapproval, details);
+        store.update(trade).unwrap();
+
+        let client_co = TradeQuery { counterparty: Some("ClientCo".into()), ..Default::default() };
+        let other_co = TradeQuery { counterparty: Some("OtherCo".into()), ..Default::default() };
+        assert!(candidate_ids(&store, &client_co).is_empty());
+        assert_eq!(candidate_ids(&store, &other_co), vec![4]);
+
+        // Pushing the same ID again is refused, the trade and its index entries stay
+        let carol = TradeQuery { requester: Some("carol".into()), ..Default::default() };
+        let alice = TradeQuery { requester: Some("alice".into()), ..Default::default() };
+        assert!(store.push(create_trade(4, "carol")).unwrap_err().contains("already exists"));
+        assert_eq!(candidate_ids(&store, &other_co), vec![4]);
+        assert!(candidate_ids(&store, &carol).is_empty());
+
+        // A committed batch replaces the trade, and its index entries
+        store.commit_batch(vec![create_trade(4, "carol")]).unwrap();
+        assert_eq!(candidate_ids(&store, &client_co), vec![4]);
+        assert!(candidate_ids(&store, &other_co).is_empty());
+        assert_eq!(candidate_ids(&store, &carol), vec![4]);
+        assert!(candidate_ids(&store, &alice).is_empty());
+    }
+
+    #[test]
+    fn test_indexed_filters_are_intersected() {
+        let store = InMemoryStore::new();
+        for (id, user) in [(1, "alice"), (2, "bob"), (3, "alice")] {
+            store.push(create_trade(id, user)).unwrap();
+        }
+        store
+            .modify(3, &mut |trade| {
+                trade.add_snapshot("alice", TradeState::Cancelled, trade_details(150.0));
+                Ok(())
+            })
+            .unwrap();
+
+        let query = TradeQuery { requester: Some("alice".into()), ..by_state(TradeState::Draft) };
+        assert_eq!(candidate_ids(&store, &query), vec![1]);
+
+        // No indexed filter: every trade is a candidate
+        let query = TradeQuery { notional_min: Some(dec!(1)), ..Default::default() };
+        assert_eq!(candidate_ids(&store, &query), vec![1, 2, 3]);
+    }
+
+    /// Hammers a handful of trades from several threads. Every modify appends exactly one snapshot,
+    /// so any lost update shows up as a short history. Timings against a global lock are in
+    /// `benches/store_locking.rs`.
+    #[test]
+    fn test_concurrent_modify_stress() {
+        use std::sync::Arc;
+        use std::thread;
+
+        const THREADS: usize = 8;
+        const TRADES: u64 = 64;
+        const OPS_PER_THREAD: usize = 2_000;
+
+        let store = Arc::new(InMemoryStore::new());
+        for id in 0..TRADES {
+            store.push(create_trade(id, "alice")).unwrap();
+        }
+
+        let workers = (0..THREADS)
+            .map(|t| {
+                let store = Arc::clone(&store);
+                thread::spawn(move || {
+                    for op in 0..OPS_PER_THREAD {
+                        let trade_id = ((t * OPS_PER_THREAD + op) as u64) % TRADES;
+                        store
+                            .modify(trade_id, &mut |trade| {
+                                let details = trade.latest_details().cloned().unwrap();
+                                trade.add_snapshot(format!("user{t}"), trade.current_state(), details);
+                                Ok(())
+                            })
+                            .unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        let snapshots: usize = (0..TRADES).map(|id| store.get(id).unwrap().unwrap().history.len() - 1).sum();
+        assert_eq!(snapshots, THREADS * OPS_PER_THREAD, "lost updates");
+        for id in 0..TRADES {
+            let history = store.get(id).unwrap().unwrap().history;
+            assert!(history.iter().enumerate().all(|(i, s)| s.snapshot_id == i), "snapshot ids out of sequence");
+        }
+    }
+
+    #[test]
+    fn test_trade_lifecycle_and_approval() {
+        let mut trade = create_trade(777, "origin");
+
+        assert_eq!(trade.current_state(), TradeState::Draft);
+        assert_eq!(trade.get_requester(), "origin".to_string());
+        assert_eq!(trade.get_first_approver(), None);
+
+        trade.add_snapshot("approver", TradeState::PendingApproval, trade_details(150.0));
+
+        assert_eq!(trade.get_first_approver(), Some("approver".to_string()));
+        assert_eq!(trade.current_state(), TradeState::PendingApproval);
+    }
+}
@@ -0,0 +1,693 @@
+use crate::errors::ValidationError;
+use crate::model::{
+    Currency, Direction, Product, RejectedAction, Trade, TradeAction, TradeDetails, TradeEventSnapshot, TradeId,
+    TradeState,
+};
+use crate::query::TradeQuery;
+use crate::store::{TradeMutation, TradeStore};
+use app_core::AppError;
+use chrono::{DateTime, SecondsFormat, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Special path understood by SQLite as "no file, keep it in RAM"
+pub const SQLITE_MEMORY: &str = ":memory:";
+
+/// Normalized schema: one row per trade envelope, one row per snapshot,
+/// the underlying currencies of each snapshot in their own table, and one row per rejected action.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS trades (
+        id          INTEGER PRIMARY KEY,
+        created_at  TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS trade_snapshots (
+        trade_id          INTEGER NOT NULL REFERENCES trades(id),
+        snapshot_id       INTEGER NOT NULL,
+        user_id           TEXT NOT NULL,
+        timestamp         TEXT NOT NULL,
+        from_state        TEXT NOT NULL,
+        to_state          TEXT NOT NULL,
+        trading_entity    TEXT NOT NULL,
+        counterparty      TEXT NOT NULL,
+        direction         TEXT NOT NULL,
+        notional_currency TEXT NOT NULL,
+        notional_amount   TEXT NOT NULL,
+        trade_date        TEXT NOT NULL,
+        value_date        TEXT NOT NULL,
+        delivery_date     TEXT NOT NULL,
+        strike            TEXT,
+        comment           TEXT,
+        reverted_from     INTEGER,
+        product           TEXT,
+        execution         TEXT,
+        PRIMARY KEY (trade_id, snapshot_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS snapshot_underlying (
+        trade_id     INTEGER NOT NULL,
+        snapshot_id  INTEGER NOT NULL,
+        position     INTEGER NOT NULL,
+        currency     TEXT NOT NULL,
+        PRIMARY KEY (trade_id, snapshot_id, position),
+        FOREIGN KEY (trade_id, snapshot_id) REFERENCES trade_snapshots(trade_id, snapshot_id)
+    );
+
+    CREATE TABLE IF NOT EXISTS trade_rejections (
+        trade_id    INTEGER NOT NULL REFERENCES trades(id),
+        position    INTEGER NOT NULL,
+        user_id     TEXT NOT NULL,
+        timestamp   TEXT NOT NULL,
+        action      TEXT NOT NULL,
+        version     INTEGER NOT NULL,
+        code        TEXT NOT NULL,
+        reason      TEXT NOT NULL,
+        PRIMARY KEY (trade_id, position)
+    );
+";
+
+/// SQLite backed store, so trade history survives a restart without a database server.
+/// Either a local file or an in-memory database (handy for tests).
+///
+/// Snapshots and rejections are append-only: `update` only writes what the database has not seen yet.
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Opens (or creates) the database at the given path, `:memory:` included
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let conn = Connection::open(path).map_err(|e| format!("Failed to open SQLite store: {e}"))?;
+        Self::init(conn)
+    }
+
+    /// A throwaway database living in memory only
+    pub fn in_memory() -> Result<Self, String> {
+        Self::open(SQLITE_MEMORY)
+    }
+
+    fn init(conn: Connection) -> Result<Self, String> {
+        conn.execute_batch("PRAGMA foreign_keys = ON;").map_err(|e| e.to_string())?;
+        conn.execute_batch(SCHEMA).map_err(|e| format!("Failed to create SQLite schema: {e}"))?;
+        Self::migrate(&conn).map_err(|e| format!("Failed to migrate SQLite schema: {e}"))?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Brings databases created before a column was added up to date
+    fn migrate(conn: &Connection) -> rusqlite::Result<()> {
+        for (column, column_type) in
+            [("comment", "TEXT"), ("reverted_from", "INTEGER"), ("product", "TEXT"), ("execution", "TEXT")]
+        {
+            let exists =
+                conn.prepare("SELECT 1 FROM pragma_table_info('trade_snapshots') WHERE name = ?1")?.exists([column])?;
+            if !exists {
+                conn.execute_batch(&format!("ALTER TABLE trade_snapshots ADD COLUMN {column} {column_type};"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes one snapshot row and its underlying currencies
+    fn insert_snapshot(conn: &Connection, trade_id: TradeId, snapshot: &TradeEventSnapshot) -> rusqlite::Result<()> {
+        let d = &snapshot.details;
+        let product = json_to_sql(&d.product)?;
+        let execution = snapshot.execution.as_ref().map(json_to_sql).transpose()?;
+        conn.execute(
+            "INSERT INTO trade_snapshots (
+                trade_id, snapshot_id, user_id, timestamp, from_state, to_state,
+                trading_entity, counterparty, direction, notional_currency, notional_amount,
+                trade_date, value_date, delivery_date, strike, comment, reverted_from, product,
+                execution
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
+            params![
+                trade_id as i64, // bit-preserving, read back with `as u64`
+                snapshot.snapshot_id as i64,
+                snapshot.user_id,
+                ts_to_sql(&snapshot.timestamp),
+                snapshot.from_state.to_string(),
+                snapshot.to_state.to_string(),
+                d.trading_entity,
+                d.counterparty,
+                d.direction.to_string(),
+                d.notional_currency.to_string(),
+                d.notional_amount.to_string(),
+                ts_to_sql(&d.trade_date),
+                ts_to_sql(&d.value_date),
+                ts_to_sql(&d.delivery_date),
+                d.strike.map(|s| s.to_string()),
+                snapshot.comment,
+                snapshot.reverted_from.map(|source| source as i64),
+                product,
+                execution,
+            ],
+        )?;
+
+        for (position, currency) in d.underlying.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO snapshot_underlying (trade_id, snapshot_id, position, currency) VALUES (?1, ?2, ?3, ?4)",
+                params![trade_id as i64, snapshot.snapshot_id as i64, position as i64, currency.to_string()],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn trade_ids(conn: &Connection) -> rusqlite::Result<Vec<TradeId>> {
+        // IDs are stored bit for bit as i64, so those past i64::MAX sort first in SQL
+        let mut stmt = conn.prepare("SELECT id FROM trades")?;
+        let rows = stmt.query_map([], |row| row.get::<_, i64>(0))?;
+        let mut trade_ids = rows.map(|id| id.map(|id| id as TradeId)).collect::<rusqlite::Result<Vec<_>>>()?;
+        trade_ids.sort_unstable();
+        Ok(trade_ids)
+    }
+
+    /// Writes the trade envelope and its whole history
+    fn insert_trade(conn: &Connection, trade: &Trade) -> rusqlite::Result<()> {
+        conn.execute(
+            "INSERT INTO trades (id, created_at) VALUES (?1, ?2)",
+            params![trade.id as i64, ts_to_sql(&trade.created_at)],
+        )?;
+        Self::insert_new(conn, trade, 0, 0)
+    }
+
+    /// How many snapshots and rejections the database holds for the trade, None if it doesn't know the trade
+    fn stored_counts(conn: &Connection, trade_id: TradeId) -> rusqlite::Result<Option<(usize, usize)>> {
+        conn.query_row(
+            "SELECT (SELECT COUNT(*) FROM trade_snapshots WHERE trade_id = t.id),
+                    (SELECT COUNT(*) FROM trade_rejections WHERE trade_id = t.id)
+             FROM trades t WHERE t.id = ?1",
+            [trade_id as i64],
+            |row| Ok((row.get::<_, i64>(0)? as usize, row.get::<_, i64>(1)? as usize)),
+        )
+        .optional()
+    }
+
+    /// Writes the snapshots and rejections past the first `stored` / `rejected` ones
+    fn insert_new(conn: &Connection, trade: &Trade, stored: usize, rejected: usize) -> rusqlite::Result<()> {
+        for snapshot in trade.history.iter().skip(stored) {
+            Self::insert_snapshot(conn, trade.id, snapshot)?;
+        }
+        for (position, rejection) in trade.rejections.iter().enumerate().skip(rejected) {
+            conn.execute(
+                "INSERT INTO trade_rejections (trade_id, position, user_id, timestamp, action, version, code, reason)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    trade.id as i64,
+                    position as i64,
+                    rejection.user_id,
+                    ts_to_sql(&rejection.timestamp),
+                    rejection.action.to_string(),
+                    rejection.version as i64,
+                    rejection.code,
+                    rejection.reason,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Rebuilds a full trade (envelope + ordered history + rejections) from the tables
+    fn load_trade(conn: &Connection, trade_id: TradeId) -> rusqlite::Result<Option<Trade>> {
+        let created_at = conn
+            .query_row("SELECT created_at FROM trades WHERE id = ?1", [trade_id as i64], |row| ts_column(row, 0))
+            .optional()?;
+
+        let Some(created_at) = created_at else {
+            return Ok(None);
+        };
+
+        let mut stmt = conn.prepare(
+            "SELECT snapshot_id, user_id, timestamp, from_state, to_state,
+                    trading_entity, counterparty, direction, notional_currency, notional_amount,
+                    trade_date, value_date, delivery_date, strike, comment, reverted_from, product,
+                    execution
+             FROM trade_snapshots WHERE trade_id = ?1 ORDER BY snapshot_id",
+        )?;
+        let mut history = stmt.query_map([trade_id as i64], snapshot_from_row)?.collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT snapshot_id, currency FROM snapshot_underlying WHERE trade_id = ?1 ORDER BY snapshot_id, position",
+        )?;
+        let underlying = stmt.query_map([trade_id as i64], |row| {
+            Ok((row.get::<_, i64>(0)? as usize, parse_column::<Currency>(row, 1)?))
+        })?;
+        for entry in underlying {
+            let (snapshot_id, currency) = entry?;
+            if let Some(snapshot) = history.get_mut(snapshot_id) {
+                // Not shared yet, so this doesn't copy anything
+                Arc::make_mut(&mut snapshot.details).underlying.push(currency);
+            }
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT user_id, timestamp, action, version, code, reason
+             FROM trade_rejections WHERE trade_id = ?1 ORDER BY position",
+        )?;
+        let rejections = stmt.query_map([trade_id as i64], rejection_from_row)?.collect::<Result<Vec<_>, _>>()?;
+
+        let mut trade = Trade { id: trade_id, created_at, history, rejections };
+        trade.share_details();
+        Ok(Some(trade))
+    }
+}
+
+impl TradeStore for SqliteStore {
+    /// Insert the trade envelope and all of its snapshots in one transaction
+    fn push(&self, trade: Trade) -> Result<TradeId, String> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+        if Self::stored_counts(&tx, trade.id).map_err(|e| e.to_string())?.is_some() {
+            return Err(format!("Trade with ID {:?} already exists", trade.id));
+        }
+        Self::insert_trade(&tx, &trade).map_err(|e| format!("Failed to insert trade {}: {e}", trade.id))?;
+
+        tx.commit().map_err(|e| e.to_string())?;
+        Ok(trade.id)
+    }
+
+    /// Get a trade by ID, materialized from the normalized tables
+    fn get(&self, trade_id: TradeId) -> Result<Option<Trade>, String> {
+        let conn = self.conn.lock();
+        Self::load_trade(&conn, trade_id).map_err(|e| format!("Failed to read trade {trade_id}: {e}"))
+    }
+
+    /// Check if the trade exists in the store
+    fn has(&self, trade_id: TradeId) -> Result<bool, String> {
+        let conn = self.conn.lock();
+        conn.query_row("SELECT 1 FROM trades WHERE id = ?1", [trade_id as i64], |_| Ok(()))
+            .optional()
+            .map(|found| found.is_some())
+            .map_err(|e| format!("Failed to look up trade {trade_id}: {e}"))
+    }
+
+    /// History is append-only, so only the snapshots (and rejections) beyond what is stored get written
+    fn update(&self, trade: Trade) -> Result<(), String> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+        let Some((stored, rejected)) = Self::stored_counts(&tx, trade.id).map_err(|e| e.to_string())? else {
+            return Err(format!("Trade with ID {:?} not found", trade.id));
+        };
+
+        Self::insert_new(&tx, &trade, stored, rejected).map_err(|e| e.to_string())?;
+
+        tx.commit().map_err(|e| e.to_string())
+    }
+
+    /// Get a list of all trade IDs in the store
+    fn keys(&self) -> Result<Vec<TradeId>, String> {
+        let conn = self.conn.lock();
+        Self::trade_ids(&conn).map_err(|e| format!("Failed to list trades: {e}"))
+    }
+
+    /// Every trade, stopping at the first one that can't be read
+    fn candidates(&self, _query: &TradeQuery) -> Result<Vec<Trade>, String> {
+        let conn = self.conn.lock();
+        let trade_ids = Self::trade_ids(&conn).map_err(|e| format!("Failed to list trades: {e}"))?;
+        trade_ids
+            .into_iter()
+            .filter_map(|trade_id| {
+                Self::load_trade(&conn, trade_id)
+                    .map_err(|e| format!("Failed to read trade {trade_id}: {e}"))
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Load, act and write back the new snapshots inside one transaction.
+    /// There is a single connection, so this holds it for the duration - SQLite only has
+    /// the one writer anyway.
+    fn modify(&self, trade_id: TradeId, action: &mut TradeMutation) -> Result<(), AppError> {
+        let internal = |e: rusqlite::Error| ValidationError::Internal(e.to_string());
+
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(internal)?;
+
+        let mut trade =
+            Self::load_trade(&tx, trade_id).map_err(internal)?.ok_or(ValidationError::TradeNotFound(trade_id))?;
+        let (stored, rejected) = (trade.history.len(), trade.rejections.len());
+        action(&mut trade)?; // dropping the transaction rolls back
+
+        Self::insert_new(&tx, &trade, stored, rejected).map_err(internal)?;
+
+        tx.commit().map_err(internal)?;
+        Ok(())
+    }
+
+    /// The whole batch in one transaction
+    fn commit_batch(&self, trades: Vec<Trade>) -> Result<(), String> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+        for trade in &trades {
+            let written = match Self::stored_counts(&tx, trade.id) {
+                Ok(Some((stored, rejected))) => Self::insert_new(&tx, trade, stored, rejected),
+                Ok(None) => Self::insert_trade(&tx, trade),
+                Err(e) => Err(e),
+            };
+            // Returning drops the transaction, which rolls the whole batch back
+            written.map_err(|e| format!("Failed to store trade {}: {e}", trade.id))?;
+        }
+
+        tx.commit().map_err(|e| e.to_string())
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Column conversion helpers
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+
+/// RFC 3339 with full precision, so timestamps round-trip exactly
+fn ts_to_sql(ts: &DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn conversion_error(idx: usize, msg: String) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, msg.into())
+}
+
+fn ts_column(row: &Row, idx: usize) -> rusqlite::Result<DateTime<Utc>> {
+    let raw: String = row.get(idx)?;
+    DateTime::parse_from_rfc3339(&raw)
+        .map(|ts| ts.with_timezone(&Utc))
+        .map_err(|e| conversion_error(idx, e.to_string()))
+}
+
+fn parse_column<T: FromStr>(row: &Row, idx: usize) -> rusqlite::Result<T> {
+    let raw: String = row.get(idx)?;
+    raw.parse::<T>().map_err(|_| conversion_error(idx, format!("Unexpected value '{raw}'")))
+}
+
+fn direction_column(row: &Row, idx: usize) -> rusqlite::Result<Direction> {
+    let raw: String = row.get(idx)?;
+    Direction::from_str(&raw).ok_or_else(|| conversion_error(idx, format!("Unexpected direction '{raw}'")))
+}
+
+/// Products and execution confirmations carry fields of their own, kept together as JSON
+fn json_to_sql<T: Serialize>(value: &T) -> rusqlite::Result<String> {
+    serde_json::to_string(value).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+fn json_column<T: DeserializeOwned>(row: &Row, idx: usize) -> rusqlite::Result<Option<T>> {
+    match row.get::<_, Option<String>>(idx)? {
+        Some(raw) => serde_json::from_str(&raw).map(Some).map_err(|e| conversion_error(idx, e.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Rows written before products existed are forwards
+fn product_column(row: &Row, idx: usize) -> rusqlite::Result<Product> {
+    Ok(json_column(row, idx)?.unwrap_or(Product::Forward))
+}
+
+/// Underlying currencies live in their own table and get attached afterwards
+fn snapshot_from_row(row: &Row) -> rusqlite::Result<TradeEventSnapshot> {
+    let strike: Option<String> = row.get(13)?;
+    let strike = match strike {
+        Some(raw) => Some(Decimal::from_str(&raw).map_err(|e| conversion_error(13, e.to_string()))?),
+        None => None,
+    };
+
+    Ok(TradeEventSnapshot {
+        snapshot_id: row.get::<_, i64>(0)? as usize,
+        user_id: row.get(1)?,
+        timestamp: ts_column(row, 2)?,
+        from_state: parse_column::<TradeState>(row, 3)?,
+        to_state: parse_column::<TradeState>(row, 4)?,
+        details: Arc::new(TradeDetails {
+            trading_entity: row.get(5)?,
+            counterparty: row.get(6)?,
+            direction: direction_column(row, 7)?,
+            notional_currency: parse_column::<Currency>(row, 8)?,
+            notional_amount: parse_column::<Decimal>(row, 9)?,
+            underlying: vec![],
+            trade_date: ts_column(row, 10)?,
+            value_date: ts_column(row, 11)?,
+            delivery_date: ts_column(row, 12)?,
+            strike,
+            product: product_column(row, 16)?,
+        }),
+        comment: row.get(14)?,
+        reverted_from: row.get::<_, Option<i64>>(15)?.map(|source| source as usize),
+        execution: json_column(row, 17)?,
+    })
+}
+
+fn rejection_from_row(row: &Row) -> rusqlite::Result<RejectedAction> {
+    Ok(RejectedAction {
+        user_id: row.get(0)?,
+        timestamp: ts_column(row, 1)?,
+        action: parse_column::<TradeAction>(row, 2)?,
+        version: row.get::<_, i64>(3)? as usize,
+        code: row.get(4)?,
+        reason: row.get(5)?,
+    })
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for sqlite.rs - same suite as the InMemoryStore, plus persistence
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ExecutionConfirmation, SwapLeg};
+    use crate::store::contract::{create_trade, store_contract_tests, trade_details};
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    fn store() -> SqliteStore {
+        SqliteStore::in_memory().expect("in-memory sqlite")
+    }
+
+    store_contract_tests!(|_| Box::new(store()));
+
+    /// Unique file per test, removed when dropped
+    struct TempDb(std::path::PathBuf);
+
+    impl TempDb {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("validus_{}_{}.db", name, std::process::id()));
+            let _ = std::fs::remove_file(&path);
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_unreadable_trade_is_an_error_not_missing() {
+        let db = TempDb::new("unreadable");
+        let store = SqliteStore::open(&db.0).unwrap();
+        store.push(create_trade(3, "alice")).unwrap();
+        drop(store);
+
+        let conn = Connection::open(&db.0).unwrap();
+        conn.execute("UPDATE trade_snapshots SET to_state = 'Bogus' WHERE trade_id = 3", []).unwrap();
+        drop(conn);
+
+        let store = SqliteStore::open(&db.0).unwrap();
+        assert!(store.has(3).unwrap());
+        assert!(store.get(3).unwrap_err().contains("Failed to read trade 3"));
+        assert!(store.candidates(&TradeQuery::default()).is_err());
+        assert!(store.get(4).unwrap().is_none());
+
+        // Nor does it drop out of the book
+        let engine = crate::engine::TradeEngine::new(store);
+        assert!(engine.trades_as_of(Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_ids_past_i64_max_keep_their_order() {
+        let store = store();
+        let ids = [u64::MAX, 7, i64::MAX as u64 + 1, i64::MAX as u64];
+        for id in ids {
+            store.push(create_trade(id, "alice")).unwrap();
+        }
+
+        assert_eq!(store.keys().unwrap(), vec![7, i64::MAX as u64, i64::MAX as u64 + 1, u64::MAX]);
+        assert_eq!(store.get(u64::MAX).unwrap().unwrap().id, u64::MAX);
+        assert!(store.push(create_trade(u64::MAX, "bob")).is_err());
+    }
+
+    #[test]
+    fn test_commit_batch_survives_reopen() {
+        let db = TempDb::new("batch");
+        let mut known = create_trade(11, "alice");
+        {
+            let store = SqliteStore::open(&db.0).unwrap();
+            store.push(known.clone()).unwrap();
+
+            known.add_snapshot("bob", TradeState::PendingApproval, trade_details(160.0));
+            store.commit_batch(vec![known.clone(), create_trade(12, "carol")]).unwrap();
+        }
+
+        let store = SqliteStore::open(&db.0).unwrap();
+        assert_eq!(store.keys().unwrap(), vec![11, 12]);
+        assert_eq!(store.get(11).unwrap().unwrap().history.len(), 2);
+        assert_eq!(store.get(11).unwrap().unwrap().current_state(), TradeState::PendingApproval);
+        assert_eq!(store.get(12).unwrap().unwrap().history.len(), 1);
+    }
+
+    #[test]
+    fn test_reload_shares_unchanged_details() {
+        let store = SqliteStore::in_memory().unwrap();
+        let mut trade = create_trade(8, "alice");
+        trade.add_snapshot("alice", TradeState::PendingApproval, trade_details(150.0));
+        trade.add_snapshot("bob", TradeState::NeedsReapproval, trade_details(151.0));
+        store.push(trade).unwrap();
+
+        let fetched = store.get(8).unwrap().unwrap();
+        assert!(Arc::ptr_eq(&fetched.history[0].details, &fetched.history[1].details));
+        assert!(!Arc::ptr_eq(&fetched.history[1].details, &fetched.history[2].details));
+        assert_eq!(fetched.history[0].details.underlying, vec![Currency::EUR]);
+    }
+
+    #[test]
+    fn test_history_survives_reopen() {
+        let db = TempDb::new("reopen");
+        let mut trade = create_trade(7, "alice");
+
+        {
+            let store = SqliteStore::open(&db.0).unwrap();
+            store.push(trade.clone()).unwrap();
+            trade.add_snapshot("bob", TradeState::PendingApproval, trade_details(175.0));
+            store.update(trade.clone()).unwrap();
+        }
+
+        let store = SqliteStore::open(&db.0).unwrap();
+        assert_eq!(store.keys().unwrap(), vec![7]);
+
+        let fetched = store.get(7).unwrap().unwrap();
+        assert_eq!(fetched.history.len(), 2);
+        assert_eq!(fetched.current_state(), TradeState::PendingApproval);
+        assert_eq!(fetched.latest_details().unwrap().notional_amount, dec!(175.0));
+    }
+
+    #[test]
+    fn test_rejections_survive_reopen() {
+        let db = TempDb::new("rejections");
+        {
+            let store = SqliteStore::open(&db.0).unwrap();
+            store.push(create_trade(6, "alice")).unwrap();
+            store
+                .modify(6, &mut |trade| {
+                    trade.add_rejection("bob", TradeAction::Approve, "TAL21", "over limit", Utc::now());
+                    Ok(())
+                })
+                .unwrap();
+
+            // A failed action takes its rejections with it
+            let failed = store.modify(6, &mut |trade| {
+                trade.add_rejection("carol", TradeAction::Approve, "TAL21", "over limit", Utc::now());
+                Err(ValidationError::Internal("nope".into()).into())
+            });
+            assert!(failed.is_err());
+        }
+
+        let store = SqliteStore::open(&db.0).unwrap();
+        let fetched = store.get(6).unwrap().unwrap();
+        assert_eq!(fetched.history.len(), 1);
+        assert_eq!(fetched.rejections.len(), 1);
+        assert_eq!(
+            (fetched.rejections[0].user_id.as_str(), fetched.rejections[0].action),
+            ("bob", TradeAction::Approve)
+        );
+        assert_eq!(fetched.rejections[0].reason, "over limit");
+    }
+
+    #[test]
+    fn test_comments_round_trip_and_old_databases_migrate() {
+        let db = TempDb::new("comments");
+        {
+            // A database from before snapshots had comments
+            let conn = Connection::open(&db.0).unwrap();
+            let old_schema = SCHEMA.replace("        comment           TEXT,\n", "");
+            assert_ne!(old_schema, SCHEMA);
+            conn.execute_batch(&old_schema).unwrap();
+        }
+
+        let store = SqliteStore::open(&db.0).unwrap();
+        let mut trade = create_trade(5, "alice");
+        trade.add_commented_snapshot_at(
+            "alice",
+            TradeState::Cancelled,
+            trade_details(150.0),
+            Utc::now(),
+            Some("Duplicate".into()),
+        );
+        store.push(trade).unwrap();
+        drop(store);
+
+        let fetched = SqliteStore::open(&db.0).unwrap().get(5).unwrap().unwrap();
+        assert_eq!(fetched.history[0].comment, None);
+        assert_eq!(fetched.history[1].comment.as_deref(), Some("Duplicate"));
+    }
+
+    #[test]
+    fn test_reverted_from_round_trips() {
+        let store = SqliteStore::in_memory().unwrap();
+        let mut trade = create_trade(6, "alice");
+        trade.add_snapshot("bob", TradeState::NeedsReapproval, trade_details(175.0));
+        trade.add_reverted_snapshot_at("bob", TradeState::NeedsReapproval, 0, Utc::now(), None).unwrap();
+        store.push(trade).unwrap();
+
+        let fetched = store.get(6).unwrap().unwrap();
+        assert_eq!(fetched.history[1].reverted_from, None);
+        assert_eq!(fetched.history[2].reverted_from, Some(0));
+        assert_eq!(fetched.history[2].details, fetched.history[0].details);
+    }
+
+    #[test]
+    fn test_products_round_trip() {
+        let store = SqliteStore::in_memory().unwrap();
+        let mut trade = create_trade(7, "alice");
+        let mut swap = trade_details(200.0);
+        swap.product = Product::Swap(SwapLeg {
+            value_date: swap.delivery_date + chrono::Duration::days(90),
+            notional_amount: swap.notional_amount,
+            rate: Some(dec!(1.2601)),
+        });
+        trade.add_snapshot("alice", TradeState::Draft, swap.clone());
+        store.push(trade).unwrap();
+
+        let fetched = store.get(7).unwrap().unwrap();
+        assert_eq!(fetched.history[0].details.product, Product::Forward);
+        assert_eq!(*fetched.history[1].details, swap);
+    }
+
+    #[test]
+    fn test_execution_round_trips() {
+        let store = SqliteStore::in_memory().unwrap();
+        let mut trade = create_trade(8, "alice");
+        let confirmation = ExecutionConfirmation {
+            rate: dec!(1.2712),
+            executed_at: Utc.with_ymd_and_hms(2025, 4, 10, 14, 30, 0).unwrap(),
+            counterparty_ref: "CPX-88412".into(),
+            executed_notional: dec!(150),
+            far_rate: None,
+        };
+        trade.add_executed_snapshot_at(
+            "olga",
+            TradeState::Executed,
+            trade_details(150.0),
+            Utc::now(),
+            confirmation.clone(),
+        );
+        store.push(trade).unwrap();
+
+        let fetched = store.get(8).unwrap().unwrap();
+        assert_eq!(fetched.history[0].execution, None);
+        assert_eq!(fetched.history[1].execution, Some(confirmation));
+    }
+}
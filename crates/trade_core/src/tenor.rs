@@ -0,0 +1,347 @@
+//! Tenors, and the value and delivery dates they stand for
+//!
+//! A tenor is how far after the trade date a trade settles, as traders say it:
+//! - `TOD` today, `TOM` the next business day, `SPOT` (or `SP`) the spot date
+//! - `<n>D`, `<n>W`, `<n>M`, `<n>Y` that long after spot, e.g. `1W`, `3M`, `1Y`, at most `MAX_TENOR_YEARS` out
+//! - `IMM<n>` the n-th IMM date (third Wednesday of March, June, September, December) after the trade date
+//!
+//! Spot is two business days after the trade date, one for USD/CAD, USD/TRY and USD/RUB. In a
+//! pair with USD, only the other currency's holidays count towards the spot lag, the spot date
+//! itself has to be good in both.
+//!
+//! Dates are rolled with the usual conventions, using the holiday calendars of the currencies:
+//! - day and week tenors roll to the following business day
+//! - month and year tenors roll modified following (the following business day, unless that is
+//!   in the next month, then the preceding one), and end-end: a spot on the last business day
+//!   of its month settles on the last business day of the target month
+//!
+//! Delivery is on the value date.
+
+use crate::calendar::Calendars;
+use crate::errors::ValidationError;
+use crate::model::Currency;
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc, Weekday};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Tenors reach at most this far out, longer ones are refused when parsed
+pub const MAX_TENOR_YEARS: u32 = 50;
+
+/// How far after the trade date a trade settles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tenor {
+    Today,
+    Tomorrow,
+    Spot,
+    Days(u32),
+    Weeks(u32),
+    Months(u32),
+    Years(u32),
+    Imm(u32),
+}
+
+impl FromStr for Tenor {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tenor = s.trim().to_ascii_uppercase();
+        let invalid =
+            || ValidationError::InvalidTenor(format!("'{s}' is not a tenor (TOD, TOM, SPOT, 1W, 3M, IMM1 ...)"));
+
+        match tenor.as_str() {
+            "TOD" => return Ok(Tenor::Today),
+            "TOM" => return Ok(Tenor::Tomorrow),
+            "SPOT" | "SP" => return Ok(Tenor::Spot),
+            _ => {}
+        }
+
+        let count = |digits: &str| digits.parse::<u32>().ok().filter(|n| *n > 0).ok_or_else(invalid);
+        let parsed = match tenor.strip_prefix("IMM") {
+            Some(n) => Tenor::Imm(count(n)?),
+            None => {
+                let unit = tenor.chars().last().ok_or_else(invalid)?;
+                let n = count(&tenor[..tenor.len() - unit.len_utf8()])?;
+                match unit {
+                    'D' => Tenor::Days(n),
+                    'W' => Tenor::Weeks(n),
+                    'M' => Tenor::Months(n),
+                    'Y' => Tenor::Years(n),
+                    _ => return Err(invalid()),
+                }
+            }
+        };
+
+        let (n, per_year) = match parsed {
+            Tenor::Days(n) => (n, 366),
+            Tenor::Weeks(n) => (n, 53),
+            Tenor::Months(n) => (n, 12),
+            Tenor::Years(n) => (n, 1),
+            Tenor::Imm(n) => (n, 4),
+            Tenor::Today | Tenor::Tomorrow | Tenor::Spot => (0, 1),
+        };
+        if n > MAX_TENOR_YEARS * per_year {
+            return Err(ValidationError::InvalidTenor(format!("'{s}' is more than {MAX_TENOR_YEARS} years out")));
+        }
+        Ok(parsed)
+    }
+}
+
+impl fmt::Display for Tenor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tenor::Today => write!(f, "TOD"),
+            Tenor::Tomorrow => write!(f, "TOM"),
+            Tenor::Spot => write!(f, "SPOT"),
+            Tenor::Days(n) => write!(f, "{n}D"),
+            Tenor::Weeks(n) => write!(f, "{n}W"),
+            Tenor::Months(n) => write!(f, "{n}M"),
+            Tenor::Years(n) => write!(f, "{n}Y"),
+            Tenor::Imm(n) => write!(f, "IMM{n}"),
+        }
+    }
+}
+
+/// Value and delivery dates derived from a tenor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TenorDates {
+    pub value_date: DateTime<Utc>,
+    pub delivery_date: DateTime<Utc>,
+}
+
+/// Business days and date rolling for the currencies of a trade
+pub struct DateRoller<'c> {
+    calendars: &'c Calendars,
+    currencies: Vec<Currency>,
+}
+
+impl<'c> DateRoller<'c> {
+    pub fn new(calendars: &'c Calendars, currencies: &[Currency]) -> Self {
+        // Each currency once, in the order given, so [USD, CAD, USD] is the USD/CAD pair
+        let mut seen = HashSet::new();
+        let currencies = currencies.iter().copied().filter(|currency| seen.insert(*currency)).collect();
+        Self { calendars, currencies }
+    }
+
+    /// Not a weekend, nor a holiday in any of the currencies
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        self.is_good_for(date, &self.currencies)
+    }
+
+    fn is_good_for(&self, date: NaiveDate, currencies: &[Currency]) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            && currencies.iter().all(|&currency| self.calendars.is_business_day(currency, date))
+    }
+
+    /// The date itself if it is a business day, otherwise the next one
+    pub fn following(&self, date: NaiveDate) -> NaiveDate {
+        date.iter_days().find(|date| self.is_business_day(*date)).unwrap_or(date)
+    }
+
+    /// The following business day, or the preceding one if that would be in the next month
+    pub fn modified_following(&self, date: NaiveDate) -> NaiveDate {
+        let following = self.following(date);
+        if following.month() == date.month() {
+            return following;
+        }
+        date.iter_days().rev().find(|date| self.is_business_day(*date)).unwrap_or(following)
+    }
+
+    /// `n` business days after the date, the date itself for 0
+    pub fn add_business_days(&self, date: NaiveDate, n: u32) -> NaiveDate {
+        let Some(nth) = (n as usize).checked_sub(1) else {
+            return date;
+        };
+        date.iter_days().skip(1).filter(|date| self.is_business_day(*date)).nth(nth).unwrap_or(date)
+    }
+
+    /// Spot date for a trade done on the date
+    pub fn spot(&self, trade_date: NaiveDate) -> NaiveDate {
+        // USD holidays don't count towards the lag of a USD pair, but spot can't fall on one
+        let counted: Vec<_> = match self.currencies.as_slice() {
+            [Currency::USD, other] | [other, Currency::USD] => vec![*other],
+            currencies => currencies.to_vec(),
+        };
+        let lagged = trade_date
+            .iter_days()
+            .skip(1)
+            .filter(|date| self.is_good_for(*date, &counted))
+            .nth(spot_lag(&self.currencies) as usize - 1)
+            .unwrap_or(trade_date);
+        self.following(lagged)
+    }
+
+    /// The value date of a trade done on the date, for the tenor
+    pub fn value_date(&self, trade_date: NaiveDate, tenor: Tenor) -> Result<NaiveDate, ValidationError> {
+        let spot = self.spot(trade_date);
+        let out_of_range = || ValidationError::InvalidTenor(format!("{tenor} after {trade_date} is out of range"));
+        let value_date = match tenor {
+            Tenor::Today if self.is_business_day(trade_date) => trade_date,
+            Tenor::Today => {
+                let reason = format!("TOD: trade date {trade_date} is not a business day");
+                return Err(ValidationError::InvalidTenor(reason));
+            }
+            Tenor::Tomorrow => self.add_business_days(trade_date, 1),
+            Tenor::Spot => spot,
+            Tenor::Days(n) => self.following(spot.checked_add_days(Days::new(n.into())).ok_or_else(out_of_range)?),
+            Tenor::Weeks(n) => {
+                self.following(spot.checked_add_days(Days::new(7 * u64::from(n))).ok_or_else(out_of_range)?)
+            }
+            Tenor::Months(n) => self.add_months(spot, n).ok_or_else(out_of_range)?,
+            Tenor::Years(n) => n.checked_mul(12).and_then(|n| self.add_months(spot, n)).ok_or_else(out_of_range)?,
+            Tenor::Imm(n) => self.modified_following(nth_imm_date(trade_date, n).ok_or_else(out_of_range)?),
+        };
+        Ok(value_date)
+    }
+
+    /// Months after the date, end-end and modified following. None past the end of the calendar.
+    fn add_months(&self, date: NaiveDate, n: u32) -> Option<NaiveDate> {
+        let target = date.checked_add_months(Months::new(n))?;
+        if self.last_business_day_of_month(date)? == date {
+            return self.last_business_day_of_month(target);
+        }
+        Some(self.modified_following(target))
+    }
+
+    fn last_business_day_of_month(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let first_of_next = date.with_day(1)?.checked_add_months(Months::new(1))?;
+        Some(first_of_next.iter_days().rev().skip(1).find(|date| self.is_business_day(*date)).unwrap_or(date))
+    }
+
+    /// Value and delivery dates for a trade done at `trade_date`, as midnight UTC
+    pub fn dates(&self, trade_date: DateTime<Utc>, tenor: Tenor) -> Result<TenorDates, ValidationError> {
+        let value_date = self.value_date(trade_date.date_naive(), tenor)?.and_time(Default::default()).and_utc();
+        Ok(TenorDates { value_date, delivery_date: value_date })
+    }
+}
+
+/// Business days from trade date to spot: T+1 for a few USD pairs, T+2 for everything else
+pub fn spot_lag(currencies: &[Currency]) -> u32 {
+    let t1 = [Currency::CAD, Currency::TRY, Currency::RUB];
+    match currencies {
+        [Currency::USD, other] | [other, Currency::USD] if t1.contains(other) => 1,
+        _ => 2,
+    }
+}
+
+/// The n-th IMM date (third Wednesday of Mar, Jun, Sep, Dec) strictly after the date,
+/// None if there is no such date in the calendar
+fn nth_imm_date(after: NaiveDate, n: u32) -> Option<NaiveDate> {
+    let first = after.with_day(1)?;
+    let quarter_months = (0..).map_while(|i| first.checked_add_months(Months::new(i)));
+    quarter_months
+        .filter(|month| month.month() % 3 == 0)
+        .filter_map(|month| NaiveDate::from_weekday_of_month_opt(month.year(), month.month(), Weekday::Wed, 3))
+        .filter(|imm| *imm > after)
+        .nth((n as usize).checked_sub(1)?)
+}
+
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+// Unit tests for tenors and date rolling
+// = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = = =
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::HolidayCalendar;
+    use chrono::TimeZone;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn calendars() -> Calendars {
+        let holiday = |d: NaiveDate| (d, String::new());
+        Calendars::default()
+            .with_calendar(
+                Currency::GBP,
+                HolidayCalendar::new([holiday(date(2025, 12, 25)), holiday(date(2025, 12, 26))]),
+            )
+            .with_calendar(
+                Currency::USD,
+                HolidayCalendar::new([holiday(date(2025, 7, 4)), holiday(date(2025, 12, 25))]),
+            )
+    }
+
+    #[test]
+    fn test_parse_tenors() {
+        assert_eq!("spot".parse::<Tenor>(), Ok(Tenor::Spot));
+        assert_eq!("TOM".parse::<Tenor>(), Ok(Tenor::Tomorrow));
+        assert_eq!("3m".parse::<Tenor>(), Ok(Tenor::Months(3)));
+        assert_eq!(" 1W ".parse::<Tenor>(), Ok(Tenor::Weeks(1)));
+        assert_eq!("IMM2".parse::<Tenor>(), Ok(Tenor::Imm(2)));
+        assert_eq!(Tenor::Years(10).to_string(), "10Y");
+
+        for bad in ["", "0M", "M", "3Q", "IMM", "IMM0", "1.5Y"] {
+            assert!(matches!(bad.parse::<Tenor>(), Err(ValidationError::InvalidTenor(_))), "{bad}");
+        }
+
+        // No further out than MAX_TENOR_YEARS
+        assert_eq!("50Y".parse::<Tenor>(), Ok(Tenor::Years(50)));
+        for far in ["51Y", "4000000000D", "9999999W", "IMM2000000", "601M"] {
+            assert!(matches!(far.parse::<Tenor>(), Err(ValidationError::InvalidTenor(_))), "{far}");
+        }
+    }
+
+    #[test]
+    fn test_spot_lag_and_holidays() {
+        let calendars = calendars();
+        let gbp_usd = DateRoller::new(&calendars, &[Currency::GBP, Currency::USD]);
+
+        // Thursday -> Monday over the weekend
+        assert_eq!(gbp_usd.spot(date(2025, 4, 10)), date(2025, 4, 14));
+        // Christmas and Boxing Day in London
+        assert_eq!(gbp_usd.spot(date(2025, 12, 23)), date(2025, 12, 29));
+        // July 4th doesn't count towards the lag, but spot can't be on it
+        assert_eq!(gbp_usd.spot(date(2025, 7, 2)), date(2025, 7, 7));
+        assert_eq!(gbp_usd.spot(date(2025, 7, 3)), date(2025, 7, 7));
+
+        let usd_cad = DateRoller::new(&calendars, &[Currency::USD, Currency::CAD]);
+        assert_eq!(usd_cad.spot(date(2025, 4, 10)), date(2025, 4, 11));
+        let repeated = DateRoller::new(&calendars, &[Currency::USD, Currency::CAD, Currency::USD]);
+        assert_eq!(repeated.spot(date(2025, 4, 10)), date(2025, 4, 11));
+        assert_eq!(spot_lag(&[Currency::CAD, Currency::USD]), 1);
+        assert_eq!(spot_lag(&[Currency::EUR, Currency::CAD]), 2);
+    }
+
+    #[test]
+    fn test_value_dates_for_tenors() {
+        let calendars = calendars();
+        let roller = DateRoller::new(&calendars, &[Currency::GBP, Currency::USD]);
+        let value = |trade_date, tenor: &str| roller.value_date(trade_date, tenor.parse().unwrap()).unwrap();
+
+        let thursday = date(2025, 4, 10);
+        assert_eq!(value(thursday, "TOD"), thursday);
+        assert_eq!(value(thursday, "TOM"), date(2025, 4, 11));
+        assert_eq!(value(thursday, "1W"), date(2025, 4, 21));
+        assert_eq!(value(thursday, "3M"), date(2025, 7, 14));
+        assert_eq!(value(thursday, "1Y"), date(2026, 4, 14));
+        assert_eq!(value(thursday, "IMM1"), date(2025, 6, 18));
+        assert_eq!(value(date(2025, 6, 18), "IMM1"), date(2025, 9, 17), "Strictly after the trade date");
+
+        // Spot on the last business day of January stays at month end: end-end
+        assert_eq!(value(date(2025, 1, 29), "1M"), date(2025, 2, 28));
+        // Modified following: Sunday 2025-11-30 would roll into December, so back to Friday
+        assert_eq!(value(date(2025, 10, 28), "1M"), date(2025, 11, 28));
+
+        let err = roller.value_date(date(2025, 12, 25), Tenor::Today).unwrap_err();
+        assert!(matches!(err, ValidationError::InvalidTenor(_)));
+
+        // Past the end of the calendar is an error, not a panic
+        for tenor in [Tenor::Days(u32::MAX), Tenor::Weeks(u32::MAX), Tenor::Years(u32::MAX), Tenor::Imm(u32::MAX)] {
+            let err = roller.value_date(thursday, tenor).unwrap_err();
+            assert!(matches!(err, ValidationError::InvalidTenor(_)), "{tenor}");
+        }
+        assert_eq!(roller.add_business_days(thursday, 0), thursday);
+    }
+
+    #[test]
+    fn test_delivery_on_the_value_date() {
+        let calendars = Calendars::default();
+        let roller = DateRoller::new(&calendars, &[Currency::EUR, Currency::USD]);
+        let trade_date = Utc.with_ymd_and_hms(2025, 4, 10, 14, 30, 0).unwrap();
+
+        let dates = roller.dates(trade_date, Tenor::Spot).unwrap();
+        assert_eq!(dates.value_date, Utc.with_ymd_and_hms(2025, 4, 14, 0, 0, 0).unwrap());
+        assert_eq!(dates.delivery_date, dates.value_date);
+    }
+}
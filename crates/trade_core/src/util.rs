@@ -22,6 +22,10 @@ pub struct TradeDiff {
     pub to_user: UserId,
     pub from_timestamp: DateTime<Utc>,
     pub to_timestamp: DateTime<Utc>,
+    pub from_comment: Option<String>, // Why each version was made, if the user said
+    pub to_comment: Option<String>,
+    pub from_reverted_from: Option<SnapshotId>, // The snapshot each version restored, if made by a revert
+    pub to_reverted_from: Option<SnapshotId>,
     pub differences: HashMap<FieldName, DiffValue>,
 }
 
@@ -32,6 +36,17 @@ impl fmt::Display for TradeDiff {
         writeln!(f, "Snapshot: {} → {}", self.from_version, self.to_version)?;
         writeln!(f, "Changed by: {} → {}", self.from_user, self.to_user)?;
         writeln!(f, "Timestamp: {} → {}", self.from_timestamp, self.to_timestamp)?;
+        if self.from_comment.is_some() || self.to_comment.is_some() {
+            let comment = |c: &Option<String>| c.as_deref().unwrap_or("-").to_string();
+            writeln!(f, "Comment: {} → {}", comment(&self.from_comment), comment(&self.to_comment))?;
+        }
+        for (version, reverted_from) in
+            [(self.from_version, self.from_reverted_from), (self.to_version, self.to_reverted_from)]
+        {
+            if let Some(source) = reverted_from {
+                writeln!(f, "Revert: v{} restores the details of v{}", version, source)?;
+            }
+        }
 
         if self.differences.is_empty() {
             writeln!(f, "No detail changes detected.")
@@ -68,5 +83,17 @@ pub fn diff_details(from: &TradeDetails, to: &TradeDetails) -> DiffMap {
     diff_field!(delivery_date);
     diff_field!(strike);
 
+    // The product's own fields, a field the other product doesn't have shows as "-"
+    let (from_fields, to_fields) = (from.product.fields(), to.product.fields());
+    let value = |fields: &[(&str, String)], name: &str| {
+        fields.iter().find(|(field, _)| *field == name).map_or("-".to_string(), |(_, value)| value.clone())
+    };
+    for (name, _) in from_fields.iter().chain(to_fields.iter()) {
+        let (from_value, to_value) = (value(&from_fields, name), value(&to_fields, name));
+        if from_value != to_value {
+            diffs.insert(name.to_string(), (from_value, to_value));
+        }
+    }
+
     diffs
 }
@@ -1,10 +1,11 @@
-use app_core::AppError;
+use app_core::{AppError, ErrorCode};
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
+use trade_core::prelude::TradeErrors;
 
 pub struct HttpAppError(pub AppError);
 
@@ -22,3 +23,19 @@ impl IntoResponse for HttpAppError {
         (status, Json(body)).into_response()
     }
 }
+
+/// True when the error means the requested trade does not exist (REST 404),
+/// or did not exist yet at the time asked for.
+pub fn is_not_found(err: &AppError) -> bool {
+    err.code() == TradeErrors::TNF01.code() || err.code() == TradeErrors::TNY18.code()
+}
+
+/// True when the policy refused the user the action (REST 403).
+pub fn is_unauthorized(err: &AppError) -> bool {
+    err.code() == TradeErrors::TUA04.code()
+}
+
+/// True when the trade moved on since the client read it (REST 412 on `If-Match`).
+pub fn is_version_conflict(err: &AppError) -> bool {
+    err.code() == TradeErrors::TVC15.code()
+}
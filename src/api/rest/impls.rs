@@ -2,21 +2,25 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 
+use crate::api::rest::errors::{is_not_found, is_unauthorized, is_version_conflict};
 use crate::service::mapper;
 use crate::service::trading_service;
 use async_trait::async_trait;
 use axum::{extract::Host, http::Method, Json};
 use axum_extra::extract::CookieJar;
 use openapi::models::{
-    ApproveTradePathParams, BookTradePathParams, CancelTradePathParams, GetTradeDetailsPathParams,
-    GetTradeHistoryPathParams, GetTradeStatusPathParams, ListTradesQueryParams, SendTradePathParams,
-    SubmitTradePathParams, TradeCreateRequest, TradeDetails, TradeDiffPathParams, TradeDiffQueryParams,
-    UpdateTradePathParams,
+    ApproveTradeHeaderParams, ApproveTradePathParams, BatchTradesHeaderParams, BookTradeHeaderParams,
+    BookTradePathParams, CancelTradeHeaderParams, CancelTradePathParams, CreateTradeHeaderParams,
+    ExecutionConfirmation, GetTradeDetailsPathParams, GetTradeHistoryPathParams, GetTradeStatusPathParams,
+    GetTradeStatusQueryParams, ListTradesQueryParams, RevertTradeHeaderParams, RevertTradePathParams,
+    RevertTradeQueryParams, SendTradeHeaderParams, SendTradePathParams, SubmitTradeHeaderParams, SubmitTradePathParams,
+    TradeBatchRequest, TradeCreateRequest, TradeDetails, TradeDiffPathParams, TradeDiffQueryParams,
+    UpdateTradeHeaderParams, UpdateTradePathParams,
 };
 use openapi::{
-    Api, ApproveTradeResponse, BookTradeResponse, CancelTradeResponse, CreateTradeResponse, GetTradeDetailsResponse,
-    GetTradeHistoryResponse, GetTradeStatusResponse, HelloResponse, ListTradesResponse, SendTradeResponse,
-    SubmitTradeResponse, TradeDiffResponse, UpdateTradeResponse,
+    Api, ApproveTradeResponse, BatchTradesResponse, BookTradeResponse, CancelTradeResponse, CreateTradeResponse,
+    GetTradeDetailsResponse, GetTradeHistoryResponse, GetTradeStatusResponse, HelloResponse, ListTradesResponse,
+    RevertTradeResponse, SendTradeResponse, SubmitTradeResponse, TradeDiffResponse, UpdateTradeResponse,
 };
 
 #[derive(Default, Clone)]
@@ -30,20 +34,24 @@ impl Api for RestApiImpl {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: CreateTradeHeaderParams,
         raw_body: TradeCreateRequest, // required by trait
     ) -> Result<CreateTradeResponse, String> {
         let user_id = raw_body.user_id.clone().ok_or("Missing user_id")?;
         let details_api = raw_body.details.clone().ok_or("Missing trade details")?;
 
-        let trade_details =
-            mapper::to_trade_details(&details_api).map_err(|e| format!("Invalid trade details: {e:?}"))?;
-
-        let trade_id = trading_service::create_trade(&user_id, trade_details)
-            .map_err(|e| format!("Trade creation failed: {e:?}"))?;
-
-        Ok(CreateTradeResponse::Status200_TradeCreated(openapi::models::TradeCreateResponse {
-            trade_id: Some(trade_id),
-        }))
+        let idempotency_key = header_params.idempotency_key.as_deref();
+        let created = mapper::to_trade_details(&details_api)
+            .and_then(|trade_details| trading_service::create_trade(&user_id, trade_details, idempotency_key));
+        Ok(match created {
+            Ok(trade_id) => CreateTradeResponse::Status200_TradeCreated(openapi::models::TradeCreateResponse {
+                trade_id: Some(trade_id),
+            }),
+            Err(e) if is_unauthorized(&e) => {
+                CreateTradeResponse::Status403_ActionNotPermitted(mapper::to_error_response(&e))
+            }
+            Err(e) => CreateTradeResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn get_trade_history(
@@ -72,9 +80,58 @@ impl Api for RestApiImpl {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: ApproveTradeHeaderParams,
         path_params: ApproveTradePathParams,
     ) -> Result<ApproveTradeResponse, String> {
-        todo!()
+        let result = mapper::to_trade_id(&path_params.id).and_then(|trade_id| {
+            let expected_version = header_params.if_match.as_deref().map(mapper::from_if_match).transpose()?.flatten();
+            let (comment, idempotency_key) =
+                (header_params.x_comment.as_deref(), header_params.idempotency_key.as_deref());
+            trading_service::approve_trade(
+                &header_params.x_user_id,
+                trade_id,
+                expected_version,
+                comment,
+                idempotency_key,
+            )
+        });
+
+        Ok(match result {
+            Ok(()) => ApproveTradeResponse::Status204_TradeApproved,
+            Err(e) if is_not_found(&e) => ApproveTradeResponse::Status404_TradeNotFound(mapper::to_error_response(&e)),
+            Err(e) if is_version_conflict(&e) => {
+                ApproveTradeResponse::Status412_VersionConflict(mapper::to_error_response(&e))
+            }
+            Err(e) if is_unauthorized(&e) => {
+                ApproveTradeResponse::Status403_ActionNotPermitted(mapper::to_error_response(&e))
+            }
+            Err(e) => ApproveTradeResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
+    }
+
+    /// Several actions in one call, every one applied or none unless the request asks for best effort
+    async fn batch_trades(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        header_params: BatchTradesHeaderParams,
+        body: TradeBatchRequest,
+    ) -> Result<BatchTradesResponse, String> {
+        let user_id = &header_params.x_user_id;
+        let request = mapper::to_batch_mode(body.mode.as_deref()).and_then(|mode| {
+            let commands = body.commands.as_deref().unwrap_or_default().iter().enumerate();
+            let commands = commands.map(|(index, command)| mapper::to_trade_command(user_id, index, command));
+            Ok((commands.collect::<Result<Vec<_>, _>>()?, mode))
+        });
+
+        Ok(match request {
+            Ok((commands, mode)) => {
+                let results = trading_service::batch_trades(commands, mode);
+                BatchTradesResponse::Status200_OneResultPerCommand(mapper::to_batch_response(&results))
+            }
+            Err(e) => BatchTradesResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn book_trade(
@@ -82,9 +139,34 @@ impl Api for RestApiImpl {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: BookTradeHeaderParams,
         path_params: BookTradePathParams,
+        body: ExecutionConfirmation,
     ) -> Result<BookTradeResponse, String> {
-        todo!()
+        let result = mapper::to_trade_id(&path_params.id).and_then(|trade_id| {
+            let confirmation = mapper::to_execution_confirmation(&body)?;
+            let expected_version = header_params.if_match.as_deref().map(mapper::from_if_match).transpose()?.flatten();
+            let idempotency_key = header_params.idempotency_key.as_deref();
+            trading_service::book_trade(
+                &header_params.x_user_id,
+                trade_id,
+                confirmation,
+                expected_version,
+                idempotency_key,
+            )
+        });
+
+        Ok(match result {
+            Ok(()) => BookTradeResponse::Status204_TradeBooked,
+            Err(e) if is_not_found(&e) => BookTradeResponse::Status404_TradeNotFound(mapper::to_error_response(&e)),
+            Err(e) if is_version_conflict(&e) => {
+                BookTradeResponse::Status412_VersionConflict(mapper::to_error_response(&e))
+            }
+            Err(e) if is_unauthorized(&e) => {
+                BookTradeResponse::Status403_ActionNotPermitted(mapper::to_error_response(&e))
+            }
+            Err(e) => BookTradeResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn cancel_trade(
@@ -92,9 +174,33 @@ impl Api for RestApiImpl {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: CancelTradeHeaderParams,
         path_params: CancelTradePathParams,
     ) -> Result<CancelTradeResponse, String> {
-        todo!()
+        let result = mapper::to_trade_id(&path_params.id).and_then(|trade_id| {
+            let expected_version = header_params.if_match.as_deref().map(mapper::from_if_match).transpose()?.flatten();
+            let (comment, idempotency_key) =
+                (header_params.x_comment.as_deref(), header_params.idempotency_key.as_deref());
+            trading_service::cancel_trade(
+                &header_params.x_user_id,
+                trade_id,
+                expected_version,
+                comment,
+                idempotency_key,
+            )
+        });
+
+        Ok(match result {
+            Ok(()) => CancelTradeResponse::Status204_TradeCancelled,
+            Err(e) if is_not_found(&e) => CancelTradeResponse::Status404_TradeNotFound(mapper::to_error_response(&e)),
+            Err(e) if is_version_conflict(&e) => {
+                CancelTradeResponse::Status412_VersionConflict(mapper::to_error_response(&e))
+            }
+            Err(e) if is_unauthorized(&e) => {
+                CancelTradeResponse::Status403_ActionNotPermitted(mapper::to_error_response(&e))
+            }
+            Err(e) => CancelTradeResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn get_trade_details(
@@ -104,7 +210,18 @@ impl Api for RestApiImpl {
         cookies: CookieJar,
         path_params: GetTradeDetailsPathParams,
     ) -> Result<GetTradeDetailsResponse, String> {
-        todo!()
+        let result = mapper::to_trade_id(&path_params.id).and_then(trading_service::trade_details_with_version);
+
+        Ok(match result {
+            Ok((details, version)) => GetTradeDetailsResponse::Status200_FullTradeDetails {
+                body: mapper::to_api_trade_details(&details),
+                e_tag: mapper::to_etag(version),
+            },
+            Err(e) if is_not_found(&e) => {
+                GetTradeDetailsResponse::Status404_TradeNotFound(mapper::to_error_response(&e))
+            }
+            Err(e) => GetTradeDetailsResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn get_trade_status(
@@ -113,8 +230,20 @@ impl Api for RestApiImpl {
         host: Host,
         cookies: CookieJar,
         path_params: GetTradeStatusPathParams,
+        query_params: GetTradeStatusQueryParams,
     ) -> Result<GetTradeStatusResponse, String> {
-        todo!()
+        let result = mapper::to_trade_id(&path_params.id).and_then(|trade_id| match query_params.as_of {
+            Some(at) => trading_service::trade_as_of(trade_id, at).map(|s| mapper::to_trade_status_as_of(&s)),
+            None => trading_service::trade_status(trade_id).map(mapper::to_trade_status),
+        });
+
+        Ok(match result {
+            Ok(status) => GetTradeStatusResponse::Status200_CurrentTradeStatus(status),
+            Err(e) if is_not_found(&e) => {
+                GetTradeStatusResponse::Status404_TradeNotFound(mapper::to_error_response(&e))
+            }
+            Err(e) => GetTradeStatusResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn hello(&self, _method: Method, _host: Host, _cookies: CookieJar) -> Result<HelloResponse, String> {
@@ -130,7 +259,49 @@ impl Api for RestApiImpl {
         cookies: CookieJar,
         query_params: ListTradesQueryParams,
     ) -> Result<ListTradesResponse, String> {
-        todo!()
+        let result = mapper::to_trade_query(&query_params).and_then(trading_service::query_trades);
+
+        Ok(match result {
+            Ok(page) => ListTradesResponse::Status200_APageOfTrades(mapper::to_trade_list_page(&page)),
+            Err(e) => ListTradesResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
+    }
+
+    async fn revert_trade(
+        &self,
+        method: Method,
+        host: Host,
+        cookies: CookieJar,
+        header_params: RevertTradeHeaderParams,
+        path_params: RevertTradePathParams,
+        query_params: RevertTradeQueryParams,
+    ) -> Result<RevertTradeResponse, String> {
+        let result = mapper::to_trade_id(&path_params.id).and_then(|trade_id| {
+            let snapshot_id = mapper::to_version(query_params.version, "version")?;
+            let expected_version = header_params.if_match.as_deref().map(mapper::from_if_match).transpose()?.flatten();
+            let (comment, idempotency_key) =
+                (header_params.x_comment.as_deref(), header_params.idempotency_key.as_deref());
+            trading_service::revert_trade(
+                &header_params.x_user_id,
+                trade_id,
+                snapshot_id,
+                expected_version,
+                comment,
+                idempotency_key,
+            )
+        });
+
+        Ok(match result {
+            Ok(()) => RevertTradeResponse::Status204_TradeReverted,
+            Err(e) if is_not_found(&e) => RevertTradeResponse::Status404_TradeNotFound(mapper::to_error_response(&e)),
+            Err(e) if is_version_conflict(&e) => {
+                RevertTradeResponse::Status412_VersionConflict(mapper::to_error_response(&e))
+            }
+            Err(e) if is_unauthorized(&e) => {
+                RevertTradeResponse::Status403_ActionNotPermitted(mapper::to_error_response(&e))
+            }
+            Err(e) => RevertTradeResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn send_trade(
@@ -138,9 +309,27 @@ impl Api for RestApiImpl {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: SendTradeHeaderParams,
         path_params: SendTradePathParams,
     ) -> Result<SendTradeResponse, String> {
-        todo!()
+        let result = mapper::to_trade_id(&path_params.id).and_then(|trade_id| {
+            let expected_version = header_params.if_match.as_deref().map(mapper::from_if_match).transpose()?.flatten();
+            let (comment, idempotency_key) =
+                (header_params.x_comment.as_deref(), header_params.idempotency_key.as_deref());
+            trading_service::send_trade(&header_params.x_user_id, trade_id, expected_version, comment, idempotency_key)
+        });
+
+        Ok(match result {
+            Ok(()) => SendTradeResponse::Status204_TradeSent,
+            Err(e) if is_not_found(&e) => SendTradeResponse::Status404_TradeNotFound(mapper::to_error_response(&e)),
+            Err(e) if is_version_conflict(&e) => {
+                SendTradeResponse::Status412_VersionConflict(mapper::to_error_response(&e))
+            }
+            Err(e) if is_unauthorized(&e) => {
+                SendTradeResponse::Status403_ActionNotPermitted(mapper::to_error_response(&e))
+            }
+            Err(e) => SendTradeResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn submit_trade(
@@ -148,9 +337,26 @@ impl Api for RestApiImpl {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: SubmitTradeHeaderParams,
         path_params: SubmitTradePathParams,
     ) -> Result<SubmitTradeResponse, String> {
-        todo!()
+        let result = mapper::to_trade_id(&path_params.id).and_then(|trade_id| {
+            let expected_version = header_params.if_match.as_deref().map(mapper::from_if_match).transpose()?.flatten();
+            let idempotency_key = header_params.idempotency_key.as_deref();
+            trading_service::submit_trade(&header_params.x_user_id, trade_id, expected_version, idempotency_key)
+        });
+
+        Ok(match result {
+            Ok(()) => SubmitTradeResponse::Status204_TradeSubmitted,
+            Err(e) if is_not_found(&e) => SubmitTradeResponse::Status404_TradeNotFound(mapper::to_error_response(&e)),
+            Err(e) if is_version_conflict(&e) => {
+                SubmitTradeResponse::Status412_VersionConflict(mapper::to_error_response(&e))
+            }
+            Err(e) if is_unauthorized(&e) => {
+                SubmitTradeResponse::Status403_ActionNotPermitted(mapper::to_error_response(&e))
+            }
+            Err(e) => SubmitTradeResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn trade_diff(
@@ -161,7 +367,17 @@ impl Api for RestApiImpl {
         path_params: TradeDiffPathParams,
         query_params: TradeDiffQueryParams,
     ) -> Result<TradeDiffResponse, String> {
-        todo!()
+        let result = mapper::to_trade_id(&path_params.id).and_then(|trade_id| {
+            let v1 = mapper::to_version(query_params.v1, "v1")?;
+            let v2 = mapper::to_version(query_params.v2, "v2")?;
+            trading_service::trade_diff(trade_id, v1, v2).and_then(|diff| mapper::to_trade_diff(&diff))
+        });
+
+        Ok(match result {
+            Ok(diff) => TradeDiffResponse::Status200_FieldDifferencesBetweenTwoVersions(diff),
+            Err(e) if is_not_found(&e) => TradeDiffResponse::Status404_TradeNotFound(mapper::to_error_response(&e)),
+            Err(e) => TradeDiffResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 
     async fn update_trade(
@@ -169,9 +385,35 @@ impl Api for RestApiImpl {
         method: Method,
         host: Host,
         cookies: CookieJar,
+        header_params: UpdateTradeHeaderParams,
         path_params: UpdateTradePathParams,
         body: TradeDetails,
     ) -> Result<UpdateTradeResponse, String> {
-        todo!()
+        let result = mapper::to_trade_id(&path_params.id).and_then(|trade_id| {
+            let details = mapper::to_trade_details(&body)?;
+            let expected_version = header_params.if_match.as_deref().map(mapper::from_if_match).transpose()?.flatten();
+            let (comment, idempotency_key) =
+                (header_params.x_comment.as_deref(), header_params.idempotency_key.as_deref());
+            trading_service::update_trade(
+                &header_params.x_user_id,
+                trade_id,
+                details,
+                expected_version,
+                comment,
+                idempotency_key,
+            )
+        });
+
+        Ok(match result {
+            Ok(()) => UpdateTradeResponse::Status204_TradeUpdated,
+            Err(e) if is_not_found(&e) => UpdateTradeResponse::Status404_TradeNotFound(mapper::to_error_response(&e)),
+            Err(e) if is_version_conflict(&e) => {
+                UpdateTradeResponse::Status412_VersionConflict(mapper::to_error_response(&e))
+            }
+            Err(e) if is_unauthorized(&e) => {
+                UpdateTradeResponse::Status403_ActionNotPermitted(mapper::to_error_response(&e))
+            }
+            Err(e) => UpdateTradeResponse::Status400_RequestRejected(mapper::to_error_response(&e)),
+        })
     }
 }
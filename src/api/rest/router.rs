@@ -1,6 +1,6 @@
 use axum::{
     body::Body,
-    http::{Request, StatusCode},
+    http::{header::CONTENT_TYPE, Request, StatusCode},
     middleware::{from_fn, Next},
     response::{IntoResponse, Response},
     routing::Router as AxumRouter,
@@ -15,7 +15,10 @@ use crate::api::rest::impls::RestApiImpl;
 async fn json_rejection_handler(req: Request<Body>, next: Next) -> Response {
     let response = next.run(req).await;
 
-    if response.status() == StatusCode::BAD_REQUEST {
+    // Handlers answer with a JSON ErrorResponse; only plain-text extractor rejections are rewritten
+    let is_json = response.headers().get(CONTENT_TYPE).is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+
+    if response.status() == StatusCode::BAD_REQUEST && !is_json {
         let body = json!({
             "error": "BadRequest",
             "message": "Failed to parse JSON payload"
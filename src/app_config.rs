@@ -6,6 +6,7 @@
 use app_core::context::FeatureMapProvider;
 use serde::Deserialize;
 use std::collections::HashMap;
+use trade_core::store::DEFAULT_COMPACT_EVERY;
 
 #[derive(Debug, Deserialize)]
 pub struct AppConfig {
@@ -46,12 +47,45 @@ impl Default for LogConfig {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(default)]
 pub struct EngineConfig {
     pub machine_id: u16,
+    pub store: StoreBackend,
+    pub store_path: Option<String>, // defaults per backend, see StoreBackend::default_path
+    pub compact_every: usize,       // file store only, 0 = never
+    pub idempotency_window: Option<u64>, // seconds, 24 hours if not set
+    pub calendars: Option<String>,  // directory of holiday calendars
 }
 impl Default for EngineConfig {
     fn default() -> Self {
-        Self { machine_id: 101 }
+        Self {
+            machine_id: 101,
+            store: StoreBackend::Memory,
+            store_path: None,
+            compact_every: DEFAULT_COMPACT_EVERY,
+            idempotency_window: None,
+            calendars: None,
+        }
+    }
+}
+
+/// Where the trade engine keeps its trades, `engine.store` in the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreBackend {
+    Memory,
+    Sqlite,
+    File,
+}
+
+impl StoreBackend {
+    /// Where the store lives when `engine.store_path` is not set
+    pub fn default_path(&self) -> &'static str {
+        match self {
+            StoreBackend::Memory => "",
+            StoreBackend::Sqlite => "./data/trades.db",
+            StoreBackend::File => "./data/trades.journal",
+        }
     }
 }
 
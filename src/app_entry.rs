@@ -1,5 +1,7 @@
 use crate::api::{start_grpc_server_bg, start_rest_server_bg};
+use crate::service::expiry::{expiry_config, start_expiry_job_bg};
 use crate::service::trading_service::*;
+use crate::state::trading_state::start_engine;
 use app_core::prelude::*;
 use std::future::Future;
 use std::pin::Pin;
@@ -12,6 +14,16 @@ pub fn run_boxed<'a>(app: &'a mut AppContext) -> Pin<Box<dyn Future<Output = Res
 pub async fn run(app: &mut AppContext) -> Result<(), AppError> {
     out_f!("App Started!");
 
+    // Build the engine (and open its store) now, so a bad [engine] config fails at startup
+    // rather than on the first request
+    start_engine()?;
+
+    // Likewise a bad [expiry] section, no section means trades never expire
+    if let Some(expiry) = expiry_config()? {
+        iout!("Starting expiry job, every {}s", expiry.interval);
+        start_expiry_job_bg(expiry);
+    }
+
     if app.feature_enabled("dev_mode") {
         wout!("Dev mode enabled, running scenarios from brief");
         tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
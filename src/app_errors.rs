@@ -10,10 +10,14 @@ pub mod err_kind {
 
 #[derive(Debug)]
 pub enum ErrCodes {
-    #[allow(dead_code)]
     E1234,
     #[allow(dead_code)]
     E2000,
+    E2001,
+    E2002,
+    E2003,
+    E2004,
+    E2005,
 }
 
 impl ErrorCode for ErrCodes {
@@ -21,6 +25,11 @@ impl ErrorCode for ErrCodes {
         match self {
             ErrCodes::E1234 => "E1234",
             ErrCodes::E2000 => "E2000",
+            ErrCodes::E2001 => "E2001",
+            ErrCodes::E2002 => "E2002",
+            ErrCodes::E2003 => "E2003",
+            ErrCodes::E2004 => "E2004",
+            ErrCodes::E2005 => "E2005",
         }
     }
 
@@ -28,6 +37,11 @@ impl ErrorCode for ErrCodes {
         match self {
             ErrCodes::E1234 => "Invalid value for {field}",
             ErrCodes::E2000 => "Missing required config: {key}",
+            ErrCodes::E2001 => "Invalid engine config: {reason}",
+            ErrCodes::E2002 => "Failed to open trade store: {reason}",
+            ErrCodes::E2003 => "Invalid authorization policy: {reason}",
+            ErrCodes::E2004 => "Invalid comment rules: {reason}",
+            ErrCodes::E2005 => "Invalid expiry config: {reason}",
         }
     }
 
@@ -35,6 +49,11 @@ impl ErrorCode for ErrCodes {
         match self {
             ErrCodes::E1234 => err_kind::VALIDATION,
             ErrCodes::E2000 => "config",
+            ErrCodes::E2001 => "config",
+            ErrCodes::E2002 => err_kind::SERVICE,
+            ErrCodes::E2003 => "config",
+            ErrCodes::E2004 => "config",
+            ErrCodes::E2005 => "config",
         }
     }
 }
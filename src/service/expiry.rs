@@ -0,0 +1,211 @@
+//! Background expiry of stale trades
+//!
+//! Trades left waiting too long (e.g. still pending approval days after their value date) are
+//! taken out of the workflow by a job that runs every `interval` seconds. What counts as stale,
+//! and what happens to a stale trade, is set per state in the `[expiry]` config section:
+//!
+//! ```toml
+//! [expiry]
+//! interval = 60    # seconds between runs
+//! user = "system"  # who the change is recorded against in the trade history
+//!
+//! [expiry.states.PendingApproval]
+//! ttl = 86400          # seconds since the trade got into the state
+//! at_value_date = true # or once its value date has passed, whichever comes first
+//! action = "expire"    # expire (-> Expired, the default) | cancel (-> Cancelled)
+//! ```
+//!
+//! The job goes through the engine like any user would, so the change is authorized (the
+//! standard `RolePolicy` only lets admins expire trades), commented with the reason, and
+//! published to the engine's subscribers. A trade that changes while the job looks at it is
+//! left alone until the next run.
+
+use app_core::config::config_section;
+use app_core::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use trade_core::engine::TradeEngine;
+use trade_core::model::{TradeEventSnapshot, TradeState};
+use trade_core::query::{TradeQuery, TradeSummary, MAX_PAGE_SIZE};
+
+use crate::app_errors::ErrCodes;
+use crate::state::trading_state::engine;
+
+/// The `[expiry]` config section
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExpiryConfig {
+    /// Seconds between runs of the job
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+
+    /// The user the job acts as
+    #[serde(default = "default_user")]
+    pub user: String,
+
+    /// When trades in a state go stale, states not listed never do
+    #[serde(default)]
+    pub states: HashMap<TradeState, StatePolicy>,
+}
+
+/// When a trade in a given state is stale, and what to do about it
+#[derive(Debug, Clone, Deserialize)]
+pub struct StatePolicy {
+    /// Seconds a trade may stay in the state
+    #[serde(default)]
+    pub ttl: Option<i64>,
+
+    /// Stale once the trade's value date has passed
+    #[serde(default)]
+    pub at_value_date: bool,
+
+    #[serde(default)]
+    pub action: ExpiryAction,
+}
+
+/// What happens to a stale trade
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpiryAction {
+    #[default]
+    Expire,
+    Cancel,
+}
+
+fn default_interval() -> u64 {
+    60
+}
+
+fn default_user() -> String {
+    "system".to_string()
+}
+
+impl ExpiryConfig {
+    /// Rules that could never fire, or fire on trades that can't move, are mistakes in the config
+    fn validate(&self) -> Result<(), String> {
+        if self.interval == 0 {
+            return Err("interval must be at least a second".into());
+        }
+        if self.user.trim().is_empty() {
+            return Err("user must not be empty".into());
+        }
+        for (state, policy) in &self.states {
+            if state.is_final() {
+                return Err(format!("{state} is final, its trades can't expire"));
+            }
+            match policy.ttl {
+                Some(ttl) if ttl < 0 => return Err(format!("ttl for {state} must not be negative")),
+                None if !policy.at_value_date => {
+                    return Err(format!("{state} needs a ttl or at_value_date, or it never expires"))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StatePolicy {
+    /// Why the trade is stale at `now`, None if it isn't.
+    /// `since` is when the trade got into its current state.
+    fn stale_reason(&self, summary: &TradeSummary, since: DateTime<Utc>, now: DateTime<Utc>) -> Option<String> {
+        if let Some(ttl) = self.ttl {
+            if now >= since + Duration::seconds(ttl) {
+                return Some(format!("Stale: in {} for more than {ttl}s", summary.state));
+            }
+        }
+        let value_date = summary.details.value_date;
+        if self.at_value_date && now >= value_date {
+            return Some(format!("Stale: value date {} has passed", value_date.date_naive()));
+        }
+        None
+    }
+}
+
+/// Expiry settings from the `[expiry]` section, None if there aren't any (no expiry job)
+pub fn expiry_config() -> Result<Option<ExpiryConfig>, AppError> {
+    let config_error =
+        |reason: String| AppError::from_code(ErrCodes::E2005, json!({ "reason": reason })).with_tag("expiry");
+
+    let Some(config) = config_section::<ExpiryConfig>("expiry").transpose().map_err(config_error)? else {
+        return Ok(None);
+    };
+    config.validate().map_err(config_error)?;
+    Ok(Some(config))
+}
+
+/// Starts the expiry job in the background, it runs until the app stops
+pub fn start_expiry_job_bg(config: ExpiryConfig) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(config.interval));
+        loop {
+            ticker.tick().await;
+
+            // The engine (and its store) blocks, keep it off the async workers
+            let run = config.clone();
+            match tokio::task::spawn_blocking(move || expire_stale_trades(engine(), &run, Utc::now())).await {
+                Ok(0) => {}
+                Ok(expired) => iout!("Expiry job: {} stale trade(s) taken out of the workflow", expired),
+                Err(e) => eout!("Expiry job failed: {}", e),
+            }
+        }
+    });
+}
+
+/// One run of the job: applies the policy of each configured state to its trades, as they stand at `now`.
+/// Returns how many trades were expired or cancelled.
+pub fn expire_stale_trades(engine: &TradeEngine, config: &ExpiryConfig, now: DateTime<Utc>) -> usize {
+    let mut expired = 0;
+    for (&state, policy) in &config.states {
+        // Collect first, so that trades leaving the state don't shift the pages
+        let stale: Vec<_> = trades_in(engine, state)
+            .into_iter()
+            .filter_map(|summary| {
+                let history = engine.trade_history(summary.trade_id).ok()?;
+                let since = entered_state_at(&history, state)?;
+                policy.stale_reason(&summary, since, now).map(|reason| (summary, reason))
+            })
+            .collect();
+
+        for (summary, reason) in stale {
+            // Only the version we looked at, anything newer gets another look next time
+            let version = Some(summary.version);
+            let result = match policy.action {
+                ExpiryAction::Expire => engine.expire(&config.user, summary.trade_id, version, Some(&reason)),
+                ExpiryAction::Cancel => engine.cancel(&config.user, summary.trade_id, version, Some(&reason)),
+            };
+            match result {
+                Ok(_) => expired += 1,
+                Err(e) => wout!("Expiry job: trade {} left as is: {}", summary.trade_id, e),
+            }
+        }
+    }
+    expired
+}
+
+/// Every trade currently in the state, page by page
+fn trades_in(engine: &TradeEngine, state: TradeState) -> Vec<TradeSummary> {
+    let mut trades = Vec::new();
+    let mut query = TradeQuery { state: Some(state), limit: Some(MAX_PAGE_SIZE), ..Default::default() };
+    loop {
+        let page = match engine.query(query.clone()) {
+            Ok(page) => page,
+            Err(e) => {
+                wout!("Expiry job: unable to query {} trades: {}", state, e);
+                return trades;
+            }
+        };
+        trades.extend(page.trades);
+        match page.next_cursor {
+            Some(cursor) => query.cursor = Some(cursor),
+            None => return trades,
+        }
+    }
+}
+
+/// When the trade got into `state`, i.e. the first of the snapshots it has been in it since.
+/// Actions that leave the state as it is (e.g. one approval short of a quorum) don't restart the clock.
+fn entered_state_at(history: &[TradeEventSnapshot], state: TradeState) -> Option<DateTime<Utc>> {
+    history.iter().rev().take_while(|snapshot| snapshot.to_state == state).last().map(|snapshot| snapshot.timestamp)
+}
@@ -1,22 +1,32 @@
 use app_core::AppError;
+use chrono::{NaiveTime, Utc};
 use openapi::{models as api, models};
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use serde_json::json;
-use trade_core::model::{Currency, Direction, TradeDetails, TradeEventSnapshot};
+use trade_core::batch::BatchMode;
+use trade_core::command::{CommandOutcome, TradeCommand};
+use trade_core::model::{
+    Currency, Direction, ExecutionConfirmation, OptionCut, OptionType, Product, SnapshotId, SwapLeg, TradeDetails,
+    TradeEventSnapshot, TradeId, TradeState, UnknownCurrency,
+};
+use trade_core::query::{TradePage, TradeQuery, TradeSummary};
+use trade_core::tenor::Tenor;
+use trade_core::TradeDiff;
+
+use crate::app_errors::ErrCodes;
+use crate::service::trading_service;
 
 pub fn to_trade_details(api: &api::TradeDetails) -> Result<TradeDetails, AppError> {
     let direction_raw = api.direction.clone().ok_or_else(|| AppError::new("100", "Missing direction"))?;
     let direction = Direction::from_str(&direction_raw).ok_or_else(|| {
         AppError::new("100", "Invalid direction")
             .with_tag("trade_details")
-            .with_data("direction", direction_raw.parse().unwrap())
+            .with_data("direction", json!(direction_raw))
     })?;
 
     let currency_raw = api.notional_currency.clone().ok_or_else(|| AppError::new("100", "Missing currency"))?;
-    let notional_currency = currency_raw.parse::<Currency>().map_err(|_| {
-        AppError::new("100", "Invalid currency").with_tag("trade_details").with_data("currency", json!(currency_raw))
-    })?;
+    let notional_currency = currency_raw.parse::<Currency>().map_err(|e| invalid_currency("notional_currency", e))?;
 
     let notional_f64 = api.notional_amount.ok_or_else(|| AppError::new("100", "Missing notional_amount"))?;
     let notional_amount = Decimal::from_f64(notional_f64).ok_or_else(|| {
@@ -30,12 +40,29 @@ pub fn to_trade_details(api: &api::TradeDetails) -> Result<TradeDetails, AppErro
         .clone()
         .unwrap_or_default()
         .into_iter()
-        .map(|s| {
-            s.parse::<Currency>()
-                .map_err(|e| AppError::from_error(e).with_tag("trade_details").with_data("underlying", json!(s)))
-        })
+        .map(|s| s.parse::<Currency>().map_err(|e| invalid_currency("underlying", e)))
         .collect::<Result<Vec<_>, _>>()?;
 
+    // A tenor stands in for the value and delivery dates, derived from the trade date (today if not given)
+    let (trade_date, value_date, delivery_date) = match api.tenor.as_deref() {
+        None => (
+            api.trade_date.unwrap_or_default(),
+            api.value_date.unwrap_or_default(),
+            api.delivery_date.unwrap_or_default(),
+        ),
+        Some(_) if api.value_date.is_some() || api.delivery_date.is_some() => {
+            return Err(AppError::from_code(ErrCodes::E1234, json!({ "field": "tenor" }))
+                .with_tag("trade_details")
+                .with_data("reason", json!("Give either a tenor or value and delivery dates, not both")));
+        }
+        Some(tenor) => {
+            // Dates are whole days, a TOD trade settles on its trade date
+            let trade_date = api.trade_date.unwrap_or_else(Utc::now).date_naive().and_time(NaiveTime::MIN).and_utc();
+            let dates = trading_service::tenor_dates(trade_date, &underlying, tenor.parse::<Tenor>()?)?;
+            (trade_date, dates.value_date, dates.delivery_date)
+        }
+    };
+
     Ok(TradeDetails {
         trading_entity: api.trading_entity.clone().ok_or_else(|| AppError::new("100", "Missing trading_entity"))?,
         counterparty: api.counterparty.clone().ok_or_else(|| AppError::new("100", "Missing counterparty"))?,
@@ -43,13 +70,123 @@ pub fn to_trade_details(api: &api::TradeDetails) -> Result<TradeDetails, AppErro
         notional_currency,
         notional_amount,
         underlying,
-        trade_date: api.trade_date.unwrap_or_default(),
-        value_date: api.value_date.unwrap_or_default(),
-        delivery_date: api.delivery_date.unwrap_or_default(),
-        strike: None,
+        trade_date,
+        value_date,
+        delivery_date,
+        strike: parse_param(&api.strike, "strike", |strike| Decimal::from_f64(*strike))?, // Refused until executed
+        product: to_product(api)?,
+    })
+}
+
+/// The product and the fields only it has, trades that don't say are forwards
+fn to_product(api: &api::TradeDetails) -> Result<Product, AppError> {
+    let name = parse_param(&api.product, "product", |name| {
+        Product::NAMES.into_iter().find(|known| known.eq_ignore_ascii_case(name))
+    })?;
+
+    Ok(match name.unwrap_or("Forward") {
+        "Spot" => Product::Spot,
+        "Forward" => Product::Forward,
+        "Swap" => Product::Swap(SwapLeg {
+            value_date: required_field(&api.far_value_date, "far_value_date", |date| Some(*date))?,
+            notional_amount: required_field(&api.far_notional_amount, "far_notional_amount", |n| {
+                Decimal::from_f64(*n)
+            })?,
+            rate: parse_param(&api.far_rate, "far_rate", |rate| Decimal::from_f64(*rate))?,
+        }),
+        "NDF" => Product::Ndf {
+            fixing_date: required_field(&api.fixing_date, "fixing_date", |date| Some(*date))?,
+            settlement_currency: required_field(&api.settlement_currency, "settlement_currency", |s| s.parse().ok())?,
+        },
+        _ => Product::VanillaOption {
+            expiry_date: required_field(&api.expiry_date, "expiry_date", |date| Some(*date))?,
+            cut: required_field(&api.cut, "cut", |s| s.parse::<OptionCut>().ok())?,
+            option_type: required_field(&api.option_type, "option_type", |s| s.parse::<OptionType>().ok())?,
+        },
+    })
+}
+
+/// Currencies must be ISO 4217 codes, anything else is an E1234 naming the field
+fn invalid_currency(field: &str, e: UnknownCurrency) -> AppError {
+    AppError::from_code(ErrCodes::E1234, json!({ "field": field }))
+        .with_tag("trade_details")
+        .with_data(field, json!(e.0))
+}
+
+/// A field that can't be left out, missing is an E1234 just like a value that won't parse
+fn required_field<T, R>(raw: &Option<T>, field: &str, parse: impl Fn(&T) -> Option<R>) -> Result<R, AppError>
+where
+    T: serde::Serialize,
+{
+    parse_param(raw, field, parse)?.ok_or_else(|| {
+        AppError::from_code(ErrCodes::E1234, json!({ "field": field }))
+            .with_tag("trade_details")
+            .with_data("reason", json!("Required"))
+    })
+}
+
+pub fn to_api_trade_details(details: &TradeDetails) -> models::TradeDetails {
+    models::TradeDetails {
+        trading_entity: Some(details.trading_entity.clone()),
+        counterparty: Some(details.counterparty.clone()),
+        direction: Some(details.direction.to_string()), // Ensure Direction: Display
+        notional_currency: Some(details.notional_currency.clone().to_string()),
+        notional_amount: Some(details.notional_amount.to_f64().unwrap()),
+        underlying: Some(details.underlying.iter().map(|c| c.to_string()).collect()),
+        trade_date: Some(details.trade_date),
+        value_date: Some(details.value_date),
+        delivery_date: Some(details.delivery_date),
+        strike: details.strike.map(|d| d.to_f64().unwrap_or(0.0)),
+        tenor: None,
+        product: Some(details.product.name().to_string()),
+        ..to_api_product(&details.product)
+    }
+}
+
+/// Only the fields of the given product are set
+fn to_api_product(product: &Product) -> models::TradeDetails {
+    let mut api = models::TradeDetails::new();
+    match product {
+        Product::Spot | Product::Forward => {}
+        Product::Swap(far) => {
+            api.far_value_date = Some(far.value_date);
+            api.far_notional_amount = far.notional_amount.to_f64();
+            api.far_rate = far.rate.and_then(|rate| rate.to_f64());
+        }
+        Product::Ndf { fixing_date, settlement_currency } => {
+            api.fixing_date = Some(*fixing_date);
+            api.settlement_currency = Some(settlement_currency.to_string());
+        }
+        Product::VanillaOption { expiry_date, cut, option_type } => {
+            api.expiry_date = Some(*expiry_date);
+            api.cut = Some(cut.to_string());
+            api.option_type = Some(option_type.to_string());
+        }
+    }
+    api
+}
+
+/// What the counterparty confirmed, every field is needed to book a trade
+pub fn to_execution_confirmation(api: &models::ExecutionConfirmation) -> Result<ExecutionConfirmation, AppError> {
+    Ok(ExecutionConfirmation {
+        rate: required_field(&api.rate, "rate", |rate| Decimal::from_f64(*rate))?,
+        executed_at: required_field(&api.executed_at, "executed_at", |at| Some(*at))?,
+        counterparty_ref: required_field(&api.counterparty_ref, "counterparty_ref", |r| Some(r.clone()))?,
+        executed_notional: required_field(&api.executed_notional, "executed_notional", |n| Decimal::from_f64(*n))?,
+        far_rate: parse_param(&api.far_rate, "far_rate", |rate| Decimal::from_f64(*rate))?,
     })
 }
 
+pub fn to_api_execution_confirmation(confirmation: &ExecutionConfirmation) -> models::ExecutionConfirmation {
+    models::ExecutionConfirmation {
+        rate: confirmation.rate.to_f64(),
+        executed_at: Some(confirmation.executed_at),
+        counterparty_ref: Some(confirmation.counterparty_ref.clone()),
+        executed_notional: confirmation.executed_notional.to_f64(),
+        far_rate: confirmation.far_rate.and_then(|rate| rate.to_f64()),
+    }
+}
+
 pub fn to_history_response(history: &[TradeEventSnapshot]) -> Result<Vec<models::TradeEvent>, AppError> {
     Ok(history
         .iter()
@@ -57,18 +194,189 @@ pub fn to_history_response(history: &[TradeEventSnapshot]) -> Result<Vec<models:
             user_id: Some(s.user_id.clone()),
             timestamp: Some(s.timestamp),
             state: Some(s.to_state.to_string()), // Ensure TradeState: Display
-            details: Some(models::TradeDetails {
-                trading_entity: Some(s.details.trading_entity.clone()),
-                counterparty: Some(s.details.counterparty.clone()),
-                direction: Some(s.details.direction.to_string()), // Ensure Direction: Display
-                notional_currency: Some(s.details.notional_currency.clone().to_string()),
-                notional_amount: Some(s.details.notional_amount.to_f64().unwrap()),
-                underlying: Some(s.details.underlying.iter().map(|c| c.to_string()).collect()),
-                trade_date: Some(s.details.trade_date),
-                value_date: Some(s.details.value_date),
-                delivery_date: Some(s.details.delivery_date),
-                strike: s.details.strike.map(|d| d.to_f64().unwrap_or(0.0)),
-            }),
+            details: Some(to_api_trade_details(&s.details)),
+            comment: s.comment.clone(),
+            reverted_from: s.reverted_from.map(|source| source as i32),
+            execution: s.execution.as_ref().map(to_api_execution_confirmation),
         })
         .collect())
 }
+
+pub fn to_trade_status(state: TradeState) -> models::TradeStatus {
+    models::TradeStatus { state: Some(state.to_string()), ..models::TradeStatus::new() }
+}
+
+/// The trade as it stood at some instant (`GET /trade/{id}?as_of=`)
+pub fn to_trade_status_as_of(snapshot: &TradeEventSnapshot) -> models::TradeStatus {
+    models::TradeStatus {
+        state: Some(snapshot.to_state.to_string()),
+        version: Some(snapshot.snapshot_id as i32),
+        user_id: Some(snapshot.user_id.clone()),
+        timestamp: Some(snapshot.timestamp),
+        details: Some(to_api_trade_details(&snapshot.details)),
+    }
+}
+
+pub fn to_trade_diff(diff: &TradeDiff) -> Result<models::TradeDiff, AppError> {
+    // Each changed field is rendered as { "field": { "from": .., "to": .. } }
+    let differences = diff
+        .differences
+        .iter()
+        .map(|(field, (from, to))| (field.clone(), json!({ "from": from, "to": to })))
+        .collect::<serde_json::Map<_, _>>();
+
+    Ok(models::TradeDiff {
+        trade_id: Some(diff.trade_id.to_string()),
+        from_version: Some(diff.from_version as i32),
+        to_version: Some(diff.to_version as i32),
+        from_reverted_from: diff.from_reverted_from.map(|source| source as i32),
+        to_reverted_from: diff.to_reverted_from.map(|source| source as i32),
+        differences: Some(serde_json::from_value(differences.into()).map_err(AppError::from_error)?),
+    })
+}
+
+pub fn to_error_response(err: &AppError) -> models::ErrorResponse {
+    models::ErrorResponse { code: Some(err.code().to_string()), message: Some(err.message().to_string()) }
+}
+
+pub fn to_trade_id(raw: &str) -> Result<TradeId, AppError> {
+    raw.parse::<TradeId>()
+        .map_err(|_| AppError::from_code(ErrCodes::E1234, json!({ "field": "id" })).with_data("id", json!(raw)))
+}
+
+pub fn to_version(raw: i32, field: &str) -> Result<usize, AppError> {
+    usize::try_from(raw)
+        .map_err(|_| AppError::from_code(ErrCodes::E1234, json!({ "field": field })).with_data(field, json!(raw)))
+}
+
+/// The trade version as a (strong) ETag, e.g. `"3"`
+pub fn to_etag(version: SnapshotId) -> String {
+    format!("\"{version}\"")
+}
+
+/// Reads an `If-Match` header back into a trade version. `*` matches any version, so no check.
+pub fn from_if_match(raw: &str) -> Result<Option<SnapshotId>, AppError> {
+    let tag = raw.trim();
+    if tag == "*" {
+        return Ok(None);
+    }
+
+    let version = tag.strip_prefix("W/").unwrap_or(tag).trim_matches('"');
+    version.parse::<SnapshotId>().map(Some).map_err(|_| {
+        AppError::from_code(ErrCodes::E1234, json!({ "field": "If-Match" })).with_data("if_match", json!(raw))
+    })
+}
+
+/// Query params of `GET /trade` into a trade query, rejecting values that don't parse
+pub fn to_trade_query(params: &models::ListTradesQueryParams) -> Result<TradeQuery, AppError> {
+    Ok(TradeQuery {
+        state: parse_param(&params.state, "state", |s| s.parse::<TradeState>().ok())?,
+        counterparty: params.counterparty.clone(),
+        trading_entity: params.trading_entity.clone(),
+        notional_currency: parse_param(&params.notional_currency, "notional_currency", |s| s.parse().ok())?,
+        direction: parse_param(&params.direction, "direction", |s| Direction::from_str(s))?,
+        requester: params.requester.clone(),
+        created_from: params.created_from,
+        created_to: params.created_to,
+        notional_min: parse_param(&params.notional_min, "notional_min", |n| Decimal::from_f64(*n))?,
+        notional_max: parse_param(&params.notional_max, "notional_max", |n| Decimal::from_f64(*n))?,
+        sort_by: parse_param(&params.sort_by, "sort_by", |s| s.parse().ok())?.unwrap_or_default(),
+        order: parse_param(&params.order, "order", |s| s.parse().ok())?.unwrap_or_default(),
+        limit: parse_param(&params.limit, "limit", |n| usize::try_from(*n).ok())?,
+        cursor: params.cursor.clone(),
+    })
+}
+
+/// Optional param through `parse`, None stays None, a value that won't parse is an E1234
+fn parse_param<T, R>(raw: &Option<T>, field: &str, parse: impl Fn(&T) -> Option<R>) -> Result<Option<R>, AppError>
+where
+    T: serde::Serialize,
+{
+    raw.as_ref()
+        .map(|value| {
+            parse(value).ok_or_else(|| {
+                AppError::from_code(ErrCodes::E1234, json!({ "field": field })).with_data(field, json!(value))
+            })
+        })
+        .transpose()
+}
+
+pub fn to_trade_list_page(page: &TradePage) -> models::TradeListPage {
+    models::TradeListPage {
+        trades: Some(page.trades.iter().map(to_trade_summary).collect()),
+        next_cursor: page.next_cursor.clone(),
+    }
+}
+
+fn to_trade_summary(summary: &TradeSummary) -> models::TradeSummary {
+    models::TradeSummary {
+        trade_id: Some(summary.trade_id.to_string()),
+        state: Some(summary.state.to_string()),
+        requester: Some(summary.requester.clone()),
+        created_at: Some(summary.created_at),
+        version: Some(summary.version as i32),
+        details: Some(to_api_trade_details(&summary.details)),
+    }
+}
+
+/// `mode` of `POST /trade/batch`, all-or-nothing unless asked otherwise
+pub fn to_batch_mode(raw: Option<&str>) -> Result<BatchMode, AppError> {
+    match raw {
+        None | Some("allOrNothing") => Ok(BatchMode::AllOrNothing),
+        Some("bestEffort") => Ok(BatchMode::BestEffort),
+        Some(other) => {
+            Err(AppError::from_code(ErrCodes::E1234, json!({ "field": "mode" })).with_data("mode", json!(other)))
+        }
+    }
+}
+
+/// One command of `POST /trade/batch`, acted on by the user calling. Which fields are needed depends on
+/// the action, e.g. `create` needs only the details, `approve` the trade id.
+pub fn to_trade_command(
+    user_id: &str,
+    index: usize,
+    api: &models::TradeBatchCommand,
+) -> Result<TradeCommand, AppError> {
+    let invalid =
+        |field: &str| AppError::from_code(ErrCodes::E1234, json!({ "field": format!("commands[{index}].{field}") }));
+    let user_id = user_id.to_string();
+    let trade_id = || api.trade_id.as_deref().ok_or_else(|| invalid("tradeId")).and_then(to_trade_id);
+    let details = || api.details.as_ref().ok_or_else(|| invalid("details")).and_then(to_trade_details);
+    let expected_version = api.version.map(|version| to_version(version, "version")).transpose()?;
+    let comment = api.comment.clone();
+
+    Ok(match api.action.as_deref() {
+        Some("create") => TradeCommand::Create { user_id, details: details()? },
+        Some("submit") => TradeCommand::Submit { user_id, trade_id: trade_id()?, expected_version },
+        Some("approve") => TradeCommand::Approve { user_id, trade_id: trade_id()?, expected_version, comment },
+        Some("cancel") => TradeCommand::Cancel { user_id, trade_id: trade_id()?, expected_version, comment },
+        Some("update") => {
+            TradeCommand::Update { user_id, trade_id: trade_id()?, details: details()?, expected_version, comment }
+        }
+        Some("revert") => {
+            let revert_to = api.revert_to.ok_or_else(|| invalid("revertTo"))?;
+            let snapshot_id = to_version(revert_to, "revertTo")?;
+            TradeCommand::Revert { user_id, trade_id: trade_id()?, snapshot_id, expected_version, comment }
+        }
+        Some("send") => TradeCommand::SendToExecute { user_id, trade_id: trade_id()?, expected_version, comment },
+        Some("book") => {
+            let confirmation = api.execution.as_ref().ok_or_else(|| invalid("execution"))?;
+            let confirmation = to_execution_confirmation(confirmation)?;
+            TradeCommand::Book { user_id, trade_id: trade_id()?, expected_version, confirmation }
+        }
+        _ => return Err(invalid("action").with_data("action", json!(api.action))),
+    })
+}
+
+/// One result per command, the trade and its new state or why the command was not applied
+pub fn to_batch_response(results: &[Result<CommandOutcome, AppError>]) -> models::TradeBatchResponse {
+    let to_result = |result: &Result<CommandOutcome, AppError>| match result {
+        Ok(outcome) => models::TradeBatchResult {
+            trade_id: Some(outcome.trade_id.to_string()),
+            state: Some(outcome.state.to_string()),
+            error: None,
+        },
+        Err(err) => models::TradeBatchResult { trade_id: None, state: None, error: Some(to_error_response(err)) },
+    };
+    models::TradeBatchResponse { results: Some(results.iter().map(to_result).collect()) }
+}
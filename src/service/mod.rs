@@ -1,3 +1,4 @@
+pub mod expiry;
 pub mod mapper;
 pub mod trading_service;
 mod trading_utils;
@@ -12,8 +12,17 @@
 //!
 #[allow(dead_code)]
 use app_core::AppError;
+use chrono::{DateTime, Utc};
 use rust_decimal::prelude::*;
-use trade_core::model::{Currency, Direction, TradeDetails, TradeEventSnapshot};
+use trade_core::batch::BatchMode;
+use trade_core::command::{CommandOutcome, TradeCommand};
+use trade_core::model::{
+    Currency, Direction, ExecutionConfirmation, Product, SnapshotId, TradeDetails, TradeEventSnapshot, TradeId,
+    TradeState,
+};
+use trade_core::query::{TradePage, TradeQuery};
+use trade_core::tenor::{Tenor, TenorDates};
+use trade_core::TradeDiff;
 
 use crate::service::trading_utils::history_to_table;
 use crate::state::trading_state::engine;
@@ -21,9 +30,12 @@ use crate::state::trading_state::engine;
 const USER_TRADER_1: &str = "userTrader1";
 const USER_ADMIN_1: &str = "userAdmin1";
 
-pub fn create_trade(user_id: &str, details: TradeDetails) -> Result<String, AppError> {
-    let trade_id = engine().create(user_id, details)?;
-    Ok(trade_id.to_string())
+/// Every command takes the caller's idempotency key, if they sent one (REST `Idempotency-Key`):
+/// a retry under the same key gets the first outcome back instead of running again
+pub fn create_trade(user_id: &str, details: TradeDetails, idempotency_key: Option<&str>) -> Result<String, AppError> {
+    let command = TradeCommand::Create { user_id: user_id.into(), details };
+    let outcome = engine().execute(command, idempotency_key)?;
+    Ok(outcome.trade_id.to_string())
 }
 
 pub fn trade_history(trade_id: u64) -> Result<Vec<TradeEventSnapshot>, AppError> {
@@ -31,6 +43,129 @@ pub fn trade_history(trade_id: u64) -> Result<Vec<TradeEventSnapshot>, AppError>
     Ok(history)
 }
 
+pub fn submit_trade(
+    user_id: &str,
+    trade_id: TradeId,
+    expected_version: Option<SnapshotId>,
+    idempotency_key: Option<&str>,
+) -> Result<(), AppError> {
+    let command = TradeCommand::Submit { user_id: user_id.into(), trade_id, expected_version };
+    run(command, idempotency_key)
+}
+
+/// `comment` is the user's reason for the action, kept in the trade history (REST `X-Comment`)
+pub fn approve_trade(
+    user_id: &str,
+    trade_id: TradeId,
+    expected_version: Option<SnapshotId>,
+    comment: Option<&str>,
+    idempotency_key: Option<&str>,
+) -> Result<(), AppError> {
+    let comment = comment.map(String::from);
+    let command = TradeCommand::Approve { user_id: user_id.into(), trade_id, expected_version, comment };
+    run(command, idempotency_key)
+}
+
+pub fn cancel_trade(
+    user_id: &str,
+    trade_id: TradeId,
+    expected_version: Option<SnapshotId>,
+    comment: Option<&str>,
+    idempotency_key: Option<&str>,
+) -> Result<(), AppError> {
+    let comment = comment.map(String::from);
+    let command = TradeCommand::Cancel { user_id: user_id.into(), trade_id, expected_version, comment };
+    run(command, idempotency_key)
+}
+
+/// `expected_version` is the snapshot the caller based the action on, if they told us (REST `If-Match`).
+/// The other actions take it the same way.
+pub fn update_trade(
+    user_id: &str,
+    trade_id: TradeId,
+    details: TradeDetails,
+    expected_version: Option<SnapshotId>,
+    comment: Option<&str>,
+    idempotency_key: Option<&str>,
+) -> Result<(), AppError> {
+    let comment = comment.map(String::from);
+    let command = TradeCommand::Update { user_id: user_id.into(), trade_id, details, expected_version, comment };
+    run(command, idempotency_key)
+}
+
+/// Brings back the details of an earlier version of the trade, as an update to them would
+pub fn revert_trade(
+    user_id: &str,
+    trade_id: TradeId,
+    snapshot_id: SnapshotId,
+    expected_version: Option<SnapshotId>,
+    comment: Option<&str>,
+    idempotency_key: Option<&str>,
+) -> Result<(), AppError> {
+    let comment = comment.map(String::from);
+    let command = TradeCommand::Revert { user_id: user_id.into(), trade_id, snapshot_id, expected_version, comment };
+    run(command, idempotency_key)
+}
+
+pub fn send_trade(
+    user_id: &str,
+    trade_id: TradeId,
+    expected_version: Option<SnapshotId>,
+    comment: Option<&str>,
+    idempotency_key: Option<&str>,
+) -> Result<(), AppError> {
+    let comment = comment.map(String::from);
+    let command = TradeCommand::SendToExecute { user_id: user_id.into(), trade_id, expected_version, comment };
+    run(command, idempotency_key)
+}
+
+pub fn book_trade(
+    user_id: &str,
+    trade_id: TradeId,
+    confirmation: ExecutionConfirmation,
+    expected_version: Option<SnapshotId>,
+    idempotency_key: Option<&str>,
+) -> Result<(), AppError> {
+    let command = TradeCommand::Book { user_id: user_id.into(), trade_id, expected_version, confirmation };
+    run(command, idempotency_key)
+}
+
+/// Runs the commands in one go, see `TradeEngine::execute_batch` for what each mode guarantees.
+/// There is no idempotency key for a batch, an all-or-nothing batch that failed can simply be sent again.
+pub fn batch_trades(commands: Vec<TradeCommand>, mode: BatchMode) -> Vec<Result<CommandOutcome, AppError>> {
+    engine().execute_batch(commands, mode)
+}
+
+/// Runs a workflow action, the REST layer only wants to know whether it went through
+fn run(command: TradeCommand, idempotency_key: Option<&str>) -> Result<(), AppError> {
+    engine().execute(command, idempotency_key).map(|_| ())
+}
+
+pub fn trade_status(trade_id: TradeId) -> Result<TradeState, AppError> {
+    engine().trade_get_status(trade_id)
+}
+
+pub fn trade_as_of(trade_id: TradeId, at: DateTime<Utc>) -> Result<TradeEventSnapshot, AppError> {
+    engine().trade_as_of(trade_id, at)
+}
+
+pub fn trade_details_with_version(trade_id: TradeId) -> Result<(TradeDetails, SnapshotId), AppError> {
+    engine().trade_details_with_version(trade_id)
+}
+
+pub fn query_trades(query: TradeQuery) -> Result<TradePage, AppError> {
+    engine().query(query)
+}
+
+/// Value and delivery dates for a trade in the currencies, settling at the tenor after `trade_date`
+pub fn tenor_dates(trade_date: DateTime<Utc>, currencies: &[Currency], tenor: Tenor) -> Result<TenorDates, AppError> {
+    engine().tenor_dates(trade_date, currencies, tenor)
+}
+
+pub fn trade_diff(trade_id: TradeId, v1: usize, v2: usize) -> Result<TradeDiff, AppError> {
+    engine().diff(trade_id, v1, v2)
+}
+
 pub(crate) fn trade_hello_world() -> Result<(), AppError> {
     let engine = engine();
 
@@ -48,6 +183,7 @@ pub(crate) fn trade_hello_world() -> Result<(), AppError> {
         value_date: Default::default(),
         delivery_date: Default::default(),
         strike: None,
+        product: Product::Forward,
     };
 
     let trade_one = engine.create(USER_TRADER_1, new_trade)?;
@@ -78,6 +214,7 @@ pub(crate) fn trade_scenario_1() -> Result<(), AppError> {
         value_date: Default::default(),
         delivery_date: Default::default(),
         strike: None,
+        product: Product::Forward,
     };
 
     // Submit the trade
@@ -86,7 +223,7 @@ pub(crate) fn trade_scenario_1() -> Result<(), AppError> {
     sout!("\t -> Trade created with ID: {} and status {:?}", trade_id, trade_status);
 
     // Submit the trade - status should transition to "PendingApproval"
-    engine.submit(USER_TRADER_1, trade_id)?;
+    engine.submit(USER_TRADER_1, trade_id, None)?;
     trade_status = engine.trade_get_status(trade_id)?;
     sout!("\t -> Trade status after submission: {:?}", trade_status);
 
@@ -96,7 +233,7 @@ pub(crate) fn trade_scenario_1() -> Result<(), AppError> {
     sout!("\t -> Notional amount form trade details: {:?}", amount);
 
     // Admin approve the trade - status should transition to "Approved"
-    engine.approve(USER_ADMIN_1, trade_id)?;
+    engine.approve(USER_ADMIN_1, trade_id, None, None)?;
     trade_status = engine.trade_get_status(trade_id)?;
     sout!("\t -> Trade status after approval: {:?}", trade_status);
 
@@ -119,6 +256,7 @@ pub(crate) fn trade_scenario_2() -> Result<(), AppError> {
         value_date: Default::default(),
         delivery_date: Default::default(),
         strike: None,
+        product: Product::Forward,
     };
 
     // Create - Submit the trade into DRAFT
@@ -133,12 +271,12 @@ pub(crate) fn trade_scenario_2() -> Result<(), AppError> {
     // Modify just the amount of the trade
     trade_details.notional_amount = Decimal::from_str("368.02").unwrap();
 
-    engine.update(USER_ADMIN_1, trade_id, trade_details)?;
+    engine.update(USER_ADMIN_1, trade_id, trade_details, None, Some("Amount corrected"))?;
     trade_status = engine.trade_get_status(trade_id)?;
     sout!("\t -> Trade status after update: {:?}", trade_status);
 
     // user 1 Re-approves the trade
-    engine.approve(USER_TRADER_1, trade_id)?;
+    engine.approve(USER_TRADER_1, trade_id, None, None)?;
     trade_status = engine.trade_get_status(trade_id)?;
     sout!("\t -> Trade status after re-approval: {:?}", trade_status);
 
@@ -166,6 +304,7 @@ pub(crate) fn trade_scenario_3() -> Result<(), AppError> {
         value_date: Default::default(),
         delivery_date: Default::default(),
         strike: None,
+        product: Product::Forward,
     };
 
     // Submit the trade
@@ -174,22 +313,29 @@ pub(crate) fn trade_scenario_3() -> Result<(), AppError> {
     sout!("\t -> Trade created with ID: {} and status {:?}", trade_id, trade_status);
 
     // Submit the trade - status should transition to "PendingApproval"
-    engine.submit(USER_TRADER_1, trade_id)?;
+    engine.submit(USER_TRADER_1, trade_id, None)?;
     trade_status = engine.trade_get_status(trade_id)?;
     sout!("\t -> Trade status after submission: {:?}", trade_status);
 
     // Admin approve the trade - status should transition to "Approved"
-    engine.approve(USER_ADMIN_1, trade_id)?;
+    engine.approve(USER_ADMIN_1, trade_id, None, None)?;
     trade_status = engine.trade_get_status(trade_id)?;
     sout!("\t -> Trade status after approval: {:?}", trade_status);
 
     // Send the trade to the counterparty - status should transition to "SentToCounterparty"
-    engine.send_to_execute(USER_ADMIN_1, trade_id)?;
+    engine.send_to_execute(USER_ADMIN_1, trade_id, None, None)?;
     trade_status = engine.trade_get_status(trade_id)?;
     sout!("\t -> Trade status after sending to counterparty: {:?}", trade_status);
 
-    // Execute the trade - status should transition to "Executed"
-    engine.book(USER_TRADER_1, trade_id)?;
+    // Execute the trade at the rate the counterparty confirmed - status should transition to "Executed"
+    let confirmation = ExecutionConfirmation {
+        rate: Decimal::from_str("1.2718").unwrap(),
+        executed_at: Utc::now(),
+        counterparty_ref: "BAR-0001".to_string(),
+        executed_notional: Decimal::from_str("112.62").unwrap(),
+        far_rate: None,
+    };
+    engine.book(USER_TRADER_1, trade_id, None, confirmation)?;
     trade_status = engine.trade_get_status(trade_id)?;
     sout!("\t -> Trade status after execution: {:?}", trade_status);
 
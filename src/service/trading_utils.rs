@@ -13,10 +13,12 @@ pub fn history_to_table(history: Vec<TradeEventSnapshot>) -> Result<Table, AppEr
         "Timestamp",
         "From",
         "To",
+        "Product",
         "Amount",
         "Ccy",
         "Entity",
-        "Counterpty"
+        "Counterpty",
+        "Comment"
     ]);
     for event in history {
         let ts: DateTime<Utc> = DateTime::<Utc>::from(event.timestamp);
@@ -27,10 +29,12 @@ pub fn history_to_table(history: Vec<TradeEventSnapshot>) -> Result<Table, AppEr
             ts.format("%Y-%m-%d %H:%M:%S"),
             format!("{:?}", event.from_state),
             format!("{:?}", event.to_state),
+            event.details.product,
             event.details.notional_amount,
             format!("{:?}", event.details.notional_currency),
             event.details.trading_entity,
             event.details.counterparty,
+            event.comment.unwrap_or_default(),
         ]);
     }
     Ok(table)
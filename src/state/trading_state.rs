@@ -1,17 +1,39 @@
 //! Global Trading Engine State
 //!
-//! This module defines the application-wide trade engine instance, built on top of
-//! an `InMemoryStore` - for now - which uses fine(er)-grained concurrency (`DashMap`) to
-//! allow multiple threads to access and mutate trades in parallel without locking
-//! the entire engine.
+//! This module defines the application-wide trade engine instance. It is built at startup
+//! from the `[engine]` config section (`EngineConfig`), which picks the store behind it:
+//! - `store = "memory"` - `InMemoryStore`, fine(er)-grained concurrency (`DashMap`), lost on restart
+//! - `store = "sqlite"` - `SqliteStore` at `store_path`, survives restarts
+//! - `store = "file"`   - `JournalStore`, append-only journal at `store_path`, compacted into
+//!   a checkpoint every `compact_every` records
+//!
+//! `engine.idempotency_window` (seconds) is how long the outcome of a command sent with an
+//! idempotency key is remembered, 24 hours if not set.
+//!
+//! `engine.calendars` names a directory of holiday calendars (`GBP.csv`, `EUR.ics`, ...), value and
+//! delivery dates then have to be business days in every underlying currency that has one.
+//! A calendar that can't be read stops the app at startup, as does a store that can't be opened.
+//!
+//! The trade workflow comes from the `[workflow]` section (or the file named by `workflow.file`),
+//! otherwise the built-in rules apply. An invalid workflow stops the app at startup.
+//!
+//! Authorization comes from the `[policy]` section: a `RolePolicy` (user roles and which roles
+//! may do what) if there is one, otherwise anyone may do anything.
+//!
+//! Approval quorum (N-eyes) rules and approval limits come from the `[approval]` section, without
+//! them a single approval will do, by anyone. Invalid rules stop the app at startup.
+//!
+//! The `[comments]` section says which actions need a comment (a reason) from the user,
+//! e.g. cancelling. Comments are optional everywhere without it.
 //!
 //! # Design
 //! - The `TradeEngine` wrapped in an `Arc`, so it to be shared across threads.
-//! - No `Mutex` is used at the engine level to avoid global lock bottlenecks.
+//! - No `Mutex` is used at the engine level to avoid global lock bottlenecks,
+//!   the store locks each trade while an action is applied to it.
 //!
 //! # Usage
-//! - Use [`engine()`] to access the global singleton instance of the trade engine.
-//!   It is lazily initialized on first use, and shared across the application.
+//! - Call [`start_engine()`] once at startup, it returns the config or store error if there is one.
+//! - Use [`engine()`] to access the global singleton instance of the trade engine from then on.
 //!
 //! - Use [`engine_instance()`] if you need to create a separate instance (e.g. in unit tests)
 //!   without interfering with the global state.
@@ -23,24 +45,40 @@
 //! let trade_id = engine().create("user1", trade_details)?;
 //! ```
 
-use once_cell::sync::Lazy;
+use app_core::config::{config_section, config_string};
+use app_core::AppError;
+use once_cell::sync::OnceCell;
+use serde_json::json;
+use std::path::Path;
 use std::sync::Arc;
+use trade_core::approval::ApprovalRules;
+use trade_core::calendar::Calendars;
+use trade_core::comments::CommentRules;
 use trade_core::engine::TradeEngine;
-use trade_core::store::InMemoryStore;
+use trade_core::errors::ValidationError;
+use trade_core::policy::RolePolicy;
+use trade_core::store::{InMemoryStore, JournalStore, SqliteStore};
+use trade_core::{StateMachine, WorkflowConfig};
 
-// The Mutex will go more granular at the trade/store level, to allow concurrent access
+use crate::app_config::{EngineConfig, StoreBackend};
+use crate::app_errors::ErrCodes;
+
+// Locking happens per trade inside the store, to allow concurrent access
 // So Arc<> will suffice here
 pub type SharedTradeEngine = Arc<TradeEngine>;
 
-static ENGINE: Lazy<SharedTradeEngine> = Lazy::new(|| {
-    let store = InMemoryStore::new();
-    Arc::new(TradeEngine::new(store))
-});
+static ENGINE: OnceCell<SharedTradeEngine> = OnceCell::new();
+
+/// Builds the global trade engine (and opens its store), once.
+/// No point carrying on without a store, so the app stops on the error this returns.
+pub fn start_engine() -> Result<&'static SharedTradeEngine, AppError> {
+    ENGINE.get_or_try_init(|| build_engine().map(Arc::new))
+}
 
 /// Public access to the global trade engine
-/// We only have one per application
+/// We only have one per application, started by `start_engine` before anything uses it
 pub fn engine() -> &'static SharedTradeEngine {
-    &ENGINE
+    ENGINE.get().expect("The trade engine is used before start_engine()")
 }
 
 // For testing, when we need multiple instances
@@ -49,3 +87,96 @@ pub fn engine_instance() -> SharedTradeEngine {
     let store = InMemoryStore::new();
     Arc::new(TradeEngine::new(store))
 }
+
+/// Builds the engine with the store backend chosen in config (memory if not set)
+fn build_engine() -> Result<TradeEngine, AppError> {
+    let config = config_section::<EngineConfig>("engine")
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(|reason| AppError::from_code(ErrCodes::E2001, json!({ "reason": reason })).with_tag("engine"))?;
+
+    let store_path = config.store_path.clone().unwrap_or_else(|| config.store.default_path().to_string());
+    let mut builder = TradeEngine::builder().state_machine(build_state_machine()?);
+    if let Some(seconds) = config.idempotency_window {
+        builder = builder.idempotency_window(chrono::Duration::seconds(seconds as i64));
+    }
+    if let Some(dir) = &config.calendars {
+        builder = builder.calendars(Calendars::load_dir(dir)?);
+    }
+    if let Some(policy) = build_policy()? {
+        builder = builder.policy(policy);
+    }
+    builder = builder.approval_rules(build_approval_rules()?).comment_rules(build_comment_rules()?);
+
+    let builder = match config.store {
+        StoreBackend::Memory => builder.store(InMemoryStore::new()),
+        StoreBackend::Sqlite => {
+            ensure_parent_dir(&store_path)?;
+            let store = SqliteStore::open(&store_path).map_err(store_open_error)?;
+            builder.store(store)
+        }
+        StoreBackend::File => {
+            ensure_parent_dir(&store_path)?;
+            let store =
+                JournalStore::open(&store_path).map_err(store_open_error)?.with_compact_every(config.compact_every);
+            builder.store(store)
+        }
+    };
+
+    Ok(builder.build())
+}
+
+/// Workflow from its own file if `workflow.file` is set, else from the `[workflow]` section,
+/// else the built-in rules
+fn build_state_machine() -> Result<StateMachine, AppError> {
+    if let Some(path) = config_string("workflow.file") {
+        return Ok(StateMachine::load(path)?);
+    }
+
+    match config_section::<WorkflowConfig>("workflow") {
+        None => Ok(StateMachine::default()),
+        Some(Ok(workflow)) => Ok(StateMachine::from_config(workflow)?),
+        Some(Err(reason)) => Err(ValidationError::InvalidWorkflow(reason).into()),
+    }
+}
+
+/// Role based policy from the `[policy]` section, None if there isn't one
+fn build_policy() -> Result<Option<RolePolicy>, AppError> {
+    config_section::<RolePolicy>("policy")
+        .transpose()
+        .map_err(|reason| AppError::from_code(ErrCodes::E2003, json!({ "reason": reason })).with_tag("engine"))
+}
+
+/// Quorum rules and approval limits from the `[approval]` section, none if there isn't one
+fn build_approval_rules() -> Result<ApprovalRules, AppError> {
+    match config_section::<ApprovalRules>("approval") {
+        None => Ok(ApprovalRules::default()),
+        Some(Ok(rules)) => {
+            rules.validate()?;
+            Ok(rules)
+        }
+        Some(Err(reason)) => Err(ValidationError::InvalidApprovalRules(reason).into()),
+    }
+}
+
+/// Mandatory comments from the `[comments]` section, none if there isn't one
+fn build_comment_rules() -> Result<CommentRules, AppError> {
+    config_section::<CommentRules>("comments")
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(|reason| AppError::from_code(ErrCodes::E2004, json!({ "reason": reason })).with_tag("engine"))
+}
+
+/// Make sure the directory for a store file exists, e.g. `./data`
+fn ensure_parent_dir(store_path: &str) -> Result<(), AppError> {
+    match Path::new(store_path).parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => {
+            std::fs::create_dir_all(dir).map_err(|e| store_open_error(e.to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn store_open_error(reason: String) -> AppError {
+    AppError::from_code(ErrCodes::E2002, json!({ "reason": reason })).with_tag("engine")
+}